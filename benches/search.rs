@@ -0,0 +1,84 @@
+// Search and cost recalculation on generated graphs, run with `cargo bench`
+
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use egui::{Pos2, Rect, Vec2};
+use egui_snarl::{NodeId, Snarl};
+
+use dijkstra::{
+    CostScale, DijkstraNode, algorithm, endpoints, generate, model, recalculate_costs,
+    search_graph, wire_costs,
+};
+
+const SIZES: [usize; 4] = [100, 500, 1_000, 10_000];
+// Roughly a Distance node as drawn, the costs only need plausible wire lengths
+const NODE_SIZE: Vec2 = Vec2::new(120.0, 60.0);
+const SPACING: f32 = 150.0;
+
+// The same generators as the dialogs, a square grid and a random graph with about four
+// connections per node
+fn graphs(size: usize) -> [(&'static str, model::Graph); 2] {
+    let side = (size as f32).sqrt().round() as usize;
+    let area = Vec2::splat(side as f32 * SPACING);
+    [
+        (
+            "grid",
+            generate::grid(side, side, SPACING, true, Pos2::ZERO),
+        ),
+        (
+            "random",
+            generate::random(size, 4.0 / size as f32, area, 42, Pos2::ZERO),
+        ),
+    ]
+}
+
+fn rects(snarl: &Snarl<DijkstraNode>) -> HashMap<NodeId, Rect> {
+    snarl
+        .nodes_pos_ids()
+        .map(|(id, pos, _)| (id, Rect::from_min_size(pos, NODE_SIZE)))
+        .collect()
+}
+
+fn bench_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("shortest_path");
+    for size in SIZES {
+        for (name, graph) in graphs(size) {
+            let mut snarl = graph.to_snarl().snarl;
+            let (adjacency, rects) = (wire_costs(&snarl), rects(&snarl));
+            recalculate_costs(&mut snarl, &adjacency, &rects, CostScale::default());
+            let search = search_graph(&snarl, &wire_costs(&snarl));
+            let (start, finish) = endpoints(&snarl).expect("generated graphs have endpoints");
+            group.bench_with_input(BenchmarkId::new(name, size), &search, |b, search| {
+                b.iter(|| algorithm::shortest_path(black_box(search), start, finish))
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_costs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("recalculate_costs");
+    for size in SIZES {
+        for (name, graph) in graphs(size) {
+            let snarl = graph.to_snarl().snarl;
+            let adjacency = wire_costs(&snarl);
+            let rects = rects(&snarl);
+            group.bench_function(BenchmarkId::new(name, size), |b| {
+                // Fresh costs every time, so each pass writes them all like after a load
+                b.iter_batched(
+                    || snarl.clone(),
+                    |mut snarl| {
+                        recalculate_costs(&mut snarl, &adjacency, &rects, CostScale::default())
+                    },
+                    BatchSize::LargeInput,
+                )
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_search, bench_costs);
+criterion_main!(benches);
@@ -21,6 +21,8 @@ pkgs.mkShell {
     gdk-pixbuf
     atk
     pango
+    # `trunk serve` for the web build
+    trunk
   ];
 
   LD_LIBRARY_PATH = libPath;
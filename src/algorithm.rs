@@ -0,0 +1,464 @@
+// Dijkstra's algorithm on a plain weighted graph, independent of the editor
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+
+use egui_snarl::NodeId;
+
+use crate::trace::TraceEntry;
+
+// Costs are whole numbers unless the graph keeps fractional ones, see `CostScale::fractional`
+pub type Cost = f64;
+
+// Whole costs are shown as they are, fractional ones to one decimal place
+pub fn format_cost(cost: Cost) -> String {
+    if cost.fract() == 0.0 {
+        format!("{}", cost)
+    } else {
+        format!("{:.1}", cost)
+    }
+}
+
+// Directed graph with the cost of every connection
+#[derive(Clone, Default, Debug)]
+pub struct Graph {
+    pub nodes: Vec<NodeId>,
+    // Outgoing connections of each node with their costs
+    pub edges: HashMap<NodeId, Vec<(NodeId, Cost)>>,
+}
+
+impl Graph {
+    pub fn add_node(&mut self, node: NodeId) {
+        self.nodes.push(node);
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, cost: Cost) {
+        self.edges.entry(from).or_default().push((to, cost));
+    }
+
+    fn contains(&self, node: NodeId) -> bool {
+        self.nodes.contains(&node)
+    }
+}
+
+#[derive(Debug)]
+pub struct PathResult {
+    pub path: Vec<NodeId>,
+    pub total: Cost,
+    // Distance from the start to every node, infinite for unreachable ones
+    pub distances: HashMap<NodeId, Cost>,
+    // Previous node on the shortest route to every reached node except the start
+    pub predecessors: HashMap<NodeId, NodeId>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PathError {
+    UnknownNode(NodeId),
+    NoPath,
+    Cancelled,
+}
+
+// Shared with a search running elsewhere, which counts the settled nodes into it and stops
+// once `cancelled` is set
+#[derive(Debug, Default)]
+pub struct Progress {
+    pub settled: AtomicUsize,
+    pub cancelled: AtomicBool,
+}
+
+// Settled nodes between two updates of the progress
+const PROGRESS_INTERVAL: usize = 256;
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathError::UnknownNode(node) => write!(f, "Node {} isn't in the graph", node.0),
+            PathError::NoPath => write!(f, "No path found"),
+            PathError::Cancelled => write!(f, "Search cancelled"),
+        }
+    }
+}
+
+// Priority queue element for Dijkstra's algorithm
+#[derive(Copy, Clone, Debug)]
+struct State {
+    cost: Cost,
+    node: NodeId,
+}
+
+// Floats aren't `Ord`, the heap orders costs by their total order instead, which agrees
+// with `<` for the finite, non-negative costs of a graph
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reverse order for min-heap
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| self.node.cmp(&other.node))
+    }
+}
+
+impl PartialEq for State {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for State {}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub fn shortest_path(
+    graph: &Graph,
+    start: NodeId,
+    finish: NodeId,
+) -> Result<PathResult, PathError> {
+    search(graph, start, finish, None, None)
+}
+
+// Same as `shortest_path`, also recording every step of the search
+pub fn shortest_path_traced(
+    graph: &Graph,
+    start: NodeId,
+    finish: NodeId,
+    trace: &mut Vec<TraceEntry>,
+) -> Result<PathResult, PathError> {
+    search(graph, start, finish, Some(trace), None)
+}
+
+// Same as `shortest_path`, reporting to `progress` and recording the steps into `trace`
+pub fn shortest_path_watched(
+    graph: &Graph,
+    start: NodeId,
+    finish: NodeId,
+    trace: Option<&mut Vec<TraceEntry>>,
+    progress: &Progress,
+) -> Result<PathResult, PathError> {
+    search(graph, start, finish, trace, Some(progress))
+}
+
+fn search(
+    graph: &Graph,
+    start: NodeId,
+    finish: NodeId,
+    mut trace: Option<&mut Vec<TraceEntry>>,
+    progress: Option<&Progress>,
+) -> Result<PathResult, PathError> {
+    for node in [start, finish] {
+        if !graph.contains(node) {
+            return Err(PathError::UnknownNode(node));
+        }
+    }
+
+    let mut dist: HashMap<NodeId, Cost> = HashMap::new();
+    let mut prev: HashMap<NodeId, NodeId> = HashMap::new();
+    let mut priority_queue = BinaryHeap::new();
+
+    // Initialize distances to infinity
+    for node in &graph.nodes {
+        dist.insert(*node, Cost::INFINITY);
+    }
+
+    // Distance to start node is 0
+    dist.insert(start, 0.0);
+    priority_queue.push(State {
+        cost: 0.0,
+        node: start,
+    });
+
+    // Settle every node rather than stopping at Finish, so all distances are final
+    let mut settled = 0;
+    while let Some(State { cost, node }) = priority_queue.pop() {
+        // Skip if we already found a better path
+        if cost > dist[&node] {
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.push(TraceEntry::SkippedStale { node, cost });
+            }
+            continue;
+        }
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.push(TraceEntry::Settled { node, dist: cost });
+        }
+        settled += 1;
+        if let Some(progress) = progress
+            && settled % PROGRESS_INTERVAL == 0
+        {
+            progress.settled.store(settled, AtomicOrdering::Relaxed);
+            if progress.cancelled.load(AtomicOrdering::Relaxed) {
+                return Err(PathError::Cancelled);
+            }
+        }
+
+        for &(remote, edge_cost) in graph.edges.get(&node).into_iter().flatten() {
+            let Some(&previous) = dist.get(&remote) else {
+                continue;
+            };
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.push(TraceEntry::Relaxed {
+                    from: node,
+                    to: remote,
+                    dist: cost,
+                    cost: edge_cost,
+                    previous,
+                });
+            }
+
+            let next = State {
+                cost: cost + edge_cost,
+                node: remote,
+            };
+
+            // If we found a better path
+            if next.cost < previous {
+                dist.insert(remote, next.cost);
+                prev.insert(remote, node);
+                priority_queue.push(next);
+            }
+        }
+    }
+
+    if let Some(progress) = progress {
+        progress.settled.store(settled, AtomicOrdering::Relaxed);
+    }
+
+    // Reconstruct the path if one exists
+    if !prev.contains_key(&finish) && finish != start {
+        return Err(PathError::NoPath);
+    }
+    let mut path = vec![finish];
+    let mut current = finish;
+    while current != start {
+        current = prev[&current];
+        path.push(current);
+    }
+    path.reverse();
+
+    Ok(PathResult {
+        path,
+        total: dist[&finish],
+        distances: dist,
+        predecessors: prev,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn graph(nodes: usize, edges: &[(usize, usize, Cost)]) -> Graph {
+        let mut graph = Graph::default();
+        for node in 0..nodes {
+            graph.add_node(NodeId(node));
+        }
+        for (from, to, cost) in edges {
+            graph.add_edge(NodeId(*from), NodeId(*to), *cost);
+        }
+        graph
+    }
+
+    fn ids(nodes: &[usize]) -> Vec<NodeId> {
+        nodes.iter().map(|node| NodeId(*node)).collect()
+    }
+
+    #[test]
+    fn finds_the_cheapest_path() {
+        // The direct connection costs more than the detour
+        let graph = graph(4, &[(0, 3, 10.0), (0, 1, 2.0), (1, 2, 3.0), (2, 3, 1.0)]);
+        let result = shortest_path(&graph, NodeId(0), NodeId(3)).unwrap();
+        assert_eq!(result.path, ids(&[0, 1, 2, 3]));
+        assert_eq!(result.total, 6.0);
+        assert_eq!(result.distances[&NodeId(2)], 5.0);
+        assert_eq!(result.predecessors[&NodeId(3)], NodeId(2));
+    }
+
+    #[test]
+    fn reports_missing_paths() {
+        let graph = graph(3, &[(0, 1, 1.0), (2, 1, 1.0)]);
+        assert_eq!(
+            shortest_path(&graph, NodeId(0), NodeId(2)).unwrap_err(),
+            PathError::NoPath
+        );
+        // Connections only lead one way
+        assert_eq!(
+            shortest_path(&graph, NodeId(1), NodeId(0)).unwrap_err(),
+            PathError::NoPath
+        );
+    }
+
+    #[test]
+    fn start_can_be_the_finish() {
+        let graph = graph(2, &[(0, 1, 4.0)]);
+        let result = shortest_path(&graph, NodeId(0), NodeId(0)).unwrap();
+        assert_eq!(result.path, ids(&[0]));
+        assert_eq!(result.total, 0.0);
+        // The other nodes are still settled
+        assert_eq!(result.distances[&NodeId(1)], 4.0);
+    }
+
+    #[test]
+    fn rejects_unknown_nodes() {
+        let graph = graph(2, &[(0, 1, 1.0)]);
+        assert_eq!(
+            shortest_path(&graph, NodeId(5), NodeId(1)).unwrap_err(),
+            PathError::UnknownNode(NodeId(5))
+        );
+        assert_eq!(
+            shortest_path(&graph, NodeId(0), NodeId(7)).unwrap_err(),
+            PathError::UnknownNode(NodeId(7))
+        );
+        // Connections to nodes outside the graph are ignored
+        let mut graph = graph;
+        graph.add_edge(NodeId(0), NodeId(9), 0.0);
+        assert_eq!(
+            shortest_path(&graph, NodeId(0), NodeId(1)).unwrap().total,
+            1.0
+        );
+    }
+
+    #[test]
+    fn follows_free_connections() {
+        let graph = graph(4, &[(0, 1, 0.0), (1, 2, 0.0), (2, 3, 0.0), (0, 3, 1.0)]);
+        let result = shortest_path(&graph, NodeId(0), NodeId(3)).unwrap();
+        assert_eq!(result.path, ids(&[0, 1, 2, 3]));
+        assert_eq!(result.total, 0.0);
+    }
+
+    #[test]
+    fn takes_the_cheapest_parallel_connection() {
+        let graph = graph(2, &[(0, 1, 9.0), (0, 1, 3.0), (0, 1, 5.0)]);
+        let result = shortest_path(&graph, NodeId(0), NodeId(1)).unwrap();
+        assert_eq!(result.path, ids(&[0, 1]));
+        assert_eq!(result.total, 3.0);
+    }
+
+    #[test]
+    fn large_costs_add_up_without_overflowing() {
+        let large = Cost::from(i32::MAX - 1);
+        let graph = graph(
+            4,
+            &[(0, 1, large), (1, 2, large), (2, 3, large), (0, 3, 1.0)],
+        );
+        let result = shortest_path(&graph, NodeId(0), NodeId(2)).unwrap();
+        assert_eq!(result.path, ids(&[0, 1, 2]));
+        assert_eq!(result.total, 2.0 * large);
+        // A cheap connection still beats the long way around
+        let result = shortest_path(&graph, NodeId(0), NodeId(3)).unwrap();
+        assert_eq!(result.path, ids(&[0, 3]));
+        assert!(result.distances.values().all(|dist| dist.is_finite()));
+        assert_eq!(result.distances[&NodeId(2)], 2.0 * large);
+    }
+
+    fn popped(costs: &[(Cost, usize)]) -> Vec<(Cost, usize)> {
+        let mut heap: BinaryHeap<State> = costs
+            .iter()
+            .map(|(cost, node)| State {
+                cost: *cost,
+                node: NodeId(*node),
+            })
+            .collect();
+        std::iter::from_fn(|| heap.pop())
+            .map(|state| (state.cost, state.node.0))
+            .collect()
+    }
+
+    #[test]
+    fn heap_pops_the_cheapest_fraction_first() {
+        // Truncated these would all be 1 or 2 and tie
+        assert_eq!(
+            popped(&[(1.9, 0), (2.1, 1), (1.2, 2), (1.25, 3)]),
+            [(1.2, 2), (1.25, 3), (1.9, 0), (2.1, 1)]
+        );
+    }
+
+    #[test]
+    fn heap_breaks_ties_by_node() {
+        assert_eq!(
+            popped(&[(3.0, 1), (3.0, 4), (1.0, 2), (3.0, 0)]),
+            [(1.0, 2), (3.0, 4), (3.0, 1), (3.0, 0)]
+        );
+        let state = |node| State {
+            cost: 3.0,
+            node: NodeId(node),
+        };
+        assert_ne!(state(1), state(4));
+        assert_eq!(state(1), state(1));
+    }
+
+    #[test]
+    fn negative_zero_counts_as_free() {
+        // The total order puts -0.0 before 0.0, the heap stays consistent either way
+        let popped = popped(&[(0.0, 0), (-0.0, 1), (0.5, 2)]);
+        assert_eq!(
+            popped.iter().map(|(_, node)| *node).collect::<Vec<_>>(),
+            [1, 0, 2]
+        );
+        let graph = graph(3, &[(0, 1, -0.0), (1, 2, 0.0), (0, 2, 0.5)]);
+        let result = shortest_path(&graph, NodeId(0), NodeId(2)).unwrap();
+        assert_eq!(result.path, ids(&[0, 1, 2]));
+        assert_eq!(result.total, 0.0);
+    }
+
+    // Up to 12 nodes with up to 30 connections between them, costs are whole so sums are exact
+    fn random_graph() -> impl Strategy<Value = (usize, Vec<(usize, usize, Cost)>)> {
+        (1..12usize).prop_flat_map(|nodes| {
+            let edge = (0..nodes, 0..nodes, (0..50u8).prop_map(Cost::from));
+            (Just(nodes), proptest::collection::vec(edge, 0..30))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_petgraph(
+            (nodes, edges) in random_graph(),
+            start in any::<prop::sample::Index>(),
+            finish in any::<prop::sample::Index>(),
+        ) {
+            let (start, finish) = (start.index(nodes), finish.index(nodes));
+            let mut reference = petgraph::Graph::<(), Cost>::new();
+            let indices: Vec<_> = (0..nodes).map(|_| reference.add_node(())).collect();
+            for (from, to, cost) in &edges {
+                reference.add_edge(indices[*from], indices[*to], *cost);
+            }
+            let expected = petgraph::algo::dijkstra(&reference, indices[start], None, |edge| {
+                *edge.weight()
+            });
+
+            let graph = graph(nodes, &edges);
+            match shortest_path(&graph, NodeId(start), NodeId(finish)) {
+                Ok(result) => {
+                    prop_assert_eq!(Some(&result.total), expected.get(&indices[finish]));
+                    prop_assert_eq!(result.path.first(), Some(&NodeId(start)));
+                    prop_assert_eq!(result.path.last(), Some(&NodeId(finish)));
+                    // The cheapest connection between each pair of the path adds up to the total
+                    let mut total = 0.0;
+                    for pair in result.path.windows(2) {
+                        let cost = edges
+                            .iter()
+                            .filter(|(from, to, _)| NodeId(*from) == pair[0] && NodeId(*to) == pair[1])
+                            .map(|(_, _, cost)| *cost)
+                            .reduce(Cost::min);
+                        prop_assert!(cost.is_some());
+                        total += cost.unwrap();
+                    }
+                    prop_assert_eq!(total, result.total);
+                    for (node, index) in indices.iter().enumerate() {
+                        let dist = result.distances[&NodeId(node)];
+                        prop_assert_eq!(expected.get(index).copied(), dist.is_finite().then_some(dist));
+                    }
+                }
+                Err(error) => {
+                    prop_assert_eq!(error, PathError::NoPath);
+                    prop_assert!(!expected.contains_key(&indices[finish]));
+                }
+            }
+        }
+    }
+}
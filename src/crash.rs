@@ -0,0 +1,68 @@
+// Panic hook for builds without a console: the panic is written to a log file and a message
+// box says where, instead of the window just closing
+
+use std::backtrace::Backtrace;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::report::format_timestamp;
+
+// Next to the executable, or the platform data directory when that isn't writable
+fn log_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+    {
+        paths.push(dir.join("dijkstra-crash.log"));
+    }
+    if let Some(dir) = dirs::data_local_dir() {
+        paths.push(dir.join("dijkstra_visualization").join("crash.log"));
+    }
+    paths
+}
+
+// Every step may fail on a read-only filesystem, the next location is tried then
+fn write_log(report: &str) -> Option<PathBuf> {
+    log_paths().into_iter().find(|path| {
+        path.parent()
+            .is_none_or(|dir| std::fs::create_dir_all(dir).is_ok())
+            && std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| file.write_all(report.as_bytes()))
+                .is_ok()
+    })
+}
+
+pub fn install() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        // Still printed for anyone running from a terminal
+        default_hook(info);
+        let report = format!(
+            "{} {}\n\n{}\n\n",
+            format_timestamp(web_time::SystemTime::now()),
+            info,
+            Backtrace::force_capture()
+        );
+        let message = match write_log(&report) {
+            Some(path) => format!(
+                "The app crashed: {}\n\nDetails were written to {}",
+                info.payload_as_str().unwrap_or("unknown error"),
+                path.display()
+            ),
+            None => format!(
+                "The app crashed: {}\n\nNo crash log could be written.",
+                info.payload_as_str().unwrap_or("unknown error")
+            ),
+        };
+        rfd::MessageDialog::new()
+            .set_level(rfd::MessageLevel::Error)
+            .set_title("Dijkstra visualization crashed")
+            .set_description(message)
+            .set_buttons(rfd::MessageButtons::Ok)
+            .show();
+    }));
+}
@@ -0,0 +1,478 @@
+// Graphviz DOT export and a parser for the subset of DOT needed to import graphs back
+
+use std::collections::HashMap;
+
+use egui::Pos2;
+
+use crate::algorithm::{Cost, format_cost};
+use crate::model::{Graph, Role};
+
+// Quote a string for use as a DOT attribute value
+fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+// Write the graph as a Graphviz digraph, positions are kept so `neato -n` reproduces the layout
+pub fn to_dot(graph: &Graph) -> String {
+    let mut dot = String::from("digraph dijkstra {\n");
+    for node in &graph.nodes {
+        let kind = match node.role {
+            Role::Start => "start",
+            Role::Distance => "distance",
+            Role::Finish => "finish",
+        };
+        let pos = node.pos.unwrap_or_default();
+        // Graphviz has the y axis pointing up, subtracting keeps a 0 from turning into -0
+        dot.push_str(&format!(
+            "    n{} [label={}, type={}, pos=\"{},{}\"];\n",
+            node.id.0,
+            quote(&node.display_label()),
+            kind,
+            pos.x,
+            0.0 - pos.y
+        ));
+    }
+    for edge in &graph.edges {
+        match edge.cost {
+            Some(cost) => dot.push_str(&format!(
+                "    n{} -> n{} [label=\"{}\", weight={}];\n",
+                edge.from.0,
+                edge.to.0,
+                format_cost(cost),
+                cost
+            )),
+            None => dot.push_str(&format!("    n{} -> n{};\n", edge.from.0, edge.to.0)),
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Id(String),
+    // `->` or `--`
+    Edge,
+    OpenBrace,
+    CloseBrace,
+    OpenBracket,
+    CloseBracket,
+    Equals,
+    Colon,
+    // `;` or `,`
+    Separator,
+}
+
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '\n' => {
+                line += 1;
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            '{' => Token::OpenBrace,
+            '}' => Token::CloseBrace,
+            '[' => Token::OpenBracket,
+            ']' => Token::CloseBracket,
+            '=' => Token::Equals,
+            ':' => Token::Colon,
+            ';' | ',' => Token::Separator,
+            // Quoted strings may be joined with `+`
+            '+' => continue,
+            '#' => {
+                while chars.next_if(|c| *c != '\n').is_some() {}
+                continue;
+            }
+            '/' if chars.next_if_eq(&'/').is_some() => {
+                while chars.next_if(|c| *c != '\n').is_some() {}
+                continue;
+            }
+            '/' if chars.next_if_eq(&'*').is_some() => {
+                let start = line;
+                let mut previous = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if previous == '*' => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            previous = c;
+                        }
+                        None => return Err(format!("Line {}: unterminated comment", start)),
+                    }
+                }
+                continue;
+            }
+            '-' if chars.next_if(|c| *c == '>' || *c == '-').is_some() => Token::Edge,
+            '"' => {
+                let start = line;
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => value.push('\n'),
+                            Some('"') => value.push('"'),
+                            Some('\\') => value.push('\\'),
+                            Some('\n') => line += 1,
+                            Some(c) => {
+                                value.push('\\');
+                                value.push(c);
+                            }
+                            None => {}
+                        },
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            value.push(c);
+                        }
+                        None => return Err(format!("Line {}: unterminated string", start)),
+                    }
+                }
+                Token::Id(value)
+            }
+            '<' => return Err(format!("Line {}: HTML labels are not supported", line)),
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let mut value = String::from(c);
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_' || *c == '.')
+                {
+                    value.push(c);
+                }
+                Token::Id(value)
+            }
+            c => return Err(format!("Line {}: unexpected character '{}'", line, c)),
+        };
+        tokens.push((token, line));
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    index: usize,
+    names: HashMap<String, usize>,
+    // Kind set with a `type` attribute, otherwise guessed from the name when finishing
+    kinds: Vec<Option<Role>>,
+    graph: Graph,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let (token, _) = self.tokens.get_mut(self.index)?;
+        self.index += 1;
+        Some(std::mem::replace(token, Token::Separator))
+    }
+
+    // Line of the token about to be read, or of the last one at the end of the file
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.index)
+            .or(self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn expect_id(&mut self, what: &str) -> Result<String, String> {
+        let line = self.line();
+        match self.next() {
+            Some(Token::Id(id)) => Ok(id),
+            Some(token) => Err(format!(
+                "Line {}: expected {}, found {:?}",
+                line, what, token
+            )),
+            None => Err(format!("Line {}: expected {}", line, what)),
+        }
+    }
+
+    // Any number of `[a=b, c=d]` lists
+    fn attributes(&mut self) -> Result<Vec<(String, String)>, String> {
+        let mut attributes = Vec::new();
+        while self.peek() == Some(&Token::OpenBracket) {
+            self.next();
+            loop {
+                match self.peek() {
+                    Some(Token::CloseBracket) => {
+                        self.next();
+                        break;
+                    }
+                    Some(Token::Separator) => {
+                        self.next();
+                    }
+                    _ => {
+                        let key = self.expect_id("attribute name")?;
+                        let line = self.line();
+                        if self.next() != Some(Token::Equals) {
+                            return Err(format!("Line {}: expected '=' after '{}'", line, key));
+                        }
+                        let value = self.expect_id("attribute value")?;
+                        attributes.push((key, value));
+                    }
+                }
+            }
+        }
+        Ok(attributes)
+    }
+
+    // Ports (`node:port`) don't matter for the graph, skip them
+    fn skip_port(&mut self) -> Result<(), String> {
+        while self.peek() == Some(&Token::Colon) {
+            self.next();
+            self.expect_id("port")?;
+        }
+        Ok(())
+    }
+
+    fn node(&mut self, name: &str) -> usize {
+        if let Some(idx) = self.names.get(name) {
+            return *idx;
+        }
+        let idx = self.graph.add_node(Role::Distance, name.to_string());
+        self.kinds.push(None);
+        self.names.insert(name.to_string(), idx);
+        idx
+    }
+
+    fn node_statement(
+        &mut self,
+        name: &str,
+        attributes: &[(String, String)],
+        line: usize,
+    ) -> Result<(), String> {
+        let idx = self.node(name);
+        for (key, value) in attributes {
+            match key.as_str() {
+                "label" => self.graph.nodes[idx].label = value.clone(),
+                "type" => {
+                    self.kinds[idx] = Some(match value.to_lowercase().as_str() {
+                        "start" => Role::Start,
+                        "finish" => Role::Finish,
+                        "distance" => Role::Distance,
+                        _ => return Err(format!("Line {}: unknown node type '{}'", line, value)),
+                    })
+                }
+                "pos" => {
+                    let coords: Vec<f32> = value
+                        .trim_end_matches('!')
+                        .split(',')
+                        .map(|coord| coord.trim().parse())
+                        .collect::<Result<_, _>>()
+                        .map_err(|_| format!("Line {}: invalid pos '{}'", line, value))?;
+                    let [x, y] = coords[..] else {
+                        return Err(format!("Line {}: invalid pos '{}'", line, value));
+                    };
+                    // Graphviz has the y axis pointing up
+                    self.graph.nodes[idx].pos = Some(Pos2::new(x, -y));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn edge_statement(
+        &mut self,
+        from: &str,
+        to: &str,
+        attributes: &[(String, String)],
+        directed: bool,
+        line: usize,
+    ) -> Result<(), String> {
+        let (from, to) = (self.node(from), self.node(to));
+        // `weight` wins over `label` when both are given
+        let value = attributes
+            .iter()
+            .find(|(key, _)| key == "weight")
+            .or_else(|| attributes.iter().find(|(key, _)| key == "label"));
+        let cost = match value {
+            Some((_, value)) => {
+                let cost: Cost = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Line {}: invalid cost '{}'", line, value))?;
+                if cost < 1.0 {
+                    return Err(format!("Line {}: cost must be at least 1", line));
+                }
+                Some(cost)
+            }
+            None => None,
+        };
+        self.graph.add_edge(from, to, cost);
+        if !directed {
+            self.graph.add_edge(to, from, cost);
+        }
+        Ok(())
+    }
+
+    fn statement(&mut self, id: String, directed: bool) -> Result<(), String> {
+        let line = self.line();
+        match id.as_str() {
+            "node" | "edge" | "graph" if self.peek() == Some(&Token::OpenBracket) => {
+                self.attributes()?;
+                return Ok(());
+            }
+            "subgraph" => return Err(format!("Line {}: subgraphs are not supported", line)),
+            _ => {}
+        }
+        // Graph attribute like `rankdir=LR`
+        if self.peek() == Some(&Token::Equals) {
+            self.next();
+            self.expect_id("attribute value")?;
+            return Ok(());
+        }
+
+        self.skip_port()?;
+        let mut chain = vec![id];
+        while self.peek() == Some(&Token::Edge) {
+            self.next();
+            chain.push(self.expect_id("node name")?);
+            self.skip_port()?;
+        }
+        let attributes = self.attributes()?;
+        if let [name] = &chain[..] {
+            return self.node_statement(name, &attributes, line);
+        }
+        for pair in chain.windows(2) {
+            self.edge_statement(&pair[0], &pair[1], &attributes, directed, line)?;
+        }
+        Ok(())
+    }
+}
+
+// Parse node statements with `label`, `type` and `pos`, and edge statements with a
+// `weight` or `label` cost. Nodes without a type named "start" or "finish" become those.
+pub fn parse(text: &str) -> Result<Graph, String> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        index: 0,
+        names: HashMap::new(),
+        kinds: Vec::new(),
+        graph: Graph::default(),
+    };
+
+    let mut keyword = parser.expect_id("'digraph' or 'graph'")?;
+    if keyword.eq_ignore_ascii_case("strict") {
+        keyword = parser.expect_id("'digraph' or 'graph'")?;
+    }
+    let directed = match keyword.to_lowercase().as_str() {
+        "digraph" => true,
+        "graph" => false,
+        _ => {
+            return Err(format!(
+                "Line {}: expected 'digraph' or 'graph', found '{}'",
+                parser.line(),
+                keyword
+            ));
+        }
+    };
+    if let Some(Token::Id(_)) = parser.peek() {
+        parser.next();
+    }
+    let line = parser.line();
+    if parser.next() != Some(Token::OpenBrace) {
+        return Err(format!("Line {}: expected '{{'", line));
+    }
+
+    loop {
+        let line = parser.line();
+        match parser.next() {
+            Some(Token::CloseBrace) => break,
+            Some(Token::Separator) => {}
+            Some(Token::Id(id)) => parser.statement(id, directed)?,
+            Some(token) => return Err(format!("Line {}: unexpected {:?}", line, token)),
+            None => return Err(format!("Line {}: missing closing '}}'", line)),
+        }
+    }
+
+    let mut names = vec![String::new(); parser.graph.nodes.len()];
+    for (name, idx) in parser.names {
+        names[idx] = name;
+    }
+    let mut graph = parser.graph;
+    for ((node, kind), name) in graph.nodes.iter_mut().zip(parser.kinds).zip(names) {
+        let named = |target: &str| {
+            name.eq_ignore_ascii_case(target) || node.label.eq_ignore_ascii_case(target)
+        };
+        node.role = match kind {
+            Some(kind) => kind,
+            None if named("start") => Role::Start,
+            None if named("finish") => Role::Finish,
+            None => Role::Distance,
+        };
+    }
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Start and Finish with labels that need escaping around an unlabeled Distance node
+    fn graph() -> Graph {
+        let mut graph = Graph::default();
+        graph.add_node(Role::Start, "Home \"A\"".to_string());
+        graph.add_node(Role::Distance, String::new());
+        graph.add_node(Role::Finish, "C:\\end\nline".to_string());
+        for (idx, pos) in [(0.0, 0.0), (150.0, 50.0), (300.0, 0.0)]
+            .into_iter()
+            .enumerate()
+        {
+            graph.nodes[idx].pos = Some(Pos2::new(pos.0, pos.1));
+        }
+        graph.add_edge(0, 1, Some(12.0));
+        graph.add_edge(1, 2, Some(2.5));
+        graph.add_edge(0, 2, None);
+        graph
+    }
+
+    #[test]
+    fn quotes_and_escapes() {
+        assert_eq!(quote("plain"), "\"plain\"");
+        assert_eq!(quote("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(quote("a\\b"), "\"a\\\\b\"");
+        assert_eq!(quote("two\nlines"), "\"two\\nlines\"");
+    }
+
+    #[test]
+    fn writes_a_digraph() {
+        assert_eq!(
+            to_dot(&graph()),
+            concat!(
+                "digraph dijkstra {\n",
+                "    n0 [label=\"Home \\\"A\\\"\", type=start, pos=\"0,0\"];\n",
+                "    n1 [label=\"Distance 1\", type=distance, pos=\"150,-50\"];\n",
+                "    n2 [label=\"C:\\\\end\\nline\", type=finish, pos=\"300,0\"];\n",
+                "    n0 -> n1 [label=\"12\", weight=12];\n",
+                "    n1 -> n2 [label=\"2.5\", weight=2.5];\n",
+                "    n0 -> n2;\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn reads_its_own_output() {
+        let graph = graph();
+        let parsed = parse(&to_dot(&graph)).unwrap();
+        assert_eq!(parsed.nodes.len(), 3);
+        for (parsed, node) in parsed.nodes.iter().zip(&graph.nodes) {
+            assert_eq!(parsed.role, node.role);
+            assert_eq!(parsed.label, node.display_label());
+            assert_eq!(parsed.pos, node.pos);
+        }
+        let costs: Vec<Option<Cost>> = parsed.edges.iter().map(|edge| edge.cost).collect();
+        assert_eq!(costs, [Some(12.0), Some(2.5), None]);
+    }
+}
@@ -0,0 +1,191 @@
+// Plain edge lists, one `A B 12` connection per line, with `start A`, `finish Z` and
+// `node A x y` lines for the endpoints and positions. `#` starts a comment.
+
+use std::collections::HashMap;
+
+use egui::Pos2;
+use egui_snarl::NodeId;
+
+use crate::algorithm::Cost;
+use crate::model::{Graph, Role};
+
+const KEYWORDS: [&str; 3] = ["start", "finish", "node"];
+
+// Names are quoted when they would otherwise be split or read as a directive
+fn quote(name: &str) -> String {
+    if name.is_empty()
+        || name.contains(|c: char| c.is_whitespace() || c == '"' || c == '#')
+        || KEYWORDS.contains(&name)
+    {
+        format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        name.to_string()
+    }
+}
+
+pub fn to_text(graph: &Graph) -> String {
+    // Labels are made unique so connections don't merge nodes on import
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for node in &graph.nodes {
+        *counts.entry(node.display_label()).or_default() += 1;
+    }
+    let names: HashMap<NodeId, String> = graph
+        .nodes
+        .iter()
+        .map(|node| {
+            let label = node.display_label();
+            let name = if counts[&label] > 1 {
+                format!("{} #{}", label, node.id.0)
+            } else {
+                label
+            };
+            (node.id, quote(&name))
+        })
+        .collect();
+
+    let mut text = String::new();
+    for node in &graph.nodes {
+        let name = &names[&node.id];
+        match node.role {
+            Role::Start => text.push_str(&format!("start {}\n", name)),
+            Role::Finish => text.push_str(&format!("finish {}\n", name)),
+            Role::Distance => {}
+        }
+        if let Some(pos) = node.pos {
+            text.push_str(&format!("node {} {} {}\n", name, pos.x, pos.y));
+        }
+    }
+    for edge in &graph.edges {
+        let (from, to) = (&names[&edge.from], &names[&edge.to]);
+        match edge.cost {
+            Some(cost) => text.push_str(&format!("{} {} {}\n", from, to, cost)),
+            None => text.push_str(&format!("{} {}\n", from, to)),
+        }
+    }
+    text
+}
+
+// Split a line into words, quoted words keep their spaces and are never keywords
+fn split_line(line: &str) -> Result<Vec<(String, bool)>, String> {
+    let mut words = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '#' {
+            break;
+        } else if c == '"' {
+            chars.next();
+            let mut word = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => word.extend(chars.next()),
+                    Some(c) => word.push(c),
+                    None => return Err("unterminated quote".to_string()),
+                }
+            }
+            words.push((word, true));
+        } else {
+            let mut word = String::new();
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '#' && *c != '"') {
+                word.push(c);
+            }
+            words.push((word, false));
+        }
+    }
+    Ok(words)
+}
+
+fn parse_number(word: &str) -> Result<f32, String> {
+    word.parse()
+        .map_err(|_| format!("'{}' is not a number", word))
+}
+
+struct Parser {
+    graph: Graph,
+    ids: HashMap<String, usize>,
+}
+
+impl Parser {
+    // Nodes are created the first time they are named
+    fn node(&mut self, name: &str) -> usize {
+        if let Some(idx) = self.ids.get(name) {
+            return *idx;
+        }
+        let idx = self.graph.add_node(Role::Distance, name.to_string());
+        self.ids.insert(name.to_string(), idx);
+        idx
+    }
+
+    fn endpoint(&mut self, name: &str, role: Role) -> Result<(), String> {
+        let other = self.graph.nodes.iter().find(|node| node.role == role);
+        if let Some(other) = other.filter(|other| other.label != name) {
+            return Err(format!("{} is already '{}'", role.name(), other.label));
+        }
+        let idx = self.node(name);
+        if self.graph.nodes[idx].role != Role::Distance {
+            return Err(format!("'{}' can't be both Start and Finish", name));
+        }
+        self.graph.nodes[idx].role = role;
+        Ok(())
+    }
+
+    fn line(&mut self, words: &[(String, bool)]) -> Result<(), String> {
+        let keyword = words
+            .first()
+            .filter(|(_, quoted)| !quoted)
+            .map(|(word, _)| word.as_str());
+        match (keyword, words.len()) {
+            (Some("start"), 2) => self.endpoint(&words[1].0, Role::Start),
+            (Some("finish"), 2) => self.endpoint(&words[1].0, Role::Finish),
+            (Some("start" | "finish"), _) => Err("expected one node name".to_string()),
+            (Some("node"), 4) => {
+                let pos = Pos2::new(parse_number(&words[2].0)?, parse_number(&words[3].0)?);
+                let idx = self.node(&words[1].0);
+                self.graph.nodes[idx].pos = Some(pos);
+                Ok(())
+            }
+            (Some("node"), _) => Err("expected 'node NAME X Y'".to_string()),
+            (_, 2 | 3) => {
+                let cost = match words.get(2) {
+                    Some((word, _)) => {
+                        let cost: Cost = word
+                            .parse()
+                            .map_err(|_| format!("'{}' is not a number", word))?;
+                        if cost < 1.0 {
+                            return Err(format!("cost must be at least 1, found {}", word));
+                        }
+                        Some(cost)
+                    }
+                    None => None,
+                };
+                let from = self.node(&words[0].0);
+                let to = self.node(&words[1].0);
+                self.graph.add_edge(from, to, cost);
+                Ok(())
+            }
+            _ => Err("expected 'FROM TO COST'".to_string()),
+        }
+    }
+}
+
+pub fn parse(text: &str) -> Result<Graph, String> {
+    let mut parser = Parser {
+        graph: Graph::default(),
+        ids: HashMap::new(),
+    };
+    for (idx, line) in text.lines().enumerate() {
+        let words = split_line(line).map_err(|err| format!("Line {}: {}", idx + 1, err))?;
+        if words.is_empty() {
+            continue;
+        }
+        parser
+            .line(&words)
+            .map_err(|err| format!("Line {}: {}", idx + 1, err))?;
+    }
+    if parser.graph.nodes.is_empty() {
+        return Err("No nodes found".to_string());
+    }
+    Ok(parser.graph)
+}
@@ -0,0 +1,286 @@
+// Graphs of the "Generate" dialogs, positioned but without costs, those come from the wire
+// lengths once drawn
+
+use std::collections::HashSet;
+
+use egui::{Pos2, Vec2};
+
+use crate::model::{Graph, Role};
+use crate::{SeededRng, can_connect};
+
+fn allowed(graph: &Graph, from: usize, to: usize) -> bool {
+    can_connect(&graph.nodes[from].role.kind(), &graph.nodes[to].role.kind())
+}
+
+// Lattice with Start and Finish in opposite corners, each node connected to its right and
+// lower neighbor
+pub fn grid(rows: usize, columns: usize, spacing: f32, two_way: bool, origin: Pos2) -> Graph {
+    let mut graph = Graph::default();
+    let last = rows * columns - 1;
+    for row in 0..rows {
+        for column in 0..columns {
+            let role = match graph.nodes.len() {
+                0 => Role::Start,
+                idx if idx == last => Role::Finish,
+                _ => Role::Distance,
+            };
+            let idx = graph.add_node(role, String::new());
+            graph.nodes[idx].pos = Some(origin + egui::vec2(column as f32, row as f32) * spacing);
+        }
+    }
+
+    for row in 0..rows {
+        for column in 0..columns {
+            let node = row * columns + column;
+            let mut neighbors = Vec::new();
+            if column + 1 < columns {
+                neighbors.push(node + 1);
+            }
+            if row + 1 < rows {
+                neighbors.push(node + columns);
+            }
+            for neighbor in neighbors {
+                // Nothing leads into Start or out of Finish
+                if allowed(&graph, node, neighbor) {
+                    graph.add_edge(node, neighbor, None);
+                }
+                if two_way && allowed(&graph, neighbor, node) {
+                    graph.add_edge(neighbor, node, None);
+                }
+            }
+        }
+    }
+    graph
+}
+
+// Grid of cells connected both ways along the corridors of a maze, entered through the
+// Start in the top-left cell and left through the Finish in the bottom-right
+pub fn maze(
+    width: usize,
+    height: usize,
+    spacing: f32,
+    braid: f32,
+    seed: u64,
+    origin: Pos2,
+) -> Graph {
+    let mut rng = SeededRng(seed);
+    let corridors = maze_corridors(width, height, braid, &mut rng);
+
+    let mut graph = Graph::default();
+    let last = width * height - 1;
+    for cell in 0..=last {
+        let role = match cell {
+            0 => Role::Start,
+            cell if cell == last => Role::Finish,
+            _ => Role::Distance,
+        };
+        graph.add_node(role, String::new());
+        graph.nodes[cell].pos =
+            Some(origin + egui::vec2((cell % width) as f32, (cell / width) as f32) * spacing);
+    }
+
+    // Corridors can be walked either way, except into Start and out of Finish
+    for (a, b) in corridors {
+        if allowed(&graph, a, b) {
+            graph.add_edge(a, b, None);
+        }
+        if allowed(&graph, b, a) {
+            graph.add_edge(b, a, None);
+        }
+    }
+    graph
+}
+
+// Carve a perfect maze with a recursive backtracker and return its corridors as cell index
+// pairs, then open `braid` of the dead ends towards a random neighbor
+fn maze_corridors(
+    width: usize,
+    height: usize,
+    braid: f32,
+    rng: &mut SeededRng,
+) -> Vec<(usize, usize)> {
+    let cells = width * height;
+    let neighbors = |cell: usize| {
+        let (x, y) = (cell % width, cell / width);
+        let mut around = Vec::with_capacity(4);
+        if x > 0 {
+            around.push(cell - 1);
+        }
+        if x + 1 < width {
+            around.push(cell + 1);
+        }
+        if y > 0 {
+            around.push(cell - width);
+        }
+        if y + 1 < height {
+            around.push(cell + width);
+        }
+        around
+    };
+
+    let mut corridors = HashSet::new();
+    let mut visited = vec![false; cells];
+    let mut stack = vec![0];
+    visited[0] = true;
+    while let Some(&cell) = stack.last() {
+        let unvisited: Vec<usize> = neighbors(cell)
+            .into_iter()
+            .filter(|next| !visited[*next])
+            .collect();
+        if unvisited.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let next = unvisited[rng.next_below(unvisited.len())];
+        visited[next] = true;
+        corridors.insert((cell.min(next), cell.max(next)));
+        stack.push(next);
+    }
+
+    for cell in 0..cells {
+        let around = neighbors(cell);
+        let open = around
+            .iter()
+            .filter(|other| corridors.contains(&(cell.min(**other), cell.max(**other))))
+            .count();
+        if open == 1 && rng.next_f32() < braid {
+            let closed: Vec<usize> = around
+                .into_iter()
+                .filter(|other| !corridors.contains(&(cell.min(*other), cell.max(*other))))
+                .collect();
+            if !closed.is_empty() {
+                let other = closed[rng.next_below(closed.len())];
+                corridors.insert((cell.min(other), cell.max(other)));
+            }
+        }
+    }
+
+    let mut corridors: Vec<(usize, usize)> = corridors.into_iter().collect();
+    corridors.sort_unstable();
+    corridors
+}
+
+// Nodes on a circle around `center` with Start and Finish opposite each other, connected
+// in every direction the roles allow
+pub fn complete(nodes: usize, radius: f32, center: Pos2) -> Graph {
+    let mut graph = Graph::default();
+    let finish = nodes / 2;
+    for idx in 0..nodes {
+        let role = match idx {
+            0 => Role::Start,
+            idx if idx == finish => Role::Finish,
+            _ => Role::Distance,
+        };
+        graph.add_node(role, String::new());
+        let angle = std::f32::consts::TAU * idx as f32 / nodes as f32;
+        graph.nodes[idx].pos = Some(center + Vec2::angled(angle) * radius);
+    }
+
+    for from in 0..nodes {
+        for to in 0..nodes {
+            if from != to && allowed(&graph, from, to) {
+                graph.add_edge(from, to, None);
+            }
+        }
+    }
+    graph
+}
+
+// Nodes scattered over `area` around `origin` with Start and Finish the furthest apart,
+// every allowed connection is made with the given probability. The seed decides it all.
+pub fn random(nodes: usize, edge_probability: f32, area: Vec2, seed: u64, origin: Pos2) -> Graph {
+    let mut rng = SeededRng(seed);
+    let corner = origin - area / 2.0;
+    let positions: Vec<Pos2> = (0..nodes)
+        .map(|_| corner + egui::vec2(rng.next_f32(), rng.next_f32()) * area)
+        .collect();
+
+    let mut endpoints = (0, 0);
+    let mut furthest = -1.0;
+    for (a, pos_a) in positions.iter().enumerate() {
+        for (b, pos_b) in positions.iter().enumerate().skip(a + 1) {
+            let dist = pos_a.distance_sq(*pos_b);
+            if dist > furthest {
+                furthest = dist;
+                endpoints = (a, b);
+            }
+        }
+    }
+
+    let mut graph = Graph::default();
+    for (idx, pos) in positions.into_iter().enumerate() {
+        let role = match idx {
+            idx if idx == endpoints.0 => Role::Start,
+            idx if idx == endpoints.1 => Role::Finish,
+            _ => Role::Distance,
+        };
+        graph.add_node(role, String::new());
+        graph.nodes[idx].pos = Some(pos);
+    }
+
+    for from in 0..nodes {
+        for to in 0..nodes {
+            // Draw for every ordered pair so the graph only depends on the seed
+            if rng.next_f32() < edge_probability && from != to && allowed(&graph, from, to) {
+                graph.add_edge(from, to, None);
+            }
+        }
+    }
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use egui::Rect;
+
+    use super::*;
+    use crate::{CostScale, algorithm, endpoints, recalculate_costs, search_graph, wire_costs};
+
+    // Search from Start to Finish with the costs of the generated layout
+    fn search(graph: &Graph) -> Result<algorithm::PathResult, algorithm::PathError> {
+        let mut snarl = graph.to_snarl().snarl;
+        let rects: HashMap<_, _> = snarl
+            .nodes_pos_ids()
+            .map(|(id, pos, _)| (id, Rect::from_min_size(pos, Vec2::new(100.0, 50.0))))
+            .collect();
+        let adjacency = wire_costs(&snarl);
+        recalculate_costs(&mut snarl, &adjacency, &rects, CostScale::default());
+        let (start, finish) = endpoints(&snarl).expect("generated graphs have endpoints");
+        algorithm::shortest_path(&search_graph(&snarl, &wire_costs(&snarl)), start, finish)
+    }
+
+    #[test]
+    fn mazes_can_be_solved() {
+        for seed in [0, 1, 7, 42, 1234] {
+            for braid in [0.0, 0.3, 1.0] {
+                for (width, height) in [(3, 1), (2, 2), (6, 5), (15, 9)] {
+                    let graph = maze(width, height, 150.0, braid, seed, Pos2::ZERO);
+                    let result = search(&graph);
+                    assert!(
+                        result.is_ok(),
+                        "{}x{} maze with seed {} and braid {}: {:?}",
+                        width,
+                        height,
+                        seed,
+                        braid,
+                        result
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn complete_graph_has_every_allowed_pair() {
+        for nodes in [2, 3, 5, 12] {
+            let graph = complete(nodes, 300.0, Pos2::ZERO);
+            assert_eq!(graph.nodes.len(), nodes);
+            // Nothing leads into Start or out of Finish, and Start isn't connected straight
+            // to Finish
+            let disallowed = 2 * (nodes - 1);
+            assert_eq!(graph.edges.len(), nodes * (nodes - 1) - disallowed);
+        }
+    }
+}
@@ -0,0 +1,288 @@
+// GraphML export and import, nodes carry type, label, x and y keys and edges a cost key
+
+use std::collections::HashMap;
+
+use egui::Pos2;
+use quick_xml::Reader;
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+
+use crate::algorithm::Cost;
+use crate::model::{Graph, Role};
+
+pub fn to_graphml(graph: &Graph) -> String {
+    // Whole costs keep the integer type other tools expect
+    let fractional = graph
+        .edges
+        .iter()
+        .any(|edge| edge.cost.is_some_and(|cost| cost.fract() != 0.0));
+    let mut xml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://graphml.graphdrawing.org/xmlns http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd">
+  <key id="type" for="node" attr.name="type" attr.type="string"/>
+  <key id="label" for="node" attr.name="label" attr.type="string"/>
+  <key id="x" for="node" attr.name="x" attr.type="double"/>
+  <key id="y" for="node" attr.name="y" attr.type="double"/>
+  <key id="cost" for="edge" attr.name="cost" attr.type=""#,
+    );
+    xml.push_str(if fractional { "double" } else { "int" });
+    xml.push_str(
+        r#""/>
+  <graph id="dijkstra" edgedefault="directed">
+"#,
+    );
+    for node in &graph.nodes {
+        let kind = match node.role {
+            Role::Start => "start",
+            Role::Distance => "distance",
+            Role::Finish => "finish",
+        };
+        let pos = node.pos.unwrap_or_default();
+        xml.push_str(&format!(
+            "    <node id=\"n{}\">\n      <data key=\"type\">{}</data>\n      <data key=\"label\">{}</data>\n      <data key=\"x\">{}</data>\n      <data key=\"y\">{}</data>\n    </node>\n",
+            node.id.0,
+            kind,
+            escape(node.display_label()),
+            pos.x,
+            pos.y
+        ));
+    }
+    for (idx, edge) in graph.edges.iter().enumerate() {
+        xml.push_str(&format!(
+            "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\">",
+            idx, edge.from.0, edge.to.0
+        ));
+        if let Some(cost) = edge.cost {
+            xml.push_str(&format!("<data key=\"cost\">{}</data>", cost));
+        }
+        xml.push_str("</edge>\n");
+    }
+    xml.push_str("  </graph>\n</graphml>\n");
+    xml
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, String> {
+    element
+        .try_get_attribute(name)
+        .map_err(|err| err.to_string())?
+        .map(|attribute| {
+            attribute
+                .unescape_value()
+                .map(|value| value.into_owned())
+                .map_err(|err| err.to_string())
+        })
+        .transpose()
+}
+
+// Element whose `<data>` children are being read
+enum Owner {
+    None,
+    Node(usize),
+    Edge(usize),
+}
+
+// Read nodes and edges of the first graph, keys are matched by their `attr.name`,
+// so files written by other tools load as long as they use the same names
+pub fn parse(text: &str) -> Result<Graph, String> {
+    let mut reader = Reader::from_str(text);
+    let mut graph = Graph::default();
+    // Key id to attribute name
+    let mut keys: HashMap<String, String> = HashMap::new();
+    let mut ids: HashMap<String, usize> = HashMap::new();
+    let mut edges: Vec<(String, String, Option<Cost>)> = Vec::new();
+    let mut coords: Vec<(Option<f32>, Option<f32>)> = Vec::new();
+    let mut owner = Owner::None;
+    let mut data_key: Option<String> = None;
+    let mut text_buf = String::new();
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|err| format!("at byte {}: {}", reader.buffer_position(), err))?;
+        match event {
+            Event::Start(ref element) | Event::Empty(ref element) => {
+                let is_empty = matches!(event, Event::Empty(_));
+                match element.local_name().as_ref() {
+                    b"key" => {
+                        if let (Some(id), Some(name)) =
+                            (attribute(element, "id")?, attribute(element, "attr.name")?)
+                        {
+                            keys.insert(id, name);
+                        }
+                    }
+                    b"node" => {
+                        let id = attribute(element, "id")?
+                            .ok_or_else(|| "node without an id".to_string())?;
+                        let idx = graph.add_node(Role::Distance, id.clone());
+                        coords.push((None, None));
+                        ids.insert(id, idx);
+                        if !is_empty {
+                            owner = Owner::Node(idx);
+                        }
+                    }
+                    b"edge" => {
+                        let source = attribute(element, "source")?
+                            .ok_or_else(|| "edge without a source".to_string())?;
+                        let target = attribute(element, "target")?
+                            .ok_or_else(|| "edge without a target".to_string())?;
+                        edges.push((source, target, None));
+                        if !is_empty {
+                            owner = Owner::Edge(edges.len() - 1);
+                        }
+                    }
+                    b"data" if !is_empty => {
+                        data_key = attribute(element, "key")?;
+                        text_buf.clear();
+                    }
+                    _ => {}
+                }
+            }
+            Event::Text(content) if data_key.is_some() => {
+                text_buf.push_str(&content.unescape().map_err(|err| err.to_string())?);
+            }
+            Event::CData(content) if data_key.is_some() => {
+                text_buf.push_str(&String::from_utf8_lossy(&content));
+            }
+            Event::End(element) => match element.local_name().as_ref() {
+                b"data" => {
+                    let Some(key) = data_key.take() else {
+                        continue;
+                    };
+                    let name = keys.get(&key).map_or(key.as_str(), String::as_str);
+                    let value = text_buf.trim();
+                    match owner {
+                        Owner::Node(idx) => match name {
+                            "type" => {
+                                graph.nodes[idx].role = match value.to_lowercase().as_str() {
+                                    "start" => Role::Start,
+                                    "finish" => Role::Finish,
+                                    _ => Role::Distance,
+                                }
+                            }
+                            "label" => graph.nodes[idx].label = value.to_string(),
+                            "x" => coords[idx].0 = value.parse().ok(),
+                            "y" => coords[idx].1 = value.parse().ok(),
+                            _ => {}
+                        },
+                        Owner::Edge(idx) if name == "cost" || name == "weight" => {
+                            let cost: Cost = value
+                                .parse()
+                                .map_err(|_| format!("invalid cost '{}'", value))?;
+                            if cost < 1.0 {
+                                return Err(format!("cost must be at least 1, found {}", value));
+                            }
+                            edges[idx].2 = Some(cost);
+                        }
+                        _ => {}
+                    }
+                }
+                b"node" | b"edge" => owner = Owner::None,
+                // Only the first graph of the file is loaded
+                b"graph" => break,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    for (node, coords) in graph.nodes.iter_mut().zip(coords) {
+        if let (Some(x), Some(y)) = coords {
+            node.pos = Some(Pos2::new(x, y));
+        }
+    }
+    for (source, target, cost) in edges {
+        let from = *ids
+            .get(&source)
+            .ok_or_else(|| format!("edge from unknown node '{}'", source))?;
+        let to = *ids
+            .get(&target)
+            .ok_or_else(|| format!("edge to unknown node '{}'", target))?;
+        graph.add_edge(from, to, cost);
+    }
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIMPLE: &str = include_str!("../tests/fixtures/graphml/simple.graphml");
+    const UNKNOWN_NODE: &str = include_str!("../tests/fixtures/graphml/unknown_node.graphml");
+    const BAD_COST: &str = include_str!("../tests/fixtures/graphml/bad_cost.graphml");
+    const BROKEN: &str = include_str!("../tests/fixtures/graphml/broken.graphml");
+
+    #[test]
+    fn reads_keys_by_name() {
+        let graph = parse(SIMPLE).unwrap();
+        let roles: Vec<Role> = graph.nodes.iter().map(|node| node.role).collect();
+        assert_eq!(roles, [Role::Start, Role::Distance, Role::Finish]);
+        let labels: Vec<&str> = graph.nodes.iter().map(|node| node.label.as_str()).collect();
+        // Nodes without a label keep their id
+        assert_eq!(labels, ["Home & garden", "via", "<Away>"]);
+        assert_eq!(graph.nodes[0].pos, Some(Pos2::new(0.0, 10.5)));
+        // Both coordinates are needed for a position
+        assert_eq!(graph.nodes[2].pos, None);
+
+        let edges: Vec<(usize, usize, Option<Cost>)> = graph
+            .edges
+            .iter()
+            .map(|edge| (edge.from.0, edge.to.0, edge.cost))
+            .collect();
+        assert_eq!(edges, [(0, 1, Some(12.0)), (1, 2, Some(2.5)), (0, 2, None)]);
+    }
+
+    #[test]
+    fn reads_its_own_output() {
+        let graph = parse(SIMPLE).unwrap();
+        let parsed = parse(&to_graphml(&graph)).unwrap();
+        assert_eq!(parsed.nodes.len(), graph.nodes.len());
+        for (parsed, node) in parsed.nodes.iter().zip(&graph.nodes) {
+            assert_eq!(parsed.role, node.role);
+            assert_eq!(parsed.label, node.display_label());
+            assert_eq!(parsed.pos, Some(node.pos.unwrap_or_default()));
+        }
+        assert_eq!(parsed.edges, graph.edges);
+    }
+
+    #[test]
+    fn cost_type_follows_the_costs() {
+        let mut graph = parse(SIMPLE).unwrap();
+        assert!(to_graphml(&graph).contains("attr.name=\"cost\" attr.type=\"double\""));
+        graph.edges[1].cost = Some(3.0);
+        assert!(to_graphml(&graph).contains("attr.name=\"cost\" attr.type=\"int\""));
+    }
+
+    #[test]
+    fn malformed_files_are_errors() {
+        assert_eq!(parse(UNKNOWN_NODE).unwrap_err(), "edge to unknown node 'b'");
+        assert_eq!(parse(BAD_COST).unwrap_err(), "invalid cost 'twelve'");
+        assert!(parse(BROKEN).unwrap_err().starts_with("at byte "));
+    }
+
+    #[test]
+    fn incomplete_elements_are_errors() {
+        let wrap = |body: &str| {
+            format!(
+                "<graphml><key id=\"c\" for=\"edge\" attr.name=\"cost\"/><graph>{}</graph></graphml>",
+                body
+            )
+        };
+        assert_eq!(parse(&wrap("<node/>")).unwrap_err(), "node without an id");
+        assert_eq!(
+            parse(&wrap("<node id=\"a\"/><edge target=\"a\"/>")).unwrap_err(),
+            "edge without a source"
+        );
+        assert_eq!(
+            parse(&wrap("<node id=\"a\"/><edge source=\"a\"/>")).unwrap_err(),
+            "edge without a target"
+        );
+        assert_eq!(
+            parse(&wrap(
+                "<node id=\"a\"/><node id=\"b\"/><edge source=\"a\" target=\"b\"><data key=\"c\">0</data></edge>"
+            ))
+            .unwrap_err(),
+            "cost must be at least 1, found 0"
+        );
+    }
+}
@@ -0,0 +1,1054 @@
+// Texts of the interface in every supported language. Exports, log messages and parser
+// errors stay in English, they are meant for other programs and bug reports.
+
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Hungarian,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Hungarian];
+
+    // Each language is listed by its own name
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Hungarian => "Magyar",
+        }
+    }
+}
+
+// Global so texts can be looked up anywhere without passing the setting around
+static LANGUAGE: AtomicU8 = AtomicU8::new(Language::English as u8);
+
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match LANGUAGE.load(Ordering::Relaxed) {
+        1 => Language::Hungarian,
+        _ => Language::English,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Text {
+    Start,
+    Distance,
+    Finish,
+    ConvertedTo,
+    PreviousBecameDistance,
+    RemovedInvalidConnections,
+    CantMergeEndpoints,
+    MergedNode,
+    MergeSkipped,
+    DuplicatedAsDistance,
+    NodeCost,
+    ManualSuffix,
+    Cost,
+    AddNode,
+    Value,
+    NodeOptions,
+    Label,
+    MergeNodeIntoThis,
+    CancelMerge,
+    MergeInto,
+    MergePickTarget,
+    Color,
+    Reset,
+    ConvertTo,
+    SplitConnectionTo,
+    LockPosition,
+    ResetManualCosts,
+    Note,
+    NoteHint,
+    Duplicate,
+    Remove,
+    CantConnectToItself,
+    AlreadyConnected,
+    EdgeList,
+    Generate,
+    Replace,
+    Cancel,
+    StartNotFound,
+    FinishNotFound,
+    FailedToReadPreferences,
+    LastFileMissing,
+    RestoredSessionOf,
+    RestoredSession,
+    FailedToRestoreSession,
+    Reopened,
+    GenerateMaze,
+    Width,
+    Height,
+    Spacing,
+    Seed,
+    Braid,
+    GenerateComplete,
+    Nodes,
+    Radius,
+    SlowToEdit,
+    ImportCsvMatrix,
+    NodesAndConnections,
+    EndpointsMustDiffer,
+    Import,
+    ImportEdgeList,
+    EdgeListHelp,
+    OpenFile,
+    GenerateRandom,
+    EdgeProbability,
+    Area,
+    GenerateGrid,
+    Rows,
+    Columns,
+    ConnectBothWays,
+    EndpointsWillBeDemoted,
+    NoCopiedNodes,
+    AlignSelected,
+    Left,
+    Right,
+    Top,
+    Bottom,
+    CenterX,
+    CenterY,
+    DistributeSelected,
+    Horizontally,
+    Vertically,
+    RemovesCurrentNodes,
+    Loaded,
+    ExportedTo,
+    FailedToExport,
+    FailedToSave,
+    FailedToSerialize,
+    FailedToCompress,
+    ChangedElsewhereUnsaved,
+    ChangedElsewhere,
+    ReloadAndDiscard,
+    Reload,
+    KeepMine,
+    Dismiss,
+    NoLongerExists,
+    SavedTo,
+    ChooseAnotherFile,
+    FailedToImportDot,
+    FailedToImportGraphMl,
+    FailedToImportMatrix,
+    FailedToRead,
+    FailedToReadFile,
+    FailedToLoad,
+    SourceFile,
+    SourceClipboard,
+    GraphCopied,
+    NoTextInClipboard,
+    StepCost,
+    Path,
+    RunFirst,
+    Copy,
+    PathSummaryCopied,
+    Show,
+    Profiling,
+    Frame,
+    DrawingGraph,
+    CostRecalculation,
+    LastSearch,
+    Connections,
+    Log,
+    Level,
+    Distances,
+    SortBy,
+    Node,
+    DistanceColumn,
+    Previous,
+    PasteGraph,
+    ReplaceWithPasted,
+    PastedGraph,
+    DroppedStaleCosts,
+    SettingsFromFile,
+    SavedPathDiscarded,
+    ExtraEndpointsDemoted,
+    SkippedConnections,
+    ImportedNodes,
+    AlreadyExists,
+    PathFound,
+    UnknownNode,
+    NoPathFound,
+    Save,
+    SaveAs,
+    Load,
+    CopyGraph,
+    PasteGraphFromClipboard,
+    File,
+    NotSavedYet,
+    FileHover,
+    ExportDot,
+    ExportSvg,
+    ExportGraphMl,
+    ExportMatrix,
+    ExportEdgeList,
+    ExportMermaid,
+    ImportDot,
+    ImportGraphMl,
+    FailedToWrite,
+    SaveEllipsis,
+    SaveMermaidHover,
+    MermaidCopied,
+    ExportResult,
+    RecordTrace,
+    ExportTrace,
+    ExportTraceHover,
+    Theme,
+    Dark,
+    Light,
+    Calculator,
+    Actions,
+    RemoveAll,
+    RemovedNodes,
+    FitView,
+    FitViewHover,
+    ClearPath,
+    RunDijkstra,
+    CurrentPathCost,
+    NoPath,
+    AutoRecalc,
+    On,
+    Off,
+    ShowNotes,
+    ShowWeights,
+    ShowProfiling,
+    ShowLog,
+    ShowPathSummary,
+    UnlockAll,
+    LockAll,
+    GraphSettings,
+    WireStyle,
+    StartColor,
+    FinishColor,
+    SaveAsDefaults,
+    SaveAsDefaultsHover,
+    DefaultsSaved,
+    FailedToSaveDefaults,
+    ResetToDefaults,
+    ReopenSession,
+    Language,
+    WireLine,
+    WireAxisAligned,
+    WireBezier3,
+    WireBezier5,
+    AutoRecalcChange,
+    WireStyleChange,
+    PastedEndpointsDemoted,
+    Appearance,
+    WireWidth,
+    GridSize,
+    CollapsibleHeaders,
+    ShowAppearance,
+    Notifications,
+    NotificationDuration,
+    StickyErrors,
+    NotificationPosition,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    ConfirmRemoveAll,
+    Confirm,
+    ShowStatusBar,
+    StatusNodes,
+    StatusConnections,
+    StatusZoom,
+    StatusPath,
+    Help,
+    HelpAddingNodes,
+    HelpAddingNodesBody,
+    HelpConnecting,
+    HelpConnectingBody,
+    HelpCosts,
+    HelpCostsBody,
+    HelpRunning,
+    HelpRunningBody,
+    HelpSaving,
+    HelpSavingBody,
+    HelpShortcuts,
+    ShortcutAddNode,
+    ShortcutDelete,
+    ShortcutCopyPaste,
+    ShortcutSave,
+    IncomingConnections,
+    OutgoingConnections,
+    NoConnections,
+    PinConnection,
+    ShowInspector,
+    Inspector,
+    SelectANode,
+    Type,
+    Position,
+    DistanceFromStart,
+    OnPath,
+    Yes,
+    No,
+    AnimatePath,
+    OneInput,
+    InputCount,
+    HideCostsBelowZoom,
+    StatusCursor,
+    CostScaleChange,
+    PixelsPerCost,
+    MinimumCost,
+    RecalcPending,
+    RecalcAtMostEvery,
+    SearchCancelled,
+    RunningSettled,
+    IssuesCount,
+    NoIssues,
+    IssueNoStart,
+    IssueNoFinish,
+    IssueIsolated,
+    IssueDeadEnd,
+    IssueStaleCosts,
+    AddStart,
+    AddFinish,
+    DropStaleCosts,
+    HighlightNeighbors,
+    Find,
+    FindHint,
+    NoMatches,
+    MatchCount,
+    ShortcutFind,
+    LegendPath,
+    LegendPredecessor,
+    LegendSuccessor,
+    ShowLegend,
+    LegendInExports,
+    Presentation,
+    ExitPresentation,
+    ShortcutPresentation,
+    ResetLayout,
+    ResetLayoutHover,
+    SetAsStart,
+    SetAsFinish,
+    ShowArrowheads,
+    MakeBidirectional,
+    CostRounding,
+    RoundDown,
+    RoundNearest,
+    RoundUp,
+    FractionalCosts,
+    FractionalCostsHover,
+    PathOutdated,
+    Recalculating,
+    NoPathBecause,
+    PathOk,
+    Palette,
+    PaletteDefault,
+    PaletteDeuteranopia,
+    PaletteTritanopia,
+    PaletteHighContrast,
+    PathWidth,
+    PathColor,
+    DimOffPath,
+    EmphasizeByDistance,
+}
+
+pub fn tr(text: Text) -> &'static str {
+    match language() {
+        Language::English => english(text),
+        Language::Hungarian => hungarian(text),
+    }
+}
+
+// Fills the `{}` placeholders of the text in order
+pub fn trf(text: Text, args: &[&dyn Display]) -> String {
+    let mut parts = tr(text).split("{}");
+    let mut filled = parts.next().unwrap_or_default().to_string();
+    for (idx, part) in parts.enumerate() {
+        if let Some(arg) = args.get(idx) {
+            filled += &arg.to_string();
+        }
+        filled += part;
+    }
+    filled
+}
+
+fn english(text: Text) -> &'static str {
+    match text {
+        Text::Start => "Start",
+        Text::Distance => "Distance",
+        Text::Finish => "Finish",
+        Text::ConvertedTo => "Converted to {}",
+        Text::PreviousBecameDistance => ", previous {} became a Distance node",
+        Text::RemovedInvalidConnections => ", removed {} invalid connection(s)",
+        Text::CantMergeEndpoints => "Start and Finish can't be merged",
+        Text::MergedNode => "Merged node {} into node {}",
+        Text::MergeSkipped => ", moved {} connection(s) and skipped {} duplicate or invalid",
+        Text::DuplicatedAsDistance => {
+            "There can only be one Start and Finish, duplicated as a Distance node"
+        }
+        Text::NodeCost => "Node {}: cost {}",
+        Text::ManualSuffix => " (manual)",
+        Text::Cost => "Cost: {}",
+        Text::AddNode => "Add node",
+        Text::Value => "Value",
+        Text::NodeOptions => "Node Options",
+        Text::Label => "Label",
+        Text::MergeNodeIntoThis => "Merge node {} into this",
+        Text::CancelMerge => "Cancel merge",
+        Text::MergeInto => "Merge into…",
+        Text::MergePickTarget => "Right-click the node to merge into",
+        Text::Color => "Color",
+        Text::Reset => "Reset",
+        Text::ConvertTo => "Convert to {}",
+        Text::SplitConnectionTo => "Split connection to",
+        Text::LockPosition => "Lock position",
+        Text::ResetManualCosts => "Reset manual costs",
+        Text::Note => "Note",
+        Text::NoteHint => "Why this node is here…",
+        Text::Duplicate => "Duplicate",
+        Text::Remove => "Remove",
+        Text::CantConnectToItself => "A node can't be connected to itself",
+        Text::AlreadyConnected => "These nodes are already connected",
+        Text::EdgeList => "Edge list",
+        Text::Generate => "Generate",
+        Text::Replace => "Replace",
+        Text::Cancel => "Cancel",
+        Text::StartNotFound => "Start node not found",
+        Text::FinishNotFound => "Finish node not found",
+        Text::FailedToReadPreferences => "Failed to read preferences: {}",
+        Text::LastFileMissing => "The last opened file {} no longer exists",
+        Text::RestoredSessionOf => "Restored the last session of {}",
+        Text::RestoredSession => "Restored the last session",
+        Text::FailedToRestoreSession => "Failed to restore the last session: {}",
+        Text::Reopened => "Reopened {}",
+        Text::GenerateMaze => "Generate maze",
+        Text::Width => "Width",
+        Text::Height => "Height",
+        Text::Spacing => "Spacing",
+        Text::Seed => "Seed",
+        Text::Braid => "Braid",
+        Text::GenerateComplete => "Generate complete graph",
+        Text::Nodes => "Nodes",
+        Text::Radius => "Radius",
+        Text::SlowToEdit => "{} connections, editing the result will be slow",
+        Text::ImportCsvMatrix => "Import CSV matrix",
+        Text::NodesAndConnections => "{} nodes, {} connections",
+        Text::EndpointsMustDiffer => "Start and Finish must be different nodes",
+        Text::Import => "Import",
+        Text::ImportEdgeList => "Import edge list",
+        Text::EdgeListHelp => {
+            "One connection per line as `A B 12`, with optional `start A`, `finish Z` and `node A x y` lines"
+        }
+        Text::OpenFile => "Open file…",
+        Text::GenerateRandom => "Generate random graph",
+        Text::EdgeProbability => "Edge probability",
+        Text::Area => "Area",
+        Text::GenerateGrid => "Generate grid",
+        Text::Rows => "Rows",
+        Text::Columns => "Columns",
+        Text::ConnectBothWays => "Connect in both directions",
+        Text::EndpointsWillBeDemoted => "The existing Start and Finish will become Distance nodes",
+        Text::NoCopiedNodes => "Clipboard doesn't contain copied nodes",
+        Text::AlignSelected => "Align selected",
+        Text::Left => "Left",
+        Text::Right => "Right",
+        Text::Top => "Top",
+        Text::Bottom => "Bottom",
+        Text::CenterX => "Center X",
+        Text::CenterY => "Center Y",
+        Text::DistributeSelected => "Distribute selected",
+        Text::Horizontally => "Horizontally",
+        Text::Vertically => "Vertically",
+        Text::RemovesCurrentNodes => "This removes the current {} nodes",
+        Text::Loaded => "Loaded {}: {} nodes, {} connections",
+        Text::ExportedTo => "Exported to {}",
+        Text::FailedToExport => "Failed to export: {}",
+        Text::FailedToSave => "Failed to save route: {}",
+        Text::FailedToSerialize => "Failed to serialize data: {}",
+        Text::FailedToCompress => "Failed to compress data: {}",
+        Text::ChangedElsewhereUnsaved => {
+            "{} was changed by another program, reloading discards your unsaved changes"
+        }
+        Text::ChangedElsewhere => "{} was changed by another program",
+        Text::ReloadAndDiscard => "Reload and discard my changes",
+        Text::Reload => "Reload",
+        Text::KeepMine => "Keep my version",
+        Text::Dismiss => "Dismiss",
+        Text::NoLongerExists => "{} no longer exists, choose where to save",
+        Text::SavedTo => "Saved to {}",
+        Text::ChooseAnotherFile => "{}, choose another file",
+        Text::FailedToImportDot => "Failed to import DOT: {}",
+        Text::FailedToImportGraphMl => "Failed to import GraphML: {}",
+        Text::FailedToImportMatrix => "Failed to import CSV matrix: {}",
+        Text::FailedToRead => "Failed to read {}: {}",
+        Text::FailedToReadFile => "Failed to read file: {}",
+        Text::FailedToLoad => "Failed to load {}: {}",
+        Text::SourceFile => "file",
+        Text::SourceClipboard => "clipboard",
+        Text::GraphCopied => "Graph copied to clipboard",
+        Text::NoTextInClipboard => "The clipboard doesn't contain text",
+        Text::StepCost => "cost {}, cum {}",
+        Text::Path => "Path",
+        Text::RunFirst => "Run the algorithm first",
+        Text::Copy => "Copy",
+        Text::PathSummaryCopied => "Path summary copied to clipboard",
+        Text::Show => "Show",
+        Text::Profiling => "Profiling",
+        Text::Frame => "Frame",
+        Text::DrawingGraph => "Drawing the graph",
+        Text::CostRecalculation => "Cost recalculation",
+        Text::LastSearch => "Last search",
+        Text::Connections => "Connections",
+        Text::Log => "Log",
+        Text::Level => "Level",
+        Text::Distances => "Distances",
+        Text::SortBy => "Sort by",
+        Text::Node => "Node",
+        Text::DistanceColumn => "Distance",
+        Text::Previous => "Previous",
+        Text::PasteGraph => "Paste graph",
+        Text::ReplaceWithPasted => "Replace the current graph with the pasted one ({} nodes)?",
+        Text::PastedGraph => "Pasted graph with {} node(s)",
+        Text::DroppedStaleCosts => "Dropped {} costs of connections that don't exist",
+        Text::SettingsFromFile => "Settings from the file: {}",
+        Text::SavedPathDiscarded => {
+            "The saved path doesn't match the graph anymore and was discarded"
+        }
+        Text::ExtraEndpointsDemoted => "{} extra Start/Finish node(s) became Distance nodes",
+        Text::SkippedConnections => "Skipped {} self-loop, duplicate or invalid connection(s)",
+        Text::ImportedNodes => "Imported {} node(s)",
+        Text::AlreadyExists => "There is already a {} node",
+        Text::PathFound => "Path found! Total cost: {}",
+        Text::UnknownNode => "Node {} isn't in the graph",
+        Text::NoPathFound => "No path found",
+        Text::Save => "Save",
+        Text::SaveAs => "Save As…",
+        Text::Load => "Load",
+        Text::CopyGraph => "Copy graph to clipboard",
+        Text::PasteGraphFromClipboard => "Paste graph from clipboard",
+        Text::File => "File: {}",
+        Text::NotSavedYet => "Not saved yet",
+        Text::FileHover => "Save and Ctrl+S write to this file",
+        Text::ExportDot => "Export DOT",
+        Text::ExportSvg => "Export SVG",
+        Text::ExportGraphMl => "Export GraphML",
+        Text::ExportMatrix => "Export CSV matrix",
+        Text::ExportEdgeList => "Export edge list",
+        Text::ExportMermaid => "Export Mermaid",
+        Text::ImportDot => "Import DOT",
+        Text::ImportGraphMl => "Import GraphML",
+        Text::FailedToWrite => "Failed to write {}: {}",
+        Text::SaveEllipsis => "Save…",
+        Text::SaveMermaidHover => "Save the Mermaid chart to a file",
+        Text::MermaidCopied => "Mermaid chart copied to clipboard",
+        Text::ExportResult => "Export result",
+        Text::RecordTrace => "Record trace",
+        Text::ExportTrace => "Export trace",
+        Text::ExportTraceHover => "Run the algorithm with Record trace on",
+        Text::Theme => "Theme",
+        Text::Dark => "Dark",
+        Text::Light => "Light",
+        Text::Calculator => "Calculator",
+        Text::Actions => "Actions",
+        Text::RemoveAll => "Remove all",
+        Text::RemovedNodes => "Removed {} nodes",
+        Text::FitView => "Fit view",
+        Text::FitViewHover => "Show the whole graph (Home)",
+        Text::ClearPath => "Clear Dijkstra Path",
+        Text::RunDijkstra => "Run Dijkstra Algorithm",
+        Text::CurrentPathCost => "Current path cost: {} ({} hops)",
+        Text::NoPath => "No path",
+        Text::AutoRecalc => "Auto recalc - {}",
+        Text::On => "on",
+        Text::Off => "off",
+        Text::ShowNotes => "Show notes on nodes",
+        Text::ShowWeights => "Show weights on wires",
+        Text::ShowProfiling => "Show profiling",
+        Text::ShowLog => "Show log",
+        Text::ShowPathSummary => "Show path summary",
+        Text::UnlockAll => "Unlock all nodes",
+        Text::LockAll => "Lock all nodes",
+        Text::GraphSettings => "Graph settings",
+        Text::WireStyle => "Wire style",
+        Text::StartColor => "Start color",
+        Text::FinishColor => "Finish color",
+        Text::SaveAsDefaults => "Save as defaults",
+        Text::SaveAsDefaultsHover => "Use these settings for new graphs",
+        Text::DefaultsSaved => "Defaults saved",
+        Text::FailedToSaveDefaults => "Failed to save defaults: {}",
+        Text::ResetToDefaults => "Reset to defaults",
+        Text::ReopenSession => "Reopen last session on startup",
+        Text::Language => "Language",
+        Text::WireLine => "Line",
+        Text::WireAxisAligned => "Axis aligned",
+        Text::WireBezier3 => "Bezier 3",
+        Text::WireBezier5 => "Bezier 5",
+        Text::AutoRecalcChange => "auto recalc {}",
+        Text::WireStyleChange => "wire style {}",
+        Text::PastedEndpointsDemoted => "{} pasted Start/Finish node(s) became Distance nodes",
+        Text::Appearance => "Appearance",
+        Text::WireWidth => "Wire width",
+        Text::GridSize => "Grid size",
+        Text::CollapsibleHeaders => "Collapsible headers",
+        Text::ShowAppearance => "Show appearance settings",
+        Text::Notifications => "Notifications",
+        Text::NotificationDuration => "Duration",
+        Text::StickyErrors => "Keep errors until closed",
+        Text::NotificationPosition => "Position",
+        Text::TopLeft => "Top left",
+        Text::TopRight => "Top right",
+        Text::BottomLeft => "Bottom left",
+        Text::BottomRight => "Bottom right",
+        Text::ConfirmRemoveAll => "Delete {} nodes and {} connections?",
+        Text::Confirm => "Confirm",
+        Text::ShowStatusBar => "Show status bar",
+        Text::StatusNodes => "Nodes: {} Start, {} Distance, {} Finish",
+        Text::StatusConnections => "Connections: {}",
+        Text::StatusZoom => "Zoom: {}%",
+        Text::StatusPath => "Path: cost {}, {} hops",
+        Text::Help => "Help",
+        Text::HelpAddingNodes => "➕ Adding nodes",
+        Text::HelpAddingNodesBody => {
+            "Right-click the empty canvas to add a Start, a Distance (Value) or a Finish node. There can only be one Start and one Finish. Right-click a node for its options: label, color, note, converting, duplicating or removing it."
+        }
+        Text::HelpConnecting => "🔗 Connecting nodes",
+        Text::HelpConnectingBody => {
+            "Drag from the output pin on the right side of a node to the input pin on the left side of another. Connections only go one way, nothing leads into Start or out of Finish. Right-click a pin to drop its connections."
+        }
+        Text::HelpCosts => "📏 How costs are computed",
+        Text::HelpCostsBody => {
+            "The cost of a connection is the distance in pixels between the two nodes divided by 10, rounded and at least 1. Moving a node updates the costs of its connections. A cost set by hand in the node menu stays until it is reset."
+        }
+        Text::HelpRunning => "▶ Running the algorithm",
+        Text::HelpRunningBody => {
+            "Press \"Run Dijkstra Algorithm\" to highlight the cheapest path from Start to Finish. With auto recalc on, the path follows every change. The Distances table lists the distance of every node from Start."
+        }
+        Text::HelpSaving => "💾 Saving and loading",
+        Text::HelpSavingBody => {
+            "Save writes the graph with its settings and path to a JSON file, ending the name in .gz compresses it. Load opens such a file again. Graphs can also be imported from and exported to DOT, GraphML, CSV matrices and edge lists."
+        }
+        Text::HelpShortcuts => "⌨ Keyboard shortcuts",
+        Text::ShortcutAddNode => "Add a Start, Distance or Finish node at the cursor",
+        Text::ShortcutDelete => "Remove the selected nodes",
+        Text::ShortcutCopyPaste => "Copy and paste the selected nodes",
+        Text::ShortcutSave => "Save to the current file",
+        Text::IncomingConnections => "Incoming connections",
+        Text::OutgoingConnections => "Outgoing connections",
+        Text::NoConnections => "No connections",
+        Text::PinConnection => "{}: {} px, cost {}",
+        Text::ShowInspector => "Show node inspector",
+        Text::Inspector => "Inspector",
+        Text::SelectANode => "Select a node to see its details",
+        Text::Type => "Type",
+        Text::Position => "Position",
+        Text::DistanceFromStart => "Distance from Start",
+        Text::OnPath => "On the path",
+        Text::Yes => "Yes",
+        Text::No => "No",
+        Text::AnimatePath => "Animate the path",
+        Text::OneInput => "1 input",
+        Text::InputCount => "{} inputs",
+        Text::HideCostsBelowZoom => "Hide costs below zoom",
+        Text::StatusCursor => "Cursor: {}, {}",
+        Text::CostScaleChange => "cost scale {} px",
+        Text::PixelsPerCost => "pixels per cost unit",
+        Text::MinimumCost => "minimum cost",
+        Text::RecalcPending => "pending…",
+        Text::RecalcAtMostEvery => "Recalculate at most every",
+        Text::SearchCancelled => "Search cancelled",
+        Text::RunningSettled => "Running… {} nodes settled",
+        Text::IssuesCount => "Issues ({})",
+        Text::NoIssues => "No issues found",
+        Text::IssueNoStart => "There is no Start node",
+        Text::IssueNoFinish => "There is no Finish node",
+        Text::IssueIsolated => "{} has no connections",
+        Text::IssueDeadEnd => "Finish can't be reached from {}",
+        Text::IssueStaleCosts => "{} keeps {} costs of missing connections",
+        Text::AddStart => "Add Start",
+        Text::AddFinish => "Add Finish",
+        Text::DropStaleCosts => "Drop",
+        Text::HighlightNeighbors => "Highlight neighbors on hover",
+        Text::Find => "Find",
+        Text::FindHint => "Label, note or id",
+        Text::NoMatches => "No matching nodes",
+        Text::MatchCount => "{} matches, Enter steps through them",
+        Text::ShortcutFind => "Find a node by label, note or id",
+        Text::LegendPath => "Shortest path",
+        Text::LegendPredecessor => "Connects into the hovered node",
+        Text::LegendSuccessor => "Connected from the hovered node",
+        Text::ShowLegend => "Show color legend",
+        Text::LegendInExports => "Include the legend in SVG exports",
+        Text::Presentation => "Presentation mode",
+        Text::ExitPresentation => "Exit presentation",
+        Text::ShortcutPresentation => "Show only the graph, e.g. on a projector",
+        Text::ResetLayout => "Reset layout",
+        Text::ResetLayoutHover => "Move the windows back and show the default panels",
+        Text::SetAsStart => "Set as Start",
+        Text::SetAsFinish => "Set as Finish",
+        Text::ShowArrowheads => "Show the direction of connections",
+        Text::MakeBidirectional => "Make bidirectional with",
+        Text::CostRounding => "rounding of costs",
+        Text::RoundDown => "Down",
+        Text::RoundNearest => "To nearest",
+        Text::RoundUp => "Up",
+        Text::FractionalCosts => "Fractional costs",
+        Text::FractionalCostsHover => {
+            "Keep the fraction of the wire length instead of rounding to whole costs, shown to one decimal place"
+        }
+        Text::PathOutdated => "Path outdated, the graph changed since the run — re-run",
+        Text::Recalculating => "Recalculating…",
+        Text::NoPathBecause => "No path: {}",
+        Text::PathOk => "Path OK (cost {})",
+        Text::Palette => "Colors",
+        Text::PaletteDefault => "Default",
+        Text::PaletteDeuteranopia => "Deuteranopia-safe (red-green)",
+        Text::PaletteTritanopia => "Tritanopia-safe (blue-yellow)",
+        Text::PaletteHighContrast => "High contrast",
+        Text::PathWidth => "Path width",
+        Text::PathColor => "Path color",
+        Text::DimOffPath => "Dim nodes off the path",
+        Text::EmphasizeByDistance => "Emphasize nodes by distance from Start",
+    }
+}
+
+fn hungarian(text: Text) -> &'static str {
+    match text {
+        Text::Start => "Start",
+        Text::Distance => "Köztes",
+        Text::Finish => "Cél",
+        Text::ConvertedTo => "Átalakítva: {}",
+        Text::PreviousBecameDistance => ", az előző {} Köztes csúcs lett",
+        Text::RemovedInvalidConnections => ", {} érvénytelen kapcsolat törölve",
+        Text::CantMergeEndpoints => "A Start és a Cél nem vonható össze",
+        Text::MergedNode => "A(z) {}. csúcs összevonva a(z) {}. csúccsal",
+        Text::MergeSkipped => ", {} kapcsolat áthelyezve, {} ismétlődő vagy érvénytelen kihagyva",
+        Text::DuplicatedAsDistance => {
+            "Csak egy Start és egy Cél lehet, Köztes csúcsként duplikálva"
+        }
+        Text::NodeCost => "{}. csúcs: költség {}",
+        Text::ManualSuffix => " (kézi)",
+        Text::Cost => "Költség: {}",
+        Text::AddNode => "Csúcs hozzáadása",
+        Text::Value => "Érték",
+        Text::NodeOptions => "Csúcs beállításai",
+        Text::Label => "Címke",
+        Text::MergeNodeIntoThis => "A(z) {}. csúcs összevonása ezzel",
+        Text::CancelMerge => "Összevonás megszakítása",
+        Text::MergeInto => "Összevonás ezzel…",
+        Text::MergePickTarget => "Kattints jobb gombbal arra a csúcsra, amelybe összevonod",
+        Text::Color => "Szín",
+        Text::Reset => "Visszaállítás",
+        Text::ConvertTo => "Átalakítás: {}",
+        Text::SplitConnectionTo => "Kapcsolat felosztása ide",
+        Text::LockPosition => "Pozíció rögzítése",
+        Text::ResetManualCosts => "Kézi költségek visszaállítása",
+        Text::Note => "Megjegyzés",
+        Text::NoteHint => "Miért van itt ez a csúcs…",
+        Text::Duplicate => "Duplikálás",
+        Text::Remove => "Törlés",
+        Text::CantConnectToItself => "Egy csúcs nem köthető önmagához",
+        Text::AlreadyConnected => "Ezek a csúcsok már össze vannak kötve",
+        Text::EdgeList => "Éllista",
+        Text::Generate => "Generálás",
+        Text::Replace => "Csere",
+        Text::Cancel => "Mégse",
+        Text::StartNotFound => "Nem található Start csúcs",
+        Text::FinishNotFound => "Nem található Cél csúcs",
+        Text::FailedToReadPreferences => "Nem sikerült beolvasni a beállításokat: {}",
+        Text::LastFileMissing => "A legutóbb megnyitott fájl ({}) már nem létezik",
+        Text::RestoredSessionOf => "Visszaállítva a legutóbbi munkamenet: {}",
+        Text::RestoredSession => "Visszaállítva a legutóbbi munkamenet",
+        Text::FailedToRestoreSession => "Nem sikerült visszaállítani a legutóbbi munkamenetet: {}",
+        Text::Reopened => "Újra megnyitva: {}",
+        Text::GenerateMaze => "Labirintus generálása",
+        Text::Width => "Szélesség",
+        Text::Height => "Magasság",
+        Text::Spacing => "Távolság",
+        Text::Seed => "Kezdőérték",
+        Text::Braid => "Körök aránya",
+        Text::GenerateComplete => "Teljes gráf generálása",
+        Text::Nodes => "Csúcsok",
+        Text::Radius => "Sugár",
+        Text::SlowToEdit => "{} kapcsolat, az eredmény szerkesztése lassú lesz",
+        Text::ImportCsvMatrix => "CSV mátrix importálása",
+        Text::NodesAndConnections => "{} csúcs, {} kapcsolat",
+        Text::EndpointsMustDiffer => "A Start és a Cél különböző csúcs kell legyen",
+        Text::Import => "Importálás",
+        Text::ImportEdgeList => "Éllista importálása",
+        Text::EdgeListHelp => {
+            "Soronként egy kapcsolat `A B 12` alakban, opcionális `start A`, `finish Z` és `node A x y` sorokkal"
+        }
+        Text::OpenFile => "Fájl megnyitása…",
+        Text::GenerateRandom => "Véletlen gráf generálása",
+        Text::EdgeProbability => "Él valószínűsége",
+        Text::Area => "Terület",
+        Text::GenerateGrid => "Rács generálása",
+        Text::Rows => "Sorok",
+        Text::Columns => "Oszlopok",
+        Text::ConnectBothWays => "Összekötés mindkét irányban",
+        Text::EndpointsWillBeDemoted => "A meglévő Start és Cél Köztes csúcs lesz",
+        Text::NoCopiedNodes => "A vágólap nem tartalmaz másolt csúcsokat",
+        Text::AlignSelected => "Kijelöltek igazítása",
+        Text::Left => "Bal",
+        Text::Right => "Jobb",
+        Text::Top => "Fent",
+        Text::Bottom => "Lent",
+        Text::CenterX => "Középre X",
+        Text::CenterY => "Középre Y",
+        Text::DistributeSelected => "Kijelöltek elosztása",
+        Text::Horizontally => "Vízszintesen",
+        Text::Vertically => "Függőlegesen",
+        Text::RemovesCurrentNodes => "Ez eltávolítja a jelenlegi {} csúcsot",
+        Text::Loaded => "{} betöltve: {} csúcs, {} kapcsolat",
+        Text::ExportedTo => "Exportálva ide: {}",
+        Text::FailedToExport => "Az exportálás sikertelen: {}",
+        Text::FailedToSave => "A mentés sikertelen: {}",
+        Text::FailedToSerialize => "Az adatok szerializálása sikertelen: {}",
+        Text::FailedToCompress => "Az adatok tömörítése sikertelen: {}",
+        Text::ChangedElsewhereUnsaved => {
+            "A(z) {} fájlt egy másik program módosította, az újratöltés elveti a nem mentett változásokat"
+        }
+        Text::ChangedElsewhere => "A(z) {} fájlt egy másik program módosította",
+        Text::ReloadAndDiscard => "Újratöltés, a változásaim elvetése",
+        Text::Reload => "Újratöltés",
+        Text::KeepMine => "A saját változatom megtartása",
+        Text::Dismiss => "Elvetés",
+        Text::NoLongerExists => "A(z) {} már nem létezik, válassz mentési helyet",
+        Text::SavedTo => "Mentve ide: {}",
+        Text::ChooseAnotherFile => "{}, válassz másik fájlt",
+        Text::FailedToImportDot => "A DOT importálása sikertelen: {}",
+        Text::FailedToImportGraphMl => "A GraphML importálása sikertelen: {}",
+        Text::FailedToImportMatrix => "A CSV mátrix importálása sikertelen: {}",
+        Text::FailedToRead => "A(z) {} olvasása sikertelen: {}",
+        Text::FailedToReadFile => "A fájl olvasása sikertelen: {}",
+        Text::FailedToLoad => "A(z) {} betöltése sikertelen: {}",
+        Text::SourceFile => "fájl",
+        Text::SourceClipboard => "vágólap",
+        Text::GraphCopied => "A gráf a vágólapra másolva",
+        Text::NoTextInClipboard => "A vágólap nem tartalmaz szöveget",
+        Text::StepCost => "költség {}, összesen {}",
+        Text::Path => "Útvonal",
+        Text::RunFirst => "Előbb futtasd az algoritmust",
+        Text::Copy => "Másolás",
+        Text::PathSummaryCopied => "Az útvonal összegzése a vágólapra másolva",
+        Text::Show => "Mutasd",
+        Text::Profiling => "Profilozás",
+        Text::Frame => "Képkocka",
+        Text::DrawingGraph => "A gráf rajzolása",
+        Text::CostRecalculation => "Költségek újraszámolása",
+        Text::LastSearch => "Utolsó keresés",
+        Text::Connections => "Kapcsolatok",
+        Text::Log => "Napló",
+        Text::Level => "Szint",
+        Text::Distances => "Távolságok",
+        Text::SortBy => "Rendezés",
+        Text::Node => "Csúcs",
+        Text::DistanceColumn => "Távolság",
+        Text::Previous => "Előző",
+        Text::PasteGraph => "Gráf beillesztése",
+        Text::ReplaceWithPasted => "Lecseréled a jelenlegi gráfot a beillesztettre ({} csúcs)?",
+        Text::PastedGraph => "Beillesztett gráf {} csúccsal",
+        Text::DroppedStaleCosts => "{} nem létező kapcsolat költsége eldobva",
+        Text::SettingsFromFile => "Beállítások a fájlból: {}",
+        Text::SavedPathDiscarded => "A mentett útvonal már nem illik a gráfhoz, ezért elvetettük",
+        Text::ExtraEndpointsDemoted => "{} fölösleges Start/Cél csúcs Köztes csúcs lett",
+        Text::SkippedConnections => "{} hurok, ismétlődő vagy érvénytelen kapcsolat kihagyva",
+        Text::ImportedNodes => "{} csúcs importálva",
+        Text::AlreadyExists => "Már van {} csúcs",
+        Text::PathFound => "Útvonal megtalálva! Teljes költség: {}",
+        Text::UnknownNode => "A(z) {} csúcs nincs a gráfban",
+        Text::NoPathFound => "Nincs útvonal",
+        Text::Save => "Mentés",
+        Text::SaveAs => "Mentés másként…",
+        Text::Load => "Betöltés",
+        Text::CopyGraph => "Gráf másolása a vágólapra",
+        Text::PasteGraphFromClipboard => "Gráf beillesztése a vágólapról",
+        Text::File => "Fájl: {}",
+        Text::NotSavedYet => "Még nincs mentve",
+        Text::FileHover => "A Mentés és a Ctrl+S ebbe a fájlba ír",
+        Text::ExportDot => "DOT exportálása",
+        Text::ExportSvg => "SVG exportálása",
+        Text::ExportGraphMl => "GraphML exportálása",
+        Text::ExportMatrix => "CSV mátrix exportálása",
+        Text::ExportEdgeList => "Éllista exportálása",
+        Text::ExportMermaid => "Mermaid exportálása",
+        Text::ImportDot => "DOT importálása",
+        Text::ImportGraphMl => "GraphML importálása",
+        Text::FailedToWrite => "A(z) {} írása sikertelen: {}",
+        Text::SaveEllipsis => "Mentés…",
+        Text::SaveMermaidHover => "A Mermaid diagram mentése fájlba",
+        Text::MermaidCopied => "A Mermaid diagram a vágólapra másolva",
+        Text::ExportResult => "Eredmény exportálása",
+        Text::RecordTrace => "Lépések rögzítése",
+        Text::ExportTrace => "Lépések exportálása",
+        Text::ExportTraceHover => "Futtasd az algoritmust bekapcsolt Lépések rögzítésével",
+        Text::Theme => "Téma",
+        Text::Dark => "Sötét",
+        Text::Light => "Világos",
+        Text::Calculator => "Kalkulátor",
+        Text::Actions => "Műveletek",
+        Text::RemoveAll => "Összes eltávolítása",
+        Text::RemovedNodes => "{} csúcs eltávolítva",
+        Text::FitView => "Teljes nézet",
+        Text::FitViewHover => "Az egész gráf megjelenítése (Home)",
+        Text::ClearPath => "Dijkstra útvonal törlése",
+        Text::RunDijkstra => "Dijkstra algoritmus futtatása",
+        Text::CurrentPathCost => "Jelenlegi útvonal költsége: {} ({} lépés)",
+        Text::NoPath => "Nincs útvonal",
+        Text::AutoRecalc => "Automatikus újraszámolás - {}",
+        Text::On => "be",
+        Text::Off => "ki",
+        Text::ShowNotes => "Megjegyzések a csúcsokon",
+        Text::ShowWeights => "Súlyok a vezetékeken",
+        Text::ShowProfiling => "Profilozás megjelenítése",
+        Text::ShowLog => "Napló megjelenítése",
+        Text::ShowPathSummary => "Útvonal összegzése",
+        Text::UnlockAll => "Összes csúcs feloldása",
+        Text::LockAll => "Összes csúcs rögzítése",
+        Text::GraphSettings => "Gráf beállításai",
+        Text::WireStyle => "Vezetékstílus",
+        Text::StartColor => "Start színe",
+        Text::FinishColor => "Cél színe",
+        Text::SaveAsDefaults => "Mentés alapértelmezésként",
+        Text::SaveAsDefaultsHover => "Ezek a beállítások az új gráfokhoz",
+        Text::DefaultsSaved => "Alapértelmezések mentve",
+        Text::FailedToSaveDefaults => "Az alapértelmezések mentése sikertelen: {}",
+        Text::ResetToDefaults => "Alapértelmezések visszaállítása",
+        Text::ReopenSession => "Az utolsó munkamenet megnyitása induláskor",
+        Text::Language => "Nyelv",
+        Text::WireLine => "Egyenes",
+        Text::WireAxisAligned => "Derékszögű",
+        Text::WireBezier3 => "Bézier 3",
+        Text::WireBezier5 => "Bézier 5",
+        Text::AutoRecalcChange => "automatikus újraszámolás {}",
+        Text::WireStyleChange => "vezetékstílus {}",
+        Text::PastedEndpointsDemoted => "{} beillesztett Start/Cél csúcs Köztes csúcs lett",
+        Text::Appearance => "Megjelenés",
+        Text::WireWidth => "Vezetékvastagság",
+        Text::GridSize => "Rácsméret",
+        Text::CollapsibleHeaders => "Összecsukható fejlécek",
+        Text::ShowAppearance => "Megjelenési beállítások",
+        Text::Notifications => "Értesítések",
+        Text::NotificationDuration => "Időtartam",
+        Text::StickyErrors => "A hibák bezárásig maradnak",
+        Text::NotificationPosition => "Elhelyezés",
+        Text::TopLeft => "Bal felső",
+        Text::TopRight => "Jobb felső",
+        Text::BottomLeft => "Bal alsó",
+        Text::BottomRight => "Jobb alsó",
+        Text::ConfirmRemoveAll => "Törlöd a(z) {} csúcsot és {} kapcsolatot?",
+        Text::Confirm => "Megerősítés",
+        Text::ShowStatusBar => "Állapotsor megjelenítése",
+        Text::StatusNodes => "Csúcsok: {} Start, {} Köztes, {} Cél",
+        Text::StatusConnections => "Kapcsolatok: {}",
+        Text::StatusZoom => "Nagyítás: {}%",
+        Text::StatusPath => "Útvonal: költség {}, {} lépés",
+        Text::Help => "Súgó",
+        Text::HelpAddingNodes => "➕ Csúcsok hozzáadása",
+        Text::HelpAddingNodesBody => {
+            "Jobb kattintás az üres vásznon: Start, Köztes (Érték) vagy Cél csúcs hozzáadása. Csak egy Start és egy Cél lehet. Egy csúcson jobb kattintva érhetők el a beállításai: címke, szín, megjegyzés, átalakítás, duplikálás vagy eltávolítás."
+        }
+        Text::HelpConnecting => "🔗 Csúcsok összekötése",
+        Text::HelpConnectingBody => {
+            "Húzz a csúcs jobb oldali kimenetétől egy másik csúcs bal oldali bemenetéig. A kapcsolatok egyirányúak, Startba nem vezet és Célból nem indul kapcsolat. Egy csatlakozón jobb kattintva törölhetők a kapcsolatai."
+        }
+        Text::HelpCosts => "📏 A költségek számítása",
+        Text::HelpCostsBody => {
+            "Egy kapcsolat költsége a két csúcs képpontban mért távolsága osztva 10-zel, kerekítve és legalább 1. Egy csúcs mozgatása frissíti a kapcsolatai költségét. A csúcs menüjében kézzel megadott költség a visszaállításig megmarad."
+        }
+        Text::HelpRunning => "▶ Az algoritmus futtatása",
+        Text::HelpRunningBody => {
+            "A \"Dijkstra algoritmus futtatása\" gomb kiemeli a legolcsóbb utat a Starttól a Célig. Bekapcsolt automatikus újraszámolással az útvonal minden változást követ. A Távolságok táblázat minden csúcs Starttól mért távolságát mutatja."
+        }
+        Text::HelpSaving => "💾 Mentés és betöltés",
+        Text::HelpSavingBody => {
+            "A Mentés JSON fájlba írja a gráfot a beállításaival és az útvonallal, .gz végű név esetén tömörítve. A Betöltés újra megnyit egy ilyen fájlt. A gráfok DOT, GraphML, CSV mátrix és éllista formátumba is exportálhatók, illetve onnan importálhatók."
+        }
+        Text::HelpShortcuts => "⌨ Billentyűparancsok",
+        Text::ShortcutAddNode => "Start, Köztes vagy Cél csúcs hozzáadása a kurzornál",
+        Text::ShortcutDelete => "A kijelölt csúcsok eltávolítása",
+        Text::ShortcutCopyPaste => "A kijelölt csúcsok másolása és beillesztése",
+        Text::ShortcutSave => "Mentés a jelenlegi fájlba",
+        Text::IncomingConnections => "Bejövő kapcsolatok",
+        Text::OutgoingConnections => "Kimenő kapcsolatok",
+        Text::NoConnections => "Nincs kapcsolat",
+        Text::PinConnection => "{}: {} px, költség {}",
+        Text::ShowInspector => "Csúcsvizsgáló megjelenítése",
+        Text::Inspector => "Vizsgáló",
+        Text::SelectANode => "Válassz ki egy csúcsot a részleteihez",
+        Text::Type => "Típus",
+        Text::Position => "Pozíció",
+        Text::DistanceFromStart => "Távolság a Starttól",
+        Text::OnPath => "Az útvonalon",
+        Text::Yes => "Igen",
+        Text::No => "Nem",
+        Text::AnimatePath => "Útvonal animálása",
+        Text::OneInput => "1 bemenet",
+        Text::InputCount => "{} bemenet",
+        Text::HideCostsBelowZoom => "Költségek elrejtése e nagyítás alatt",
+        Text::StatusCursor => "Kurzor: {}, {}",
+        Text::CostScaleChange => "költségskála {} px",
+        Text::PixelsPerCost => "képpont költségegységenként",
+        Text::MinimumCost => "legkisebb költség",
+        Text::RecalcPending => "függőben…",
+        Text::RecalcAtMostEvery => "Újraszámolás legfeljebb ennyi időnként:",
+        Text::SearchCancelled => "A keresés megszakítva",
+        Text::RunningSettled => "Fut… {} csúcs lezárva",
+        Text::IssuesCount => "Problémák ({})",
+        Text::NoIssues => "Nincs probléma",
+        Text::IssueNoStart => "Nincs Start csúcs",
+        Text::IssueNoFinish => "Nincs Cél csúcs",
+        Text::IssueIsolated => "{} csúcsnak nincs kapcsolata",
+        Text::IssueDeadEnd => "{} csúcsból nem érhető el a Cél",
+        Text::IssueStaleCosts => "{} csúcs {} hiányzó kapcsolat költségét őrzi",
+        Text::AddStart => "Start hozzáadása",
+        Text::AddFinish => "Cél hozzáadása",
+        Text::DropStaleCosts => "Eldobás",
+        Text::HighlightNeighbors => "Szomszédok kiemelése rámutatáskor",
+        Text::Find => "Keresés",
+        Text::FindHint => "Címke, megjegyzés vagy azonosító",
+        Text::NoMatches => "Nincs találat",
+        Text::MatchCount => "{} találat, az Enter végiglépked rajtuk",
+        Text::ShortcutFind => "Csúcs keresése címke, megjegyzés vagy azonosító alapján",
+        Text::LegendPath => "Legrövidebb út",
+        Text::LegendPredecessor => "Az egér alatti csúcsba vezet",
+        Text::LegendSuccessor => "Az egér alatti csúcsból vezet ide",
+        Text::ShowLegend => "Színmagyarázat megjelenítése",
+        Text::LegendInExports => "Színmagyarázat az SVG exportban",
+        Text::Presentation => "Bemutató mód",
+        Text::ExitPresentation => "Kilépés a bemutatóból",
+        Text::ShortcutPresentation => "Csak a gráf megjelenítése, pl. kivetítéshez",
+        Text::ResetLayout => "Elrendezés visszaállítása",
+        Text::ResetLayoutHover => {
+            "Az ablakok visszakerülnek a helyükre, a panelek az alapértelmezettek lesznek"
+        }
+        Text::SetAsStart => "Legyen ez a Start",
+        Text::SetAsFinish => "Legyen ez a Cél",
+        Text::ShowArrowheads => "Kapcsolatok irányának megjelenítése",
+        Text::MakeBidirectional => "Kétirányúvá tétel ezzel",
+        Text::CostRounding => "költségek kerekítése",
+        Text::RoundDown => "Lefelé",
+        Text::RoundNearest => "Legközelebbire",
+        Text::RoundUp => "Felfelé",
+        Text::FractionalCosts => "Tört költségek",
+        Text::FractionalCostsHover => {
+            "A vezeték hosszából adódó tört részt is megtartja a kerekítés helyett, egy tizedesjegyig mutatva"
+        }
+        Text::PathOutdated => "Az útvonal elavult, a gráf változott a futtatás óta — futtasd újra",
+        Text::Recalculating => "Újraszámolás…",
+        Text::NoPathBecause => "Nincs útvonal: {}",
+        Text::PathOk => "Útvonal rendben (költség {})",
+        Text::Palette => "Színek",
+        Text::PaletteDefault => "Alapértelmezett",
+        Text::PaletteDeuteranopia => "Deuteranópia-barát (vörös-zöld)",
+        Text::PaletteTritanopia => "Tritanópia-barát (kék-sárga)",
+        Text::PaletteHighContrast => "Nagy kontraszt",
+        Text::PathWidth => "Útvonal vastagsága",
+        Text::PathColor => "Útvonal színe",
+        Text::DimOffPath => "Az útvonalon kívüli csomópontok halványítása",
+        Text::EmphasizeByDistance => "Csomópontok kiemelése a Starttól való távolság szerint",
+    }
+}
@@ -0,0 +1,202 @@
+// Conversions to and from petgraph for analysis outside the editor. `From` can't be
+// implemented between the two foreign graph types, so these are plain functions returning
+// the index map alongside the graph.
+
+use std::collections::HashMap;
+
+use egui::Pos2;
+use egui_snarl::{NodeId, Snarl};
+use petgraph::graph::{Graph, NodeIndex};
+
+use crate::model::{self, Role};
+use crate::{Cost, DijkstraNode, NodeKind, wire_costs};
+
+// Nodes are added in id order and each wire becomes an edge weighted by the cost the search
+// would use
+pub fn to_petgraph(
+    snarl: &Snarl<DijkstraNode>,
+) -> (Graph<NodeKind, Cost>, HashMap<NodeId, NodeIndex>) {
+    let mut ids: Vec<NodeId> = snarl.node_ids().map(|(id, _)| id).collect();
+    ids.sort_by_key(|id| id.0);
+
+    let mut graph = Graph::with_capacity(ids.len(), snarl.wires().count());
+    let index: HashMap<NodeId, NodeIndex> = ids
+        .iter()
+        .map(|id| (*id, graph.add_node(snarl[*id].kind.clone())))
+        .collect();
+
+    let mut wires = wire_costs(snarl);
+    wires.sort_by_key(|(from, to, _)| (from.0, to.0));
+    for (from, to, cost) in wires {
+        graph.add_edge(index[&from], index[&to], cost);
+    }
+    (graph, index)
+}
+
+// The editor's rules apply as for imported files: only the first Start and Finish keep
+// their role and connections the roles don't allow are dropped. Edge weights become manual
+// costs. Without a position for every node the whole graph is laid out instead.
+pub fn from_petgraph(
+    graph: &Graph<NodeKind, Cost>,
+    positions: Option<&HashMap<NodeIndex, Pos2>>,
+) -> (model::Conversion, HashMap<NodeIndex, NodeId>) {
+    let mut imported = model::Graph::default();
+    for idx in graph.node_indices() {
+        let node = imported.add_node(Role::of(&graph[idx]), String::new());
+        imported.nodes[node].pos = positions.and_then(|positions| positions.get(&idx).copied());
+    }
+    for edge in graph.raw_edges() {
+        imported.add_edge(
+            edge.source().index(),
+            edge.target().index(),
+            Some(edge.weight),
+        );
+    }
+
+    let conversion = imported.to_snarl();
+    let index = graph
+        .node_indices()
+        .zip(conversion.ids.iter().copied())
+        .collect();
+    (conversion, index)
+}
+
+#[cfg(test)]
+mod tests {
+    use egui_snarl::{InPinId, OutPinId};
+
+    use super::*;
+
+    // Start, two Distance nodes and Finish with a gap in the ids where a node was removed
+    fn snarl() -> (Snarl<DijkstraNode>, [NodeId; 4]) {
+        let mut snarl = Snarl::new();
+        let start = snarl.insert_node(Pos2::new(0.0, 0.0), DijkstraNode::new(NodeKind::Start));
+        let removed = snarl.insert_node(Pos2::ZERO, DijkstraNode::new(NodeKind::Start));
+        let upper = snarl.insert_node(
+            Pos2::new(200.0, -50.0),
+            DijkstraNode::new(NodeKind::Distance(HashMap::from([(start, 4.0)]))),
+        );
+        let lower = snarl.insert_node(
+            Pos2::new(200.0, 50.0),
+            DijkstraNode::new(NodeKind::Distance(HashMap::from([(start, 2.0)]))),
+        );
+        let finish = snarl.insert_node(
+            Pos2::new(400.0, 0.0),
+            DijkstraNode::new(NodeKind::Finish(HashMap::from([
+                (upper, 1.0),
+                (lower, 6.0),
+            ]))),
+        );
+        snarl.remove_node(removed);
+        for (from, to) in [
+            (start, upper),
+            (start, lower),
+            (upper, finish),
+            (lower, finish),
+        ] {
+            snarl.connect(
+                OutPinId {
+                    node: from,
+                    output: 0,
+                },
+                InPinId { node: to, input: 0 },
+            );
+        }
+        (snarl, [start, upper, lower, finish])
+    }
+
+    #[test]
+    fn index_map_points_at_the_same_nodes() {
+        let (snarl, nodes) = snarl();
+        let (graph, index) = to_petgraph(&snarl);
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(index.len(), 4);
+        for node in nodes {
+            assert_eq!(graph[index[&node]], snarl[node].kind);
+        }
+        for (from, to, cost) in wire_costs(&snarl) {
+            let edge = graph.find_edge(index[&from], index[&to]).unwrap();
+            assert_eq!(graph[edge], cost);
+        }
+        assert_eq!(graph.edge_count(), 4);
+    }
+
+    fn costs(kind: &NodeKind) -> HashMap<NodeId, Cost> {
+        match kind {
+            NodeKind::Distance(costs) | NodeKind::Finish(costs) => costs.clone(),
+            NodeKind::Start => HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn round_trip_keeps_the_graph() {
+        let (snarl, nodes) = snarl();
+        let (graph, index) = to_petgraph(&snarl);
+        let positions: HashMap<NodeIndex, Pos2> = snarl
+            .nodes_pos_ids()
+            .map(|(id, pos, _)| (index[&id], pos))
+            .collect();
+        let (conversion, back) = from_petgraph(&graph, Some(&positions));
+        assert_eq!((conversion.demoted, conversion.skipped), (0, 0));
+
+        // Old id to new id through both index maps
+        let new = |id: NodeId| back[&index[&id]];
+        for node in nodes {
+            let converted = &conversion.snarl[new(node)];
+            let expected: HashMap<NodeId, Cost> = costs(&snarl[node].kind)
+                .into_iter()
+                .map(|(remote, cost)| (new(remote), cost))
+                .collect();
+            assert_eq!(Role::of(&converted.kind), Role::of(&snarl[node].kind));
+            assert_eq!(costs(&converted.kind), expected);
+            assert_eq!(
+                conversion.snarl.get_node_info(new(node)).unwrap().pos,
+                snarl.get_node_info(node).unwrap().pos
+            );
+        }
+        let mut wires: Vec<(NodeId, NodeId)> = snarl
+            .wires()
+            .map(|(out_pin, in_pin)| (new(out_pin.node), new(in_pin.node)))
+            .collect();
+        let mut converted: Vec<(NodeId, NodeId)> = conversion
+            .snarl
+            .wires()
+            .map(|(out_pin, in_pin)| (out_pin.node, in_pin.node))
+            .collect();
+        wires.sort();
+        converted.sort();
+        assert_eq!(wires, converted);
+    }
+
+    #[test]
+    fn only_the_first_endpoints_keep_their_role() {
+        let mut graph = Graph::new();
+        let starts = [
+            graph.add_node(NodeKind::Start),
+            graph.add_node(NodeKind::Start),
+        ];
+        let finishes = [
+            graph.add_node(NodeKind::Finish(HashMap::new())),
+            graph.add_node(NodeKind::Finish(HashMap::new())),
+        ];
+        // Start straight into Finish isn't a connection the editor allows
+        graph.add_edge(starts[0], finishes[0], 3.0);
+        // Both ends become Distance nodes, so this one is kept
+        graph.add_edge(starts[1], finishes[1], 5.0);
+
+        let (conversion, index) = from_petgraph(&graph, None);
+        assert_eq!((conversion.demoted, conversion.skipped), (2, 1));
+        let role = |idx: NodeIndex| Role::of(&conversion.snarl[index[&idx]].kind);
+        assert_eq!(role(starts[0]), Role::Start);
+        assert_eq!(role(starts[1]), Role::Distance);
+        assert_eq!(role(finishes[0]), Role::Finish);
+        assert_eq!(role(finishes[1]), Role::Distance);
+
+        let (from, to) = (index[&starts[1]], index[&finishes[1]]);
+        assert_eq!(conversion.snarl.wires().count(), 1);
+        assert_eq!(
+            conversion.snarl[to].meta.overrides,
+            HashMap::from([(from, 5.0)])
+        );
+    }
+}
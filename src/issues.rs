@@ -0,0 +1,103 @@
+// Structural problems listed in the Issues section, found from the nodes and the
+// connections the search uses
+
+use std::collections::{HashMap, HashSet};
+
+use egui_snarl::{NodeId, Snarl};
+
+use crate::{Cost, DijkstraNode, NodeKind};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Issue {
+    NoStart,
+    NoFinish,
+    // A Distance node without any connections
+    Isolated(NodeId),
+    // Connected, but none of its connections lead on to Finish
+    DeadEnd(NodeId),
+    // Costs kept for sources that aren't connected and manual costs for removed nodes
+    StaleCosts(NodeId, usize),
+}
+
+impl Issue {
+    pub fn node(&self) -> Option<NodeId> {
+        match self {
+            Issue::NoStart | Issue::NoFinish => None,
+            Issue::Isolated(node) | Issue::DeadEnd(node) | Issue::StaleCosts(node, _) => {
+                Some(*node)
+            }
+        }
+    }
+}
+
+pub fn find(snarl: &Snarl<DijkstraNode>, adjacency: &[(NodeId, NodeId, Cost)]) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    if !snarl
+        .nodes()
+        .any(|node| matches!(node.kind, NodeKind::Start))
+    {
+        issues.push(Issue::NoStart);
+    }
+    let finishes: Vec<NodeId> = snarl
+        .nodes_ids_data()
+        .filter(|(_, node)| matches!(node.value.kind, NodeKind::Finish(_)))
+        .map(|(id, _)| id)
+        .collect();
+    if finishes.is_empty() {
+        issues.push(Issue::NoFinish);
+    }
+
+    let mut ids: Vec<NodeId> = snarl.node_ids().map(|(id, _)| id).collect();
+    ids.sort_by_key(|id| id.0);
+    let connected: HashSet<NodeId> = adjacency
+        .iter()
+        .flat_map(|(from, to, _)| [*from, *to])
+        .collect();
+
+    // Walk the connections backwards from Finish, the nodes left out can't reach it
+    let mut sources: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+    for (from, to, _) in adjacency {
+        sources.entry(*to).or_default().push(*from);
+    }
+    let mut reaching: HashSet<NodeId> = finishes.iter().copied().collect();
+    let mut queue = finishes.clone();
+    while let Some(node) = queue.pop() {
+        for source in sources.get(&node).into_iter().flatten() {
+            if reaching.insert(*source) {
+                queue.push(*source);
+            }
+        }
+    }
+
+    let wires: HashSet<(NodeId, NodeId)> =
+        adjacency.iter().map(|(from, to, _)| (*from, *to)).collect();
+    for id in ids {
+        let node = &snarl[id];
+        if matches!(node.kind, NodeKind::Distance(_)) {
+            if !connected.contains(&id) {
+                issues.push(Issue::Isolated(id));
+            } else if !finishes.is_empty() && !reaching.contains(&id) {
+                issues.push(Issue::DeadEnd(id));
+            }
+        }
+
+        // The same entries `drop_stale_costs` removes
+        let stale_costs = match &node.kind {
+            NodeKind::Distance(costs) | NodeKind::Finish(costs) => costs
+                .keys()
+                .filter(|remote| !wires.contains(&(**remote, id)))
+                .count(),
+            NodeKind::Start => 0,
+        };
+        let stale_overrides = node
+            .meta
+            .overrides
+            .keys()
+            .filter(|remote| snarl.get_node(**remote).is_none())
+            .count();
+        if stale_costs + stale_overrides > 0 {
+            issues.push(Issue::StaleCosts(id, stale_costs + stale_overrides));
+        }
+    }
+    issues
+}
@@ -0,0 +1,5862 @@
+use eframe::{App as EframeApp, CreationContext, NativeOptions, egui};
+use egui::{Color32, Pos2, Rect, Vec2, WidgetText};
+use egui_snarl::{
+    InPin, InPinId, NodeId, OutPin, OutPinId, Snarl,
+    ui::{
+        BackgroundPattern, Grid, PinInfo, PinShape, PinWireInfo, SnarlPin, SnarlStyle, SnarlViewer,
+        Viewport, WireStyle,
+    },
+};
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use i18n::{Language, Text, tr, trf};
+
+pub mod algorithm;
+#[cfg(not(target_arch = "wasm32"))]
+mod crash;
+mod dot;
+mod edgelist;
+pub mod generate;
+mod graphml;
+mod i18n;
+#[cfg(feature = "petgraph")]
+pub mod interop;
+mod issues;
+mod logging;
+mod matrix;
+mod mermaid;
+pub mod model;
+mod notifications;
+mod palette;
+mod report;
+mod save;
+mod settings;
+#[cfg(not(target_arch = "wasm32"))]
+mod solve;
+mod svg;
+#[cfg(test)]
+mod tests;
+mod trace;
+mod wire;
+mod worker;
+
+pub use algorithm::{Cost, format_cost};
+pub use settings::CostScale;
+
+// Offset between the cursor and pasted nodes, so they don't land under the pointer
+const PASTE_OFFSET: Vec2 = Vec2::new(20.0, 20.0);
+// Gap between a node and its duplicate
+const DUPLICATE_GAP: f32 = 20.0;
+// Distance between columns and rows of automatically placed nodes
+const LAYOUT_SPACING: Vec2 = Vec2::new(200.0, 120.0);
+// Shortest time between two auto-recalc searches while dragging, until the user picks one
+const DEFAULT_RECALC_INTERVAL_MS: u64 = 100;
+// Zoom limits of the graph view
+const MIN_SCALE: f32 = 0.2;
+const MAX_SCALE: f32 = 1.0;
+// A pulse runs from Start to Finish in this many seconds, split between the edges by cost
+const PULSE_LOOP_SECONDS: f64 = 4.0;
+// Share of the loop a free edge still gets, so the pulse doesn't jump across it
+const PULSE_MIN_EDGE_COST: f64 = 1.0;
+// Length of the arrowheads on the wires at full zoom
+const ARROW_SIZE: f32 = 9.0;
+// How long a node found with Find blinks, in seconds
+const FLASH_DURATION: f64 = 1.5;
+// Space kept around the nodes by "Fit view", in graph units
+const FIT_VIEW_MARGIN: f32 = 50.0;
+// The auto-recalc status when a path was found, egui's visuals only have warning and error colors
+const STATUS_OK_COLOR: Color32 = Color32::from_rgb(60, 170, 80);
+// Opacity of the nodes off the path when they are dimmed
+const DIMMED_OPACITY: f32 = 0.35;
+// Opacity of the nodes the search didn't reach, with distance emphasis
+const FADED_OPACITY: f32 = 0.15;
+// Pin sizes of the farthest and the closest nodes, with distance emphasis
+const MIN_PIN_SIZE: f32 = 0.7;
+const MAX_PIN_SIZE: f32 = 1.6;
+// Points each path wire is drawn through when it's widened
+const PATH_WIRE_SAMPLES: usize = 24;
+
+// Role of a node, Distance and Finish keep the costs of their incoming connections
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum NodeKind {
+    Start,
+    Distance(#[serde(serialize_with = "save::write_costs")] HashMap<NodeId, Cost>),
+    Finish(#[serde(serialize_with = "save::write_costs")] HashMap<NodeId, Cost>),
+}
+
+// Settings of a node that don't affect the algorithm
+#[derive(PartialEq, Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+struct NodeMeta {
+    // Shown instead of the node type when set
+    #[serde(skip_serializing_if = "String::is_empty")]
+    label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<Color32>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    note: String,
+    // Locked nodes snap back to their position after every drag
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    locked: bool,
+    // Manually set costs of incoming connections, used instead of the wire length
+    #[serde(
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "save::write_costs"
+    )]
+    overrides: HashMap<NodeId, Cost>,
+    // Sources of two-way connections whose wire into this node costs what this node's wire
+    // back to them does
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    mirrored: HashSet<NodeId>,
+}
+
+// Define a simple node type
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "serde_json::Value")]
+pub struct DijkstraNode {
+    kind: NodeKind,
+    meta: NodeMeta,
+}
+
+impl NodeKind {
+    fn name(&self) -> &'static str {
+        match self {
+            NodeKind::Start => tr(Text::Start),
+            NodeKind::Distance(_) => tr(Text::Distance),
+            NodeKind::Finish(_) => tr(Text::Finish),
+        }
+    }
+
+    // Same variant as `target`, keeping this node's costs where the variant has any
+    fn converted(&self, target: &NodeKind) -> NodeKind {
+        let costs = match self {
+            NodeKind::Distance(costs) | NodeKind::Finish(costs) => costs.clone(),
+            NodeKind::Start => HashMap::new(),
+        };
+        match target {
+            NodeKind::Start => NodeKind::Start,
+            NodeKind::Distance(_) => NodeKind::Distance(costs),
+            NodeKind::Finish(_) => NodeKind::Finish(costs),
+        }
+    }
+}
+
+impl DijkstraNode {
+    fn new(kind: NodeKind) -> Self {
+        Self {
+            kind,
+            meta: NodeMeta::default(),
+        }
+    }
+
+    // Label if it has one, otherwise the node type
+    fn title(&self) -> &str {
+        if self.meta.label.is_empty() {
+            self.kind.name()
+        } else {
+            &self.meta.label
+        }
+    }
+}
+
+// Older saves stored only the node kind, without the surrounding struct
+impl TryFrom<serde_json::Value> for DijkstraNode {
+    type Error = serde_json::Error;
+
+    fn try_from(mut value: serde_json::Value) -> Result<Self, Self::Error> {
+        let Some(kind) = value.get_mut("kind").map(serde_json::Value::take) else {
+            return Ok(Self::new(serde_json::from_value(value)?));
+        };
+        let meta = match value.get_mut("meta").map(serde_json::Value::take) {
+            Some(meta) => serde_json::from_value(meta)?,
+            None => NodeMeta::default(),
+        };
+        Ok(Self {
+            kind: serde_json::from_value(kind)?,
+            meta,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct GraphStats {
+    starts: usize,
+    distances: usize,
+    finishes: usize,
+    connections: usize,
+}
+
+// Copy of the snarl viewport from the last frame, used to map screen positions to the graph
+#[derive(Clone, Copy, Debug)]
+struct ViewTransform {
+    rect: Rect,
+    scale: f32,
+    offset: Vec2,
+    // The view shift the nodes were drawn with
+    shift: Vec2,
+}
+
+impl ViewTransform {
+    fn screen_pos_to_graph(&self, pos: Pos2) -> Pos2 {
+        (pos + self.offset - self.rect.center().to_vec2()) / self.scale - self.shift
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum PinSide {
+    Input,
+    Output,
+}
+
+// Screen rects of the pins drawn this frame, filled in by TrackedPin
+type PinRects = Rc<RefCell<HashMap<(NodeId, PinSide), Rect>>>;
+
+// Pin that records where it was drawn, so pointer drags can be matched to pins
+struct TrackedPin {
+    info: PinInfo,
+    key: (NodeId, PinSide),
+    rects: PinRects,
+    // Multiplies the size from the snarl style
+    size: f32,
+}
+
+impl SnarlPin for TrackedPin {
+    fn pin_rect(&self, x: f32, y0: f32, y1: f32, size: f32) -> Rect {
+        self.info.pin_rect(x, y0, y1, size * self.size)
+    }
+
+    fn draw(
+        self,
+        scale: f32,
+        snarl_style: &SnarlStyle,
+        style: &egui::Style,
+        rect: Rect,
+        painter: &egui::Painter,
+    ) -> PinWireInfo {
+        self.rects.borrow_mut().insert(self.key, rect);
+        SnarlPin::draw(self.info, scale, snarl_style, style, rect, painter)
+    }
+}
+
+struct DijkstraViewer {
+    stored_nodes: HashMap<NodeId, Rect>,
+    toasts: notifications::Notifications,
+    path_nodes: Vec<NodeId>,
+    view: Option<ViewTransform>,
+    // egui_snarl can't be told its pan, so the nodes are drawn moved by this and get their
+    // positions back after the frame. While the snarl is shown its positions are the drawn ones.
+    view_shift: Vec2,
+    // Mirror of the snarl's selection, refreshed after every frame
+    selected: HashSet<NodeId>,
+    // Node picked with "Merge into…", waiting for the target to be chosen
+    merge_source: Option<NodeId>,
+    // Show the first line of each note inside its node
+    show_notes: bool,
+    pin_rects: PinRects,
+    show_weights: bool,
+    // Copied from the graph settings, a change recalculates every cost
+    cost_scale: CostScale,
+    // Below this zoom the costs inside Distance nodes are only counted, copied from the appearance
+    cost_label_scale: f32,
+    // Node under the pointer in the last frame and the one found while drawing this frame
+    hovered: Option<NodeId>,
+    hovered_now: Option<NodeId>,
+    // Sources and targets of the hovered node's connections, found once it's hovered
+    hovered_neighbors: Option<(HashSet<NodeId>, HashSet<NodeId>)>,
+    highlight_neighbors: bool,
+    dim_off_path: bool,
+    // Closeness to Start from 1 down to 0 for the nodes the last search reached, None while
+    // distance emphasis is off or there was no search
+    closeness: Option<HashMap<NodeId, f32>>,
+    // Node found with Find while its blink is lit
+    flashing: Option<NodeId>,
+    // Shift was held, a finished wire drag also connects the way back
+    two_way_modifier: bool,
+    // Cost shown next to the cursor while dragging a wire, kept so the label is only formatted on change
+    cost_preview: Option<(Cost, String)>,
+    // Nodes or connections changed, so the costs of all connections have to be recomputed
+    costs_dirty: bool,
+    // Nodes whose rect changed since, only their connections are recomputed
+    moved_nodes: HashSet<NodeId>,
+    // Connections with the costs the search uses, rebuilt on first use after a change
+    adjacency: Option<Vec<(NodeId, NodeId, Cost)>>,
+    // Counts for the status bar, rebuilt like `adjacency`
+    stats: Option<GraphStats>,
+    // Problems for the Issues section, also rebuilt like `adjacency`
+    issues: Option<Vec<issues::Issue>>,
+    // Bumped whenever the graph or its costs change, searches of older versions are stale
+    graph_version: u64,
+    // Time spent in `update_costs` during the current frame
+    costs_time: Duration,
+    // Colors of the current theme and the endpoint colors of the settings, set before
+    // every frame
+    palette: palette::Palette,
+    start_color: Color32,
+    finish_color: Color32,
+}
+
+impl DijkstraViewer {
+    fn new() -> Self {
+        Self {
+            stored_nodes: HashMap::new(),
+            toasts: notifications::Notifications::default(),
+            path_nodes: Vec::new(),
+            view: None,
+            view_shift: Vec2::ZERO,
+            selected: HashSet::new(),
+            merge_source: None,
+            show_notes: true,
+            pin_rects: PinRects::default(),
+            show_weights: true,
+            cost_scale: CostScale::default(),
+            cost_label_scale: Appearance::default().cost_label_scale,
+            hovered: None,
+            hovered_now: None,
+            hovered_neighbors: None,
+            highlight_neighbors: true,
+            dim_off_path: false,
+            closeness: None,
+            flashing: None,
+            two_way_modifier: false,
+            cost_preview: None,
+            costs_dirty: true,
+            moved_nodes: HashSet::new(),
+            adjacency: None,
+            stats: None,
+            issues: None,
+            graph_version: 0,
+            palette: palette::Palette::new(palette::Preset::Default, true),
+            start_color: palette::Palette::new(palette::Preset::Default, true).start,
+            finish_color: palette::Palette::new(palette::Preset::Default, true).finish,
+            costs_time: Duration::ZERO,
+        }
+    }
+
+    // After the graph is drawn, the neighbors are outlined from the next frame on
+    fn update_hovered(&mut self, ctx: &egui::Context) {
+        let hovered = self.hovered_now.take();
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            self.hovered_neighbors = None;
+            if self.highlight_neighbors {
+                ctx.request_repaint();
+            }
+        }
+    }
+
+    // Called for every change of nodes or connections, moves are picked up in `final_node_rect`
+    // and both are handled by `update_costs` once the graph is drawn
+    fn mark_changed(&mut self) {
+        self.costs_dirty = true;
+        self.adjacency = None;
+        self.stats = None;
+        self.issues = None;
+        self.hovered_neighbors = None;
+        self.graph_version += 1;
+    }
+
+    fn adjacency(&mut self, snarl: &Snarl<DijkstraNode>) -> &[(NodeId, NodeId, Cost)] {
+        self.adjacency.get_or_insert_with(|| wire_costs(snarl))
+    }
+
+    fn stats(&mut self, snarl: &Snarl<DijkstraNode>) -> GraphStats {
+        if let Some(stats) = self.stats {
+            return stats;
+        }
+        let mut stats = GraphStats {
+            connections: self.adjacency(snarl).len(),
+            ..GraphStats::default()
+        };
+        for node in snarl.nodes() {
+            match node.kind {
+                NodeKind::Start => stats.starts += 1,
+                NodeKind::Distance(_) => stats.distances += 1,
+                NodeKind::Finish(_) => stats.finishes += 1,
+            }
+        }
+        self.stats = Some(stats);
+        stats
+    }
+
+    fn issues(&mut self, snarl: &Snarl<DijkstraNode>) -> Vec<issues::Issue> {
+        if let Some(issues) = &self.issues {
+            return issues.clone();
+        }
+        let issues = issues::find(snarl, self.adjacency(snarl));
+        self.issues = Some(issues.clone());
+        issues
+    }
+
+    // Remove nodes together with their rects and any costs neighbors keep for them
+    fn remove_nodes(&mut self, nodes: &[NodeId], snarl: &mut Snarl<DijkstraNode>) {
+        log::debug!("Removing {} nodes", nodes.len());
+        self.mark_changed();
+        for node in nodes {
+            if snarl.get_node(*node).is_none() {
+                continue;
+            }
+            snarl.remove_node(*node);
+            self.stored_nodes.remove(node);
+            self.selected.remove(node);
+        }
+        for value in snarl.nodes_mut() {
+            match &mut value.kind {
+                NodeKind::Distance(costs) | NodeKind::Finish(costs) => {
+                    costs.retain(|remote, _| !nodes.contains(remote));
+                }
+                NodeKind::Start => {}
+            }
+            value
+                .meta
+                .overrides
+                .retain(|remote, _| !nodes.contains(remote));
+            value.meta.mirrored.retain(|remote| !nodes.contains(remote));
+        }
+        // The highlighted path is no longer valid once one of its nodes is gone
+        if self.path_nodes.iter().any(|node| nodes.contains(node)) {
+            self.path_nodes.clear();
+        }
+        // A later node could get the id and be merged by accident
+        self.merge_source.take_if(|source| nodes.contains(source));
+    }
+
+    // Swap the node's variant, demoting the existing Start/Finish and dropping
+    // connections the new variant doesn't allow
+    fn convert_node(&mut self, node: NodeId, target: &NodeKind, snarl: &mut Snarl<DijkstraNode>) {
+        let is_endpoint = matches!(target, NodeKind::Start | NodeKind::Finish(_));
+        let existing = snarl
+            .nodes_ids_data()
+            .find(|(id, other)| {
+                *id != node
+                    && is_endpoint
+                    && std::mem::discriminant(&other.value.kind) == std::mem::discriminant(target)
+            })
+            .map(|(id, _)| id);
+
+        let mut dropped = 0;
+        if let Some(existing) = existing {
+            let kind = snarl[existing]
+                .kind
+                .converted(&NodeKind::Distance(HashMap::new()));
+            snarl[existing].kind = kind;
+            dropped += drop_invalid_wires(existing, snarl);
+        }
+        let kind = snarl[node].kind.converted(target);
+        snarl[node].kind = kind;
+        dropped += drop_invalid_wires(node, snarl);
+        self.path_nodes.clear();
+        self.mark_changed();
+
+        let mut msg = trf(Text::ConvertedTo, &[&target.name()]);
+        if existing.is_some() {
+            msg += &trf(Text::PreviousBecameDistance, &[&target.name()]);
+        }
+        if dropped > 0 {
+            msg += &trf(Text::RemovedInvalidConnections, &[&dropped]);
+        }
+        self.add_success_notification(msg);
+    }
+
+    // Move all connections of `source` over to `target` and remove `source`
+    fn merge_nodes(&mut self, source: NodeId, target: NodeId, snarl: &mut Snarl<DijkstraNode>) {
+        self.merge_source = None;
+        self.mark_changed();
+        if matches!(
+            (&snarl[source].kind, &snarl[target].kind),
+            (NodeKind::Start, NodeKind::Finish(_)) | (NodeKind::Finish(_), NodeKind::Start)
+        ) {
+            self.add_error_notification(tr(Text::CantMergeEndpoints));
+            return;
+        }
+
+        let wires: Vec<(NodeId, NodeId)> = snarl
+            .wires()
+            .filter(|(out_pin, in_pin)| out_pin.node == source || in_pin.node == source)
+            .map(|(out_pin, in_pin)| (out_pin.node, in_pin.node))
+            .collect();
+        let source_costs = match &snarl[source].kind {
+            NodeKind::Distance(costs) | NodeKind::Finish(costs) => costs.clone(),
+            NodeKind::Start => HashMap::new(),
+        };
+        let source_overrides = snarl[source].meta.overrides.clone();
+
+        let mut moved = 0;
+        let mut skipped = 0;
+        for (from, to) in wires {
+            let from = if from == source { target } else { from };
+            let to = if to == source { target } else { to };
+            if from == to
+                || is_connected(snarl, from, to)
+                || !can_connect(&snarl[from].kind, &snarl[to].kind)
+            {
+                skipped += 1;
+                continue;
+            }
+            snarl.connect(
+                OutPinId {
+                    node: from,
+                    output: 0,
+                },
+                InPinId { node: to, input: 0 },
+            );
+            moved += 1;
+
+            // Carry the existing cost over, rekeyed from the source to the target
+            let cost = if to == target {
+                source_costs.get(&from).copied()
+            } else {
+                match &snarl[to].kind {
+                    NodeKind::Distance(costs) | NodeKind::Finish(costs) => {
+                        costs.get(&source).copied()
+                    }
+                    NodeKind::Start => None,
+                }
+            };
+            if let (Some(cost), NodeKind::Distance(costs) | NodeKind::Finish(costs)) =
+                (cost, &mut snarl[to].kind)
+            {
+                costs.entry(from).or_insert(cost);
+            }
+            let manual = if to == target {
+                source_overrides.get(&from).copied()
+            } else {
+                snarl[to].meta.overrides.remove(&source)
+            };
+            if let Some(manual) = manual {
+                snarl[to].meta.overrides.entry(from).or_insert(manual);
+            }
+        }
+
+        self.remove_nodes(&[source], snarl);
+        let mut msg = trf(Text::MergedNode, &[&source.0, &target.0]);
+        if skipped > 0 {
+            msg += &trf(Text::MergeSkipped, &[&moved, &skipped]);
+        }
+        self.add_success_notification(msg);
+    }
+
+    // Replace the connection `from` -> `to` with `from` -> new Distance node -> `to`,
+    // placing the new node halfway along the wire
+    fn split_connection(&mut self, from: NodeId, to: NodeId, snarl: &mut Snarl<DijkstraNode>) {
+        let (Some(from_rect), Some(to_rect)) = (
+            self.stored_nodes.get(&from).copied(),
+            self.stored_nodes.get(&to).copied(),
+        ) else {
+            return;
+        };
+        let out_pin = OutPinId {
+            node: from,
+            output: 0,
+        };
+        let in_pin = InPinId { node: to, input: 0 };
+        snarl.disconnect(out_pin, in_pin);
+        self.mark_changed();
+        if let NodeKind::Distance(costs) | NodeKind::Finish(costs) = &mut snarl[to].kind {
+            costs.remove(&from);
+        }
+        forget_mirror(snarl, from, to);
+
+        let midpoint = from_rect.right_center().lerp(to_rect.left_center(), 0.5);
+        let rect = Rect::from_center_size(midpoint, from_rect.size());
+        // Only split while the snarl is shown, where it has the drawn positions
+        let new_node = snarl.insert_node(
+            rect.min + self.view_shift,
+            DijkstraNode::new(NodeKind::Distance(HashMap::new())),
+        );
+        // Estimate the rect until the node is drawn, so costs don't wait for another frame
+        self.stored_nodes.insert(new_node, rect);
+
+        let new_in = snarl.in_pin(InPinId {
+            node: new_node,
+            input: 0,
+        });
+        self.connect_pins(&snarl.out_pin(out_pin), &new_in, snarl);
+        let new_out = snarl.out_pin(OutPinId {
+            node: new_node,
+            output: 0,
+        });
+        self.connect_pins(&new_out, &snarl.in_pin(in_pin), snarl);
+        self.path_nodes.clear();
+    }
+
+    // Connect unless it's the same node, already connected or not allowed, returns whether
+    // the wire was added
+    fn connect_pins(&mut self, from: &OutPin, to: &InPin, snarl: &mut Snarl<DijkstraNode>) -> bool {
+        if from.id.node == to.id.node {
+            self.add_error_notification(tr(Text::CantConnectToItself));
+            return false;
+        }
+        if is_connected(snarl, from.id.node, to.id.node) {
+            self.add_error_notification(tr(Text::AlreadyConnected));
+            return false;
+        }
+
+        // Allow all valid connections
+        if !can_connect(&snarl[from.id.node].kind, &snarl[to.id.node].kind) {
+            return false;
+        }
+        log::debug!("Connected node {} to {}", from.id.node.0, to.id.node.0);
+        snarl.connect(from.id, to.id);
+        self.mark_changed();
+        true
+    }
+
+    // Connect `to` back to `from` too, the way back costs what the connection there does
+    // and follows it when costs are recalculated
+    fn make_two_way(&mut self, from: NodeId, to: NodeId, snarl: &mut Snarl<DijkstraNode>) {
+        if !is_connected(snarl, to, from) {
+            snarl.connect(
+                OutPinId {
+                    node: to,
+                    output: 0,
+                },
+                InPinId {
+                    node: from,
+                    input: 0,
+                },
+            );
+        }
+        log::debug!("Made the connection of {} and {} two-way", from.0, to.0);
+        snarl[from].meta.mirrored.insert(to);
+        // The mirrored direction has no cost of its own
+        snarl[from].meta.overrides.remove(&to);
+        self.mark_changed();
+    }
+
+    // Insert a copy of the node to its right, without any of its connections
+    fn duplicate_node(&mut self, node: NodeId, snarl: &mut Snarl<DijkstraNode>) {
+        let Some(info) = snarl.get_node_info(node) else {
+            return;
+        };
+        let kind = match &info.value.kind {
+            NodeKind::Distance(_) => NodeKind::Distance(HashMap::new()),
+            NodeKind::Start | NodeKind::Finish(_) => {
+                self.add_warning_notification(tr(Text::DuplicatedAsDistance));
+                NodeKind::Distance(HashMap::new())
+            }
+        };
+
+        let rect = self.stored_nodes.get(&node).copied();
+        let width = rect.map_or(0.0, |rect| rect.width());
+        let offset = egui::vec2(width + DUPLICATE_GAP, 0.0);
+        let value = DijkstraNode {
+            kind,
+            meta: info.value.meta.clone(),
+        };
+        let new_node = snarl.insert_node(info.pos + offset, value);
+        self.mark_changed();
+
+        // Track the rect right away so the cost update doesn't wait for the next frame
+        if let Some(rect) = rect {
+            self.stored_nodes.insert(new_node, rect.translate(offset));
+        }
+    }
+
+    // Recalculate the costs of changed connections from the stored node rects, returns
+    // whether any cost changed
+    fn update_costs(&mut self, snarl: &mut Snarl<DijkstraNode>) -> bool {
+        if !self.costs_dirty && self.moved_nodes.is_empty() {
+            return false;
+        }
+        let started = web_time::Instant::now();
+        let mut adjacency = self.adjacency.take().unwrap_or_else(|| wire_costs(snarl));
+        let changed = if self.costs_dirty {
+            recalculate_costs(snarl, &adjacency, &self.stored_nodes, self.cost_scale)
+        } else {
+            // A moved node changes the length of wires on both of its sides, every wire
+            // into an affected node is needed to compute its costs
+            let targets: HashSet<NodeId> = adjacency
+                .iter()
+                .filter(|(from, to, _)| {
+                    self.moved_nodes.contains(from) || self.moved_nodes.contains(to)
+                })
+                .map(|(_, to, _)| *to)
+                .collect();
+            let wires: Vec<(NodeId, NodeId, Cost)> = adjacency
+                .iter()
+                .filter(|(_, to, _)| targets.contains(to))
+                .copied()
+                .collect();
+            recalculate_costs(snarl, &wires, &self.stored_nodes, self.cost_scale)
+        };
+        self.costs_dirty = false;
+        self.moved_nodes.clear();
+        if changed {
+            for (from, to, cost) in adjacency.iter_mut() {
+                *cost = edge_cost(snarl, *from, *to);
+            }
+            self.graph_version += 1;
+        }
+        self.adjacency = Some(adjacency);
+        self.costs_time += started.elapsed();
+        changed
+    }
+
+    fn tracked(&self, node: NodeId, side: PinSide, info: PinInfo) -> TrackedPin {
+        TrackedPin {
+            info,
+            key: (node, side),
+            rects: Rc::clone(&self.pin_rects),
+            size: self.closeness.as_ref().map_or(1.0, |closeness| {
+                let closeness = closeness.get(&node).copied().unwrap_or(0.0);
+                MIN_PIN_SIZE + (MAX_PIN_SIZE - MIN_PIN_SIZE) * closeness
+            }),
+        }
+    }
+
+    // Pins on the path also stand out by shape and outline, not only by color
+    fn path_cue(&self, node: NodeId, info: PinInfo, ui: &egui::Ui) -> PinInfo {
+        if !self.path_nodes.contains(&node) {
+            return info;
+        }
+        info.with_shape(PinShape::Star)
+            .with_stroke(egui::Stroke::new(2.0, ui.visuals().strong_text_color()))
+    }
+
+    // The path highlight takes precedence over the node's own color, which takes precedence
+    // over the endpoint colors
+    fn pin_fill(&self, node: NodeId, value: &DijkstraNode) -> Option<Color32> {
+        match value.kind {
+            NodeKind::Distance(_) if self.path_nodes.contains(&node) => Some(self.palette.path),
+            NodeKind::Distance(_) => value.meta.color,
+            NodeKind::Start => Some(value.meta.color.unwrap_or(self.start_color)),
+            NodeKind::Finish(_) => Some(value.meta.color.unwrap_or(self.finish_color)),
+        }
+    }
+
+    fn add_error_notification(&mut self, msg: impl Into<WidgetText>) {
+        let msg = msg.into();
+        log::error!("{}", msg.text());
+        self.toasts.notify(notifications::Level::Error, msg.text());
+    }
+
+    fn add_warning_notification(&mut self, msg: impl Into<WidgetText>) {
+        let msg = msg.into();
+        log::warn!("{}", msg.text());
+        self.toasts
+            .notify(notifications::Level::Warning, msg.text());
+    }
+
+    fn add_info_notification(&mut self, msg: impl Into<WidgetText>) {
+        let msg = msg.into();
+        log::info!("{}", msg.text());
+        self.toasts.notify(notifications::Level::Info, msg.text());
+    }
+
+    fn add_success_notification(&mut self, msg: impl Into<WidgetText>) {
+        let msg = msg.into();
+        log::info!("{}", msg.text());
+        self.toasts
+            .notify(notifications::Level::Success, msg.text());
+    }
+}
+
+impl SnarlViewer<DijkstraNode> for DijkstraViewer {
+    fn title(&mut self, node: &DijkstraNode) -> String {
+        let name = node.title();
+        if node.meta.locked {
+            format!("{} 🔒", name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn node_frame(
+        &mut self,
+        default: egui::Frame,
+        node: NodeId,
+        _inputs: &[InPin],
+        _outputs: &[OutPin],
+        snarl: &Snarl<DijkstraNode>,
+    ) -> egui::Frame {
+        let default =
+            if self.dim_off_path && !self.path_nodes.is_empty() && !self.path_nodes.contains(&node)
+            {
+                default.multiply_with_opacity(DIMMED_OPACITY)
+            } else {
+                default
+            };
+        // The path stays readable, its nodes are only told apart by pin size
+        let default = match &self.closeness {
+            Some(closeness) if !self.path_nodes.contains(&node) => {
+                let closeness = closeness.get(&node).copied().unwrap_or(0.0);
+                default.multiply_with_opacity(FADED_OPACITY + (1.0 - FADED_OPACITY) * closeness)
+            }
+            _ => default,
+        };
+        if self.flashing == Some(node) {
+            return default.stroke(egui::Stroke::new(
+                default.stroke.width.max(1.0) * 3.0,
+                self.palette.flash,
+            ));
+        }
+        let Some(hovered) = self.hovered.filter(|_| self.highlight_neighbors) else {
+            return default;
+        };
+        if self.hovered_neighbors.is_none() {
+            let mut neighbors = (HashSet::new(), HashSet::new());
+            for (from, to, _) in self.adjacency(snarl) {
+                if *to == hovered {
+                    neighbors.0.insert(*from);
+                }
+                if *from == hovered {
+                    neighbors.1.insert(*to);
+                }
+            }
+            self.hovered_neighbors = Some(neighbors);
+        }
+        let Some((predecessors, successors)) = &self.hovered_neighbors else {
+            return default;
+        };
+        // Only the outline changes, the path keeps showing through the pins
+        let color = if predecessors.contains(&node) {
+            self.palette.predecessor
+        } else if successors.contains(&node) {
+            self.palette.successor
+        } else {
+            return default;
+        };
+        default.stroke(egui::Stroke::new(
+            default.stroke.width.max(1.0) * 2.0,
+            color,
+        ))
+    }
+
+    fn header_frame(
+        &mut self,
+        default: egui::Frame,
+        node: NodeId,
+        _inputs: &[InPin],
+        _outputs: &[OutPin],
+        snarl: &Snarl<DijkstraNode>,
+    ) -> egui::Frame {
+        match snarl.get_node(node).map(|node| &node.kind) {
+            Some(NodeKind::Start) => default.fill(self.start_color),
+            Some(NodeKind::Finish(_)) => default.fill(self.finish_color),
+            _ => default,
+        }
+    }
+
+    fn inputs(&mut self, node: &DijkstraNode) -> usize {
+        match node.kind {
+            NodeKind::Start => 0,
+            NodeKind::Distance(_) => 1, // Allow multiple inputs
+            NodeKind::Finish(_) => 1,   // Allow multiple inputs
+        }
+    }
+
+    fn show_input(
+        &mut self,
+        pin: &InPin,
+        ui: &mut egui::Ui,
+        scale: f32,
+        snarl: &mut Snarl<DijkstraNode>,
+    ) -> impl SnarlPin + 'static {
+        let fill = self.pin_fill(pin.id.node, &snarl[pin.id.node]);
+        let remotes = snarl.in_pin(pin.id).remotes.len();
+        let info = match &snarl[pin.id.node].kind {
+            // The snarl zooms the node's style, so the labels already follow the scale. Too
+            // small to read they would only overflow the node, a count takes their place.
+            NodeKind::Distance(_) if remotes > 0 && scale < self.cost_label_scale => {
+                let count = if remotes == 1 {
+                    tr(Text::OneInput).to_string()
+                } else {
+                    trf(Text::InputCount, &[&remotes])
+                };
+                ui.label(egui::RichText::new(count).weak());
+                PinInfo::triangle().with_fill(fill.unwrap_or(self.palette.node))
+            }
+            NodeKind::Distance(values) => {
+                // Display all remote nodes and their costs
+                if remotes > 0 {
+                    ui.vertical(|ui| {
+                        for remote in &snarl.in_pin(pin.id).remotes {
+                            let remote_node = remote.node;
+                            if let Some(&cost) = values.get(&remote_node) {
+                                let manual =
+                                    snarl[pin.id.node].meta.overrides.contains_key(&remote_node);
+                                let suffix = if manual { tr(Text::ManualSuffix) } else { "" };
+                                ui.label(
+                                    trf(Text::NodeCost, &[&remote_node.0, &format_cost(cost)])
+                                        + suffix,
+                                );
+                            }
+                        }
+                    });
+                }
+
+                PinInfo::triangle().with_fill(fill.unwrap_or(self.palette.node))
+            }
+            NodeKind::Finish(hash_map) => {
+                if let Some(cost) = self.path_nodes.iter().find_map(|node| hash_map.get(node)) {
+                    ui.label(trf(Text::Cost, &[&format_cost(*cost)]));
+                }
+                with_optional_fill(PinInfo::triangle(), fill)
+            }
+            // Start has no inputs, only a node converted during the frame ends up here
+            NodeKind::Start => with_optional_fill(PinInfo::triangle(), fill),
+        };
+        let info = self.path_cue(pin.id.node, info, ui);
+        self.tracked(pin.id.node, PinSide::Input, info)
+    }
+
+    fn outputs(&mut self, node: &DijkstraNode) -> usize {
+        match node.kind {
+            NodeKind::Start => 1,       // Allow multiple outputs
+            NodeKind::Distance(_) => 1, // Allow multiple outputs
+            NodeKind::Finish(_) => 0,
+        }
+    }
+
+    fn show_output(
+        &mut self,
+        pin: &OutPin,
+        ui: &mut egui::Ui,
+        _scale: f32,
+        snarl: &mut Snarl<DijkstraNode>,
+    ) -> impl SnarlPin + 'static {
+        let fill = self.pin_fill(pin.id.node, &snarl[pin.id.node]);
+        let info = match &snarl[pin.id.node].kind {
+            NodeKind::Distance(_) => PinInfo::circle().with_fill(fill.unwrap_or(self.palette.node)),
+            _ => with_optional_fill(PinInfo::default(), fill),
+        };
+        let info = self.path_cue(pin.id.node, info, ui);
+        self.tracked(pin.id.node, PinSide::Output, info)
+    }
+
+    fn has_body(&mut self, node: &DijkstraNode) -> bool {
+        self.show_notes && !node.meta.note.is_empty()
+    }
+
+    fn show_body(
+        &mut self,
+        node: NodeId,
+        _inputs: &[InPin],
+        _outputs: &[OutPin],
+        ui: &mut egui::Ui,
+        _scale: f32,
+        snarl: &mut Snarl<DijkstraNode>,
+    ) {
+        let note = &snarl[node].meta.note;
+        let first_line = note.lines().next().unwrap_or_default();
+        let text = if note.lines().nth(1).is_some() {
+            format!("{}…", first_line)
+        } else {
+            first_line.to_string()
+        };
+        ui.add(egui::Label::new(egui::RichText::new(text).small().weak()).truncate());
+    }
+
+    fn has_on_hover_popup(&mut self, node: &DijkstraNode) -> bool {
+        !node.meta.note.is_empty()
+    }
+
+    fn show_on_hover_popup(
+        &mut self,
+        node: NodeId,
+        _inputs: &[InPin],
+        _outputs: &[OutPin],
+        ui: &mut egui::Ui,
+        _scale: f32,
+        snarl: &mut Snarl<DijkstraNode>,
+    ) {
+        ui.label(&snarl[node].meta.note);
+    }
+
+    fn has_graph_menu(&mut self, _pos: egui::Pos2, _snarl: &mut Snarl<DijkstraNode>) -> bool {
+        true
+    }
+
+    fn show_graph_menu(
+        &mut self,
+        pos: egui::Pos2,
+        ui: &mut egui::Ui,
+        _scale: f32,
+        snarl: &mut Snarl<DijkstraNode>,
+    ) {
+        ui.label(tr(Text::AddNode));
+        if snarl
+            .nodes()
+            .all(|node| !matches!(node.kind, NodeKind::Start))
+            && ui.button(tr(Text::Start)).clicked()
+        {
+            snarl.insert_node(pos, DijkstraNode::new(NodeKind::Start));
+            self.mark_changed();
+            ui.close_menu();
+        }
+        if ui.button(tr(Text::Value)).clicked() {
+            snarl.insert_node(pos, DijkstraNode::new(NodeKind::Distance(HashMap::new())));
+            self.mark_changed();
+            ui.close_menu();
+        }
+        if snarl
+            .nodes()
+            .all(|node| !matches!(node.kind, NodeKind::Finish(_)))
+            && ui.button(tr(Text::Finish)).clicked()
+        {
+            snarl.insert_node(pos, DijkstraNode::new(NodeKind::Finish(HashMap::new())));
+            self.mark_changed();
+            ui.close_menu();
+        }
+    }
+
+    fn has_node_menu(&mut self, _node: &DijkstraNode) -> bool {
+        true
+    }
+
+    fn show_node_menu(
+        &mut self,
+        node: egui_snarl::NodeId,
+        _inputs: &[InPin],
+        _outputs: &[OutPin],
+        ui: &mut egui::Ui,
+        _scale: f32,
+        snarl: &mut Snarl<DijkstraNode>,
+    ) {
+        ui.label(tr(Text::NodeOptions));
+        ui.horizontal(|ui| {
+            ui.label(tr(Text::Label));
+            ui.text_edit_singleline(&mut snarl[node].meta.label);
+        });
+        match self.merge_source {
+            Some(source) if source != node && snarl.get_node(source).is_some() => {
+                if ui
+                    .button(trf(Text::MergeNodeIntoThis, &[&source.0]))
+                    .clicked()
+                {
+                    self.merge_nodes(source, node, snarl);
+                    ui.close_menu();
+                }
+                if ui.button(tr(Text::CancelMerge)).clicked() {
+                    self.merge_source = None;
+                    ui.close_menu();
+                }
+            }
+            _ => {
+                if ui.button(tr(Text::MergeInto)).clicked() {
+                    self.merge_source = Some(node);
+                    self.add_success_notification(tr(Text::MergePickTarget));
+                    ui.close_menu();
+                }
+            }
+        }
+        ui.horizontal(|ui| {
+            ui.label(tr(Text::Color));
+            let meta = &mut snarl[node].meta;
+            let mut color = meta.color.unwrap_or(self.palette.node);
+            if egui::color_picker::color_edit_button_srgba(
+                ui,
+                &mut color,
+                egui::color_picker::Alpha::Opaque,
+            )
+            .changed()
+            {
+                meta.color = Some(color);
+            }
+            if meta.color.is_some() && ui.button(tr(Text::Reset)).clicked() {
+                meta.color = None;
+            }
+        });
+        for target in [
+            NodeKind::Start,
+            NodeKind::Distance(HashMap::new()),
+            NodeKind::Finish(HashMap::new()),
+        ] {
+            if std::mem::discriminant(&target) == std::mem::discriminant(&snarl[node].kind) {
+                continue;
+            }
+            // Picking an endpoint among the Distance nodes is the usual case, worded as such
+            let text = match (&snarl[node].kind, &target) {
+                (NodeKind::Distance(_), NodeKind::Start) => tr(Text::SetAsStart).to_string(),
+                (NodeKind::Distance(_), NodeKind::Finish(_)) => tr(Text::SetAsFinish).to_string(),
+                _ => trf(Text::ConvertTo, &[&target.name()]),
+            };
+            if ui.button(text).clicked() {
+                self.convert_node(node, &target, snarl);
+                ui.close_menu();
+            }
+        }
+        let targets: Vec<NodeId> = snarl
+            .wires()
+            .filter(|(out_pin, _)| out_pin.node == node)
+            .map(|(_, in_pin)| in_pin.node)
+            .collect();
+        // Only Distance nodes can be connected both ways
+        let one_way: Vec<NodeId> = targets
+            .iter()
+            .copied()
+            .filter(|target| {
+                matches!(snarl[node].kind, NodeKind::Distance(_))
+                    && matches!(snarl[*target].kind, NodeKind::Distance(_))
+                    && !snarl[node].meta.mirrored.contains(target)
+                    && !snarl[*target].meta.mirrored.contains(&node)
+            })
+            .collect();
+        if !one_way.is_empty() {
+            ui.menu_button(tr(Text::MakeBidirectional), |ui| {
+                for target in one_way {
+                    let label = format!("{} {}", snarl[target].kind.name(), target.0);
+                    if ui.button(label).clicked() {
+                        self.make_two_way(node, target, snarl);
+                        ui.close_menu();
+                    }
+                }
+            });
+        }
+        if !targets.is_empty() {
+            ui.menu_button(tr(Text::SplitConnectionTo), |ui| {
+                for target in targets {
+                    let label = format!("{} {}", snarl[target].kind.name(), target.0);
+                    if ui.button(label).clicked() {
+                        self.split_connection(node, target, snarl);
+                        ui.close_menu();
+                    }
+                }
+            });
+        }
+        ui.checkbox(&mut snarl[node].meta.locked, tr(Text::LockPosition));
+        if !snarl[node].meta.overrides.is_empty() && ui.button(tr(Text::ResetManualCosts)).clicked()
+        {
+            snarl[node].meta.overrides.clear();
+            self.costs_dirty = true;
+            ui.close_menu();
+        }
+        ui.label(tr(Text::Note));
+        ui.add(
+            egui::TextEdit::multiline(&mut snarl[node].meta.note)
+                .desired_rows(3)
+                .hint_text(tr(Text::NoteHint)),
+        );
+        if ui.button(tr(Text::Duplicate)).clicked() {
+            self.duplicate_node(node, snarl);
+            ui.close_menu();
+        }
+        if ui.button(tr(Text::Remove)).clicked() {
+            self.remove_nodes(&[node], snarl);
+            ui.close_menu();
+        }
+    }
+
+    fn connect(&mut self, from: &OutPin, to: &InPin, snarl: &mut Snarl<DijkstraNode>) {
+        if !self.connect_pins(from, to, snarl) {
+            return;
+        }
+        // Shift while dropping the wire connects two Distance nodes both ways
+        if self.two_way_modifier
+            && matches!(snarl[from.id.node].kind, NodeKind::Distance(_))
+            && matches!(snarl[to.id.node].kind, NodeKind::Distance(_))
+        {
+            self.make_two_way(from.id.node, to.id.node, snarl);
+        }
+    }
+
+    fn disconnect(&mut self, from: &OutPin, to: &InPin, snarl: &mut Snarl<DijkstraNode>) {
+        log::debug!("Disconnected node {} from {}", from.id.node.0, to.id.node.0);
+        snarl.disconnect(from.id, to.id);
+        if let NodeKind::Distance(costs) | NodeKind::Finish(costs) = &mut snarl[to.id.node].kind {
+            costs.remove(&from.id.node);
+        }
+        // The other direction stays, with a cost of its own
+        forget_mirror(snarl, from.id.node, to.id.node);
+        self.mark_changed();
+    }
+
+    fn drop_outputs(&mut self, pin: &OutPin, snarl: &mut Snarl<DijkstraNode>) {
+        snarl.drop_outputs(pin.id);
+        drop_stale_costs(snarl);
+        self.mark_changed();
+    }
+
+    fn drop_inputs(&mut self, pin: &InPin, snarl: &mut Snarl<DijkstraNode>) {
+        snarl.drop_inputs(pin.id);
+        drop_stale_costs(snarl);
+        self.mark_changed();
+    }
+
+    fn draw_background(
+        &mut self,
+        background: Option<&BackgroundPattern>,
+        viewport: &Viewport,
+        snarl_style: &SnarlStyle,
+        style: &egui::Style,
+        painter: &egui::Painter,
+        _snarl: &Snarl<DijkstraNode>,
+    ) {
+        self.view = Some(ViewTransform {
+            rect: viewport.rect,
+            scale: viewport.scale,
+            offset: viewport.offset,
+            shift: self.view_shift,
+        });
+        if let Some(background) = background {
+            background.draw(viewport, snarl_style, style, painter);
+        }
+    }
+
+    fn final_node_rect(
+        &mut self,
+        node: NodeId,
+        ui_rect: egui::Rect,
+        graph_rect: egui::Rect,
+        ui: &mut egui::Ui,
+        _scale: f32,
+        snarl: &mut Snarl<DijkstraNode>,
+    ) {
+        let graph_rect = graph_rect.translate(-self.view_shift);
+        // Rects of nodes removed without `remove_nodes` would be matched to reused ids
+        if self.stored_nodes.len() > snarl.nodes().count() {
+            self.stored_nodes
+                .retain(|node, _| snarl.get_node(*node).is_some());
+        }
+        if ui.rect_contains_pointer(ui_rect) {
+            self.hovered_now = Some(node);
+        }
+        // Costs only depend on the rects, so nothing is recomputed while the graph sits still
+        if self.stored_nodes.insert(node, graph_rect) != Some(graph_rect) {
+            self.moved_nodes.insert(node);
+        }
+    }
+}
+
+// Folder the file dialogs open in: `saved` in the working directory, created on first use,
+// or the documents folder when that isn't possible
+#[cfg(not(target_arch = "wasm32"))]
+fn saved_dir() -> Option<std::path::PathBuf> {
+    saved_dir_in(std::env::current_dir().ok().as_deref())
+}
+
+// `saved` in `base`, taking the documents or home folder when it can't be created
+#[cfg(not(target_arch = "wasm32"))]
+fn saved_dir_in(base: Option<&std::path::Path>) -> Option<std::path::PathBuf> {
+    base.map(|dir| dir.join("saved"))
+        .filter(|dir| std::fs::create_dir_all(dir).is_ok())
+        .or_else(dirs::document_dir)
+        .or_else(dirs::home_dir)
+}
+
+fn read_save(path: &std::path::Path) -> std::io::Result<String> {
+    save::decode(std::fs::read(path)?)
+}
+
+fn async_file_dialog() -> rfd::AsyncFileDialog {
+    let dialog = rfd::AsyncFileDialog::new();
+    // Browsers pick the folder themselves
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(dir) = saved_dir() {
+        return dialog.set_directory(dir);
+    }
+    dialog
+}
+
+// Browsers ask where to put the file once it's written, nothing is reported back
+#[cfg(target_arch = "wasm32")]
+fn download(file_name: String, contents: Vec<u8>) {
+    wasm_bindgen_futures::spawn_local(async move {
+        let dialog = rfd::AsyncFileDialog::new().set_file_name(file_name);
+        if let Some(file) = dialog.save_file().await {
+            let _ = file.write(&contents).await;
+        }
+    });
+}
+
+// Graph, Start and Finish of a search
+type SearchInput = (algorithm::Graph, NodeId, NodeId);
+
+// egui_snarl 0.7 can't be told its pan or scale. "Fit view" shifts the view, see
+// `DijkstraViewer::view_shift`, and zooms by feeding the snarl a zoom gesture at a point it
+// isn't covered.
+enum FitView {
+    // Waiting for the nodes to be drawn, their rects are needed
+    Requested,
+    // Injected into the input of the next frame
+    Zoom { factor: f32, pivot: Pos2 },
+}
+
+struct PendingSearch {
+    id: u64,
+    // Graph version the search was started for
+    version: u64,
+    manual: bool,
+    progress: Arc<algorithm::Progress>,
+}
+
+// Outcome of a file dialog. Browsers don't give out paths, there the file is written or
+// read while the dialog is handled.
+enum DialogResult {
+    #[cfg(not(target_arch = "wasm32"))]
+    SaveAs(std::path::PathBuf),
+    #[cfg(not(target_arch = "wasm32"))]
+    Load(std::path::PathBuf),
+    #[cfg(target_arch = "wasm32")]
+    Downloaded(String),
+    #[cfg(target_arch = "wasm32")]
+    Opened(String, Vec<u8>),
+    Import(ImportFormat, Result<Vec<u8>, String>),
+    #[cfg(not(target_arch = "wasm32"))]
+    Export(std::path::PathBuf, Export),
+}
+
+// Contents of an export, written to the path picked in the dialog
+#[cfg(not(target_arch = "wasm32"))]
+struct Export {
+    // Filter name, extension and contents of each offered format, the first one is written
+    // when the picked name has none of the extensions
+    formats: Vec<(&'static str, &'static str, String)>,
+    // Extension and contents of a file written next to the export
+    sidecar: Option<(&'static str, String)>,
+}
+
+#[derive(Clone, Copy)]
+enum ImportFormat {
+    Dot,
+    GraphMl,
+    Matrix,
+    // Fills the text of the open edge list dialog
+    EdgeList,
+}
+
+impl ImportFormat {
+    fn filter(self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            ImportFormat::Dot => ("Graphviz DOT", &["dot", "gv"]),
+            ImportFormat::GraphMl => ("GraphML", &["graphml", "xml"]),
+            ImportFormat::Matrix => ("CSV", &["csv"]),
+            ImportFormat::EdgeList => (tr(Text::EdgeList), &["txt"]),
+        }
+    }
+}
+
+// Generate button of the generator dialogs, asking for confirmation first if there's a warning
+fn generate_button(ui: &mut egui::Ui, confirm: &mut bool, warning: Option<String>) -> bool {
+    let Some(warning) = warning else {
+        return ui.button(tr(Text::Generate)).clicked();
+    };
+    if !*confirm {
+        *confirm = ui.button(tr(Text::Generate)).clicked();
+        return false;
+    }
+
+    ui.label(warning);
+    let mut generate = false;
+    ui.horizontal(|ui| {
+        generate = ui.button(tr(Text::Replace)).clicked();
+        if ui.button(tr(Text::Cancel)).clicked() {
+            *confirm = false;
+        }
+    });
+    *confirm &= !generate;
+    generate
+}
+
+// Place nodes in columns by their distance from the nodes without incoming connections
+fn layered_layout(count: usize, edges: &[(usize, usize)]) -> Vec<Pos2> {
+    let mut level: Vec<Option<usize>> = vec![None; count];
+    let mut queue: VecDeque<usize> = (0..count)
+        .filter(|node| edges.iter().all(|(_, to)| to != node))
+        .collect();
+    for node in &queue {
+        level[*node] = Some(0);
+    }
+    loop {
+        while let Some(node) = queue.pop_front() {
+            let next = level[node].unwrap_or_default() + 1;
+            for (_, to) in edges.iter().filter(|(from, _)| *from == node) {
+                if level[*to].is_none() {
+                    level[*to] = Some(next);
+                    queue.push_back(*to);
+                }
+            }
+        }
+        // A cycle without an entry point, start again from its first node
+        match level.iter().position(Option::is_none) {
+            Some(node) => {
+                level[node] = Some(0);
+                queue.push_back(node);
+            }
+            None => break,
+        }
+    }
+
+    let mut rows: HashMap<usize, usize> = HashMap::new();
+    level
+        .into_iter()
+        .map(|level| {
+            let level = level.unwrap_or_default();
+            let row = rows.entry(level).or_default();
+            let pos = egui::pos2(
+                level as f32 * LAYOUT_SPACING.x,
+                *row as f32 * LAYOUT_SPACING.y,
+            );
+            *row += 1;
+            pos
+        })
+        .collect()
+}
+
+// Length of a connection between two node rects, from the output side to the input side
+fn geometric_distance(from: Rect, to: Rect) -> f32 {
+    from.right_center().distance(to.left_center())
+}
+
+// Cost of a connection between two node rects, the same formula final_node_rect uses
+fn geometric_cost(from: Rect, to: Rect, scale: CostScale) -> Cost {
+    scale.cost(geometric_distance(from, to))
+}
+
+// Reset goes back to following the theme
+fn theme_color_edit(
+    ui: &mut egui::Ui,
+    label: &str,
+    setting: &mut Option<Color32>,
+    theme_color: Color32,
+) {
+    ui.horizontal(|ui| {
+        let mut color = setting.unwrap_or(theme_color);
+        if egui::color_picker::color_edit_button_srgba(
+            ui,
+            &mut color,
+            egui::color_picker::Alpha::Opaque,
+        )
+        .changed()
+        {
+            *setting = Some(color);
+        }
+        ui.label(label);
+        if setting.is_some() && ui.button(tr(Text::Reset)).clicked() {
+            *setting = None;
+        }
+    });
+}
+
+// An ordinary connection above a path connection, as they are drawn with the appearance
+fn appearance_preview(ui: &mut egui::Ui, appearance: &Appearance, palette: palette::Palette) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(160.0, 36.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 4.0, ui.visuals().extreme_bg_color);
+    let mut other = ui.visuals().widgets.noninteractive.fg_stroke.color;
+    if appearance.dim_off_path {
+        other = other.gamma_multiply(DIMMED_OPACITY);
+    }
+    let path = appearance.path_color.unwrap_or(palette.path);
+    let (left, right) = (rect.left() + 8.0, rect.right() - 8.0);
+    for (y, width, color) in [
+        (rect.top() + 11.0, appearance.wire_width, other),
+        (
+            rect.bottom() - 11.0,
+            appearance.wire_width * appearance.path_width,
+            path,
+        ),
+    ] {
+        painter.line_segment(
+            [egui::pos2(left, y), egui::pos2(right, y)],
+            egui::Stroke::new(width, color),
+        );
+    }
+}
+
+// The wire style is a graph setting, offered both with the other graph settings and in the
+// Appearance window
+fn wire_style_combo(ui: &mut egui::Ui, wire_style: &mut WireStyle) {
+    egui::ComboBox::from_label(tr(Text::WireStyle))
+        .selected_text(settings::wire_style_name(*wire_style))
+        .show_ui(ui, |ui| {
+            for style in settings::WIRE_STYLES {
+                ui.selectable_value(wire_style, style, settings::wire_style_name(style));
+            }
+        });
+}
+
+// Label of a node in windows, unlabeled ones are named by their translated type
+fn shown_label(graph: &model::Graph, node: NodeId) -> String {
+    match graph.node(node) {
+        Some(node) if node.label.is_empty() => format!("{} {}", node.role.kind().name(), node.id.0),
+        Some(node) => node.label.clone(),
+        None => format!("{} {}", tr(Text::Node), node.0),
+    }
+}
+
+// Label of a node in the editor, the kind and id for unlabeled ones
+fn node_label(snarl: &Snarl<DijkstraNode>, node: NodeId) -> String {
+    let value = &snarl[node];
+    if value.meta.label.is_empty() {
+        format!("{} {}", value.kind.name(), node.0)
+    } else {
+        value.meta.label.clone()
+    }
+}
+
+fn on_off(value: bool) -> &'static str {
+    tr(if value { Text::On } else { Text::Off })
+}
+
+fn with_optional_fill(info: PinInfo, fill: Option<Color32>) -> PinInfo {
+    match fill {
+        Some(fill) => info.with_fill(fill),
+        None => info,
+    }
+}
+
+// Wires may only lead from Start or Distance nodes into Distance or Finish nodes
+fn can_connect(from: &NodeKind, to: &NodeKind) -> bool {
+    matches!(
+        (from, to),
+        (NodeKind::Start, NodeKind::Distance(_))
+            | (NodeKind::Distance(_), NodeKind::Distance(_))
+            | (NodeKind::Distance(_), NodeKind::Finish(_))
+    )
+}
+
+// Disconnect wires of the node that are no longer allowed, returning how many were removed
+fn drop_invalid_wires(node: NodeId, snarl: &mut Snarl<DijkstraNode>) -> usize {
+    let invalid: Vec<(OutPinId, InPinId)> = snarl
+        .wires()
+        .filter(|(out_pin, in_pin)| out_pin.node == node || in_pin.node == node)
+        .filter(|(out_pin, in_pin)| {
+            !can_connect(&snarl[out_pin.node].kind, &snarl[in_pin.node].kind)
+        })
+        .collect();
+    for (out_pin, in_pin) in invalid.iter() {
+        snarl.disconnect(*out_pin, *in_pin);
+        match &mut snarl[in_pin.node].kind {
+            NodeKind::Distance(costs) | NodeKind::Finish(costs) => {
+                costs.remove(&out_pin.node);
+            }
+            NodeKind::Start => {}
+        }
+        forget_mirror(snarl, out_pin.node, in_pin.node);
+    }
+    invalid.len()
+}
+
+// The connection from `from` to `to` is gone, neither direction mirrors the other anymore
+fn forget_mirror(snarl: &mut Snarl<DijkstraNode>, from: NodeId, to: NodeId) {
+    snarl[to].meta.mirrored.remove(&from);
+    snarl[from].meta.mirrored.remove(&to);
+}
+
+// Drop costs kept for sources that aren't connected and manual costs for nodes that no
+// longer exist, a later node reusing the id would pick them up. Returns how many went.
+fn drop_stale_costs(snarl: &mut Snarl<DijkstraNode>) -> usize {
+    let wires: HashSet<(NodeId, NodeId)> = snarl
+        .wires()
+        .map(|(out_pin, in_pin)| (out_pin.node, in_pin.node))
+        .collect();
+    let nodes: HashSet<NodeId> = snarl.nodes_ids_data().map(|(id, _)| id).collect();
+    let mut dropped = 0;
+    for (node, value) in snarl.nodes_ids_mut() {
+        if let NodeKind::Distance(costs) | NodeKind::Finish(costs) = &mut value.kind {
+            let before = costs.len();
+            costs.retain(|remote, _| wires.contains(&(*remote, node)));
+            dropped += before - costs.len();
+        }
+        let before = value.meta.overrides.len();
+        value
+            .meta
+            .overrides
+            .retain(|remote, _| nodes.contains(remote));
+        dropped += before - value.meta.overrides.len();
+        // Two-way connections missing either direction
+        value
+            .meta
+            .mirrored
+            .retain(|remote| wires.contains(&(*remote, node)) && wires.contains(&(node, *remote)));
+    }
+    dropped
+}
+
+// Check whether any wire already leads from `from` to `to`, regardless of pin index
+fn is_connected(snarl: &Snarl<DijkstraNode>, from: NodeId, to: NodeId) -> bool {
+    snarl
+        .wires()
+        .any(|(out_pin, in_pin)| out_pin.node == from && in_pin.node == to)
+}
+
+// Hash of what a save would store, except costs which follow from the positions.
+// Compared against the hash at the last save or load to tell if there are unsaved edits.
+fn graph_fingerprint(snarl: &Snarl<DijkstraNode>) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    let mut nodes: Vec<(NodeId, Pos2, &DijkstraNode)> = snarl.nodes_pos_ids().collect();
+    nodes.sort_by_key(|(id, _, _)| id.0);
+    for (id, pos, node) in nodes {
+        (id.0, pos.x.to_bits(), pos.y.to_bits()).hash(&mut hasher);
+        std::mem::discriminant(&node.kind).hash(&mut hasher);
+        let meta = &node.meta;
+        (&meta.label, meta.color, &meta.note, meta.locked).hash(&mut hasher);
+        let mut overrides: Vec<(usize, u64)> = meta
+            .overrides
+            .iter()
+            .map(|(from, cost)| (from.0, cost.to_bits()))
+            .collect();
+        overrides.sort_unstable();
+        overrides.hash(&mut hasher);
+        let mut mirrored: Vec<usize> = meta.mirrored.iter().map(|remote| remote.0).collect();
+        mirrored.sort_unstable();
+        mirrored.hash(&mut hasher);
+    }
+    let mut wires: Vec<(usize, usize, usize, usize)> = snarl
+        .wires()
+        .map(|(out_pin, in_pin)| (out_pin.node.0, out_pin.output, in_pin.node.0, in_pin.input))
+        .collect();
+    wires.sort_unstable();
+    wires.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn modified_time(path: &std::path::Path) -> Option<std::time::SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+// Where the last frame's time went, shown in the profiling window
+#[derive(Default)]
+struct FrameTimes {
+    update: Duration,
+    snarl_show: Duration,
+    costs: Duration,
+}
+
+struct RunResult {
+    algorithm: &'static str,
+    path: Vec<NodeId>,
+    // Distance from Start to every node, infinite for unreachable ones
+    distances: HashMap<NodeId, Cost>,
+    predecessors: HashMap<NodeId, NodeId>,
+    total: Cost,
+    finished_at: web_time::SystemTime,
+}
+
+impl RunResult {
+    // 1 for Start down to 0 for the farthest reached node, unreachable nodes are left out
+    fn closeness(&self) -> HashMap<NodeId, f32> {
+        let farthest = self
+            .distances
+            .values()
+            .copied()
+            .filter(|dist| dist.is_finite())
+            .fold(0.0, Cost::max);
+        self.distances
+            .iter()
+            .filter(|(_, dist)| dist.is_finite())
+            .map(|(node, dist)| {
+                let closeness = if farthest > 0.0 {
+                    1.0 - dist / farthest
+                } else {
+                    1.0
+                };
+                (*node, closeness as f32)
+            })
+            .collect()
+    }
+
+    // Shortest route from Start to the node, empty for unreachable ones
+    fn route_to(&self, node: NodeId) -> Vec<NodeId> {
+        if self
+            .distances
+            .get(&node)
+            .is_none_or(|dist| dist.is_infinite())
+        {
+            return Vec::new();
+        }
+        let mut route = vec![node];
+        let mut current = node;
+        while let Some(prev) = self.predecessors.get(&current) {
+            current = *prev;
+            route.push(current);
+        }
+        route.reverse();
+        route
+    }
+}
+
+// Parsed CSV matrix waiting for Start and Finish to be picked
+struct MatrixImport {
+    matrix: matrix::Matrix,
+    start: usize,
+    finish: usize,
+}
+
+// Text of the edge list import dialog, kept with the last parse error until it's fixed
+#[derive(Default)]
+struct EdgeListImport {
+    text: String,
+    error: Option<String>,
+}
+
+const SESSION_KEY: &str = "session";
+const THEME_KEY: &str = "theme";
+const LANGUAGE_KEY: &str = "language";
+const APPEARANCE_KEY: &str = "appearance";
+const NOTIFICATIONS_KEY: &str = "notifications";
+const HELP_SEEN_KEY: &str = "help_seen";
+const RECALC_INTERVAL_KEY: &str = "recalc_interval";
+const LAYOUT_KEY: &str = "layout";
+
+// Which tool windows and panels were open, kept across sessions. Where the windows were is
+// in egui's memory, which eframe keeps by itself.
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+struct Layout {
+    show_log: bool,
+    show_profiling: bool,
+    show_path_summary: bool,
+    show_appearance: bool,
+    show_inspector: bool,
+    show_weights: bool,
+    show_notes: bool,
+    // None before it was first stored, the graph settings decide then
+    auto_recalc: Option<bool>,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            show_log: false,
+            show_profiling: false,
+            show_path_summary: false,
+            show_appearance: false,
+            show_inspector: true,
+            show_weights: true,
+            show_notes: true,
+            auto_recalc: None,
+        }
+    }
+}
+
+// How the graph view looks regardless of the graph, kept across sessions. The wire style
+// belongs to the graph settings instead, it's saved with each graph.
+#[derive(Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+struct Appearance {
+    wire_width: f32,
+    grid_spacing: f32,
+    collapsible: bool,
+    status_bar: bool,
+    // Off for screenshots and recordings that should stay still
+    path_pulse: bool,
+    // Zoom below which the costs inside nodes are replaced by their count
+    cost_label_scale: f32,
+    highlight_neighbors: bool,
+    legend: bool,
+    legend_in_exports: bool,
+    // Off for dense graphs where they only add noise
+    arrowheads: bool,
+    palette: palette::Preset,
+    // None follows the palette
+    path_color: Option<Color32>,
+    // Path wires are this many times as wide as the others
+    path_width: f32,
+    // Fade the nodes off the path while one is shown
+    dim_off_path: bool,
+    // Nodes far from Start fade out and get smaller pins, after a search
+    distance_emphasis: bool,
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Self {
+            wire_width: 2.0,
+            grid_spacing: 30.0,
+            collapsible: false,
+            status_bar: true,
+            path_pulse: true,
+            cost_label_scale: 0.5,
+            highlight_neighbors: true,
+            legend: true,
+            legend_in_exports: true,
+            arrowheads: true,
+            palette: palette::Preset::Default,
+            path_color: None,
+            path_width: 1.0,
+            dim_off_path: false,
+            distance_emphasis: false,
+        }
+    }
+}
+
+impl Appearance {
+    fn apply(&self, style: &mut SnarlStyle) {
+        style.wire_width = Some(self.wire_width);
+        style.bg_pattern = Some(BackgroundPattern::Grid(Grid::new(
+            egui::vec2(self.grid_spacing, self.grid_spacing),
+            0.0,
+        )));
+        style.collapsible = Some(self.collapsible);
+    }
+}
+
+// What is needed to pick up where the last run left off, kept in eframe's storage
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+struct Session {
+    reopen: bool,
+    file: Option<std::path::PathBuf>,
+    // The graph as it was on exit in the save file format, it may have unsaved changes
+    graph: Option<String>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            reopen: true,
+            file: None,
+            graph: None,
+        }
+    }
+}
+
+// Nodes copied to the clipboard, positioned relative to their bounding box.
+// Ids are the original ones and only used to rebuild connections and cost maps on paste.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ClipboardNodes {
+    nodes: Vec<(NodeId, Vec2, DijkstraNode)>,
+    wires: Vec<(NodeId, NodeId)>,
+}
+
+// Costs of the connections from `sources` into `node`, the distance between the pins turned
+// into a cost by `scale`. Manual costs win and are kept even while a source has no rect yet,
+// the way back of a two-way connection takes the cost of the way there.
+fn compute_incoming_costs(
+    node: NodeId,
+    sources: &[NodeId],
+    rects: &HashMap<NodeId, Rect>,
+    snarl: &Snarl<DijkstraNode>,
+    scale: CostScale,
+) -> HashMap<NodeId, Cost> {
+    let overrides = &snarl[node].meta.overrides;
+    let mut costs = HashMap::new();
+    for remote in sources {
+        if let Some(cost) = overrides.get(remote) {
+            costs.insert(*remote, *cost);
+            continue;
+        }
+        if snarl[node].meta.mirrored.contains(remote) {
+            if let Some(cost) = snarl[*remote].meta.overrides.get(&node) {
+                costs.insert(*remote, *cost);
+            } else if let (Some(node_rect), Some(remote_rect)) =
+                (rects.get(&node), rects.get(remote))
+            {
+                costs.insert(*remote, geometric_cost(*node_rect, *remote_rect, scale));
+            }
+            continue;
+        }
+        if let (Some(node_rect), Some(parent_rect)) = (rects.get(&node), rects.get(remote)) {
+            costs.insert(*remote, geometric_cost(*parent_rect, *node_rect, scale));
+        }
+    }
+    costs
+}
+
+// Set the costs into every node with a rect from the wire lengths, returns whether any changed
+pub fn recalculate_costs(
+    snarl: &mut Snarl<DijkstraNode>,
+    adjacency: &[(NodeId, NodeId, Cost)],
+    rects: &HashMap<NodeId, Rect>,
+    scale: CostScale,
+) -> bool {
+    // Sources of the connections into each node
+    let mut incoming: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+    for (from, to, _) in adjacency {
+        incoming.entry(*to).or_default().push(*from);
+    }
+    let mut changed = false;
+
+    for (node_id, sources) in &incoming {
+        if !rects.contains_key(node_id) {
+            continue;
+        }
+        let costs = compute_incoming_costs(*node_id, sources, rects, snarl, scale);
+        let kind = match &snarl[*node_id].kind {
+            NodeKind::Start => continue,
+            NodeKind::Distance(_) => NodeKind::Distance(costs),
+            NodeKind::Finish(_) => NodeKind::Finish(costs),
+        };
+        if snarl[*node_id].kind != kind {
+            snarl[*node_id].kind = kind;
+            changed = true;
+        }
+    }
+    changed
+}
+
+// Cost the search uses for a connection, a missing cost counts as 1 into Distance nodes
+// and 0 into the Finish
+fn edge_cost(snarl: &Snarl<DijkstraNode>, from: NodeId, to: NodeId) -> Cost {
+    match &snarl[to].kind {
+        NodeKind::Distance(costs) => *costs.get(&from).unwrap_or(&1.0),
+        NodeKind::Finish(costs) => *costs.get(&from).unwrap_or(&0.0),
+        NodeKind::Start => 0.0,
+    }
+}
+
+pub fn wire_costs(snarl: &Snarl<DijkstraNode>) -> Vec<(NodeId, NodeId, Cost)> {
+    snarl
+        .wires()
+        .map(|(out_pin, in_pin)| {
+            let (from, to) = (out_pin.node, in_pin.node);
+            (from, to, edge_cost(snarl, from, to))
+        })
+        .collect()
+}
+
+// Start and Finish of the graph, the search needs both
+pub fn endpoints(snarl: &Snarl<DijkstraNode>) -> Result<(NodeId, NodeId), String> {
+    let mut start_node = None;
+    let mut finish_node = None;
+    for (node_id, node) in snarl.nodes_ids_data() {
+        match node.value.kind {
+            NodeKind::Start => start_node = Some(node_id),
+            NodeKind::Finish(_) => finish_node = Some(node_id),
+            _ => {}
+        }
+    }
+    let start = start_node.ok_or(tr(Text::StartNotFound).to_string())?;
+    let finish = finish_node.ok_or(tr(Text::FinishNotFound).to_string())?;
+    Ok((start, finish))
+}
+
+// Nodes of the snarl with the viewer's cached connections
+pub fn search_graph(
+    snarl: &Snarl<DijkstraNode>,
+    adjacency: &[(NodeId, NodeId, Cost)],
+) -> algorithm::Graph {
+    let mut graph = algorithm::Graph::default();
+    for (node, _) in snarl.nodes_ids_data() {
+        graph.add_node(node);
+    }
+    for (from, to, cost) in adjacency {
+        graph.add_edge(*from, *to, *cost);
+    }
+    graph
+}
+
+// Remap cost keys to the pasted node ids, dropping costs of connections that weren't copied
+fn remap_costs(
+    costs: &HashMap<NodeId, Cost>,
+    ids: &HashMap<NodeId, NodeId>,
+) -> HashMap<NodeId, Cost> {
+    costs
+        .iter()
+        .filter_map(|(node, cost)| ids.get(node).map(|new| (*new, *cost)))
+        .collect()
+}
+
+// Layout operations on the selected nodes
+#[derive(Clone, Copy)]
+enum Alignment {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    CenterX,
+    CenterY,
+    DistributeX,
+    DistributeY,
+}
+
+// Settings of the "Generate grid" dialog
+struct GridParams {
+    rows: usize,
+    columns: usize,
+    spacing: f32,
+    two_way: bool,
+    // Set when generating would replace an existing Start/Finish
+    confirm_replace: bool,
+}
+
+impl Default for GridParams {
+    fn default() -> Self {
+        Self {
+            rows: 4,
+            columns: 5,
+            spacing: 150.0,
+            two_way: false,
+            confirm_replace: false,
+        }
+    }
+}
+
+// Settings of the "Generate random graph" dialog
+struct RandomParams {
+    nodes: usize,
+    edge_probability: f32,
+    area: Vec2,
+    seed: u64,
+    // Set when generating would remove the current graph
+    confirm_clear: bool,
+}
+
+impl Default for RandomParams {
+    fn default() -> Self {
+        Self {
+            nodes: 20,
+            edge_probability: 0.1,
+            area: egui::vec2(1200.0, 800.0),
+            seed: 42,
+            confirm_clear: false,
+        }
+    }
+}
+
+// Settings of the "Generate complete graph" dialog
+struct CompleteParams {
+    nodes: usize,
+    radius: f32,
+    confirm_clear: bool,
+}
+
+impl Default for CompleteParams {
+    fn default() -> Self {
+        Self {
+            nodes: 8,
+            radius: 400.0,
+            confirm_clear: false,
+        }
+    }
+}
+
+// Above this many nodes the per-frame cost recalculation of K_n gets noticeably slow
+const COMPLETE_GRAPH_WARN_NODES: usize = 30;
+
+// Settings of the "Generate maze" dialog
+struct MazeParams {
+    width: usize,
+    height: usize,
+    spacing: f32,
+    seed: u64,
+    // Share of dead ends that get an extra opening, creating alternative routes
+    braid: f32,
+    confirm_clear: bool,
+}
+
+impl Default for MazeParams {
+    fn default() -> Self {
+        Self {
+            width: 6,
+            height: 5,
+            spacing: 150.0,
+            seed: 1,
+            braid: 0.0,
+            confirm_clear: false,
+        }
+    }
+}
+
+// Small seeded generator (SplitMix64), so a seed produces the same graph on every platform
+struct SeededRng(u64);
+
+impl SeededRng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform float in 0..1
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+// Implement the eframe::App trait
+struct DijkstraApp {
+    snarl: Snarl<DijkstraNode>,
+    style: SnarlStyle,
+    viewer: DijkstraViewer,
+    // Saved with the graph, new graphs start from the user's preferences
+    settings: settings::GraphSettings,
+    // Cost of the highlighted path, None before any run
+    total_cost: Option<Cost>,
+    // Why the last run found no path or couldn't start, shown in the auto-recalc status
+    path_error: Option<String>,
+    grid_dialog: Option<GridParams>,
+    random_dialog: Option<RandomParams>,
+    complete_dialog: Option<CompleteParams>,
+    maze_dialog: Option<MazeParams>,
+    matrix_import: Option<MatrixImport>,
+    edge_list_import: Option<EdgeListImport>,
+    mermaid_direction: mermaid::Direction,
+    // Result of the last successful run, for exporting
+    last_run: Option<RunResult>,
+    // Distance table order and the row whose route is highlighted
+    distances_by_cost: bool,
+    distance_row: Option<NodeId>,
+    // File the graph was last saved to or loaded from, where Save writes without asking
+    current_file: Option<std::path::PathBuf>,
+    // Only collected while enabled, holds the steps of the last run
+    record_trace: bool,
+    trace: Vec<trace::TraceEntry>,
+    // Pass in which the clipboard was asked for to paste a whole graph
+    graph_paste_requested: Option<u64>,
+    // Pasted graph waiting for the user to confirm replacing the current one
+    graph_paste_confirm: Option<save::SaveFile>,
+    // "Remove all" was clicked, waiting for the user to confirm, there's no undo
+    remove_all_confirm: bool,
+    reopen_session: bool,
+    // Modification time of the current file when the app last wrote or read it
+    file_mtime: Option<std::time::SystemTime>,
+    // Fingerprint of the graph at that point, unknown after restoring unsaved changes
+    saved_fingerprint: Option<u64>,
+    last_file_check: f64,
+    // The current file was changed by another program and the user hasn't decided yet
+    external_change: bool,
+    // Open Save As or Load dialog, the picked path arrives here, or None when cancelled
+    pending_dialog: Option<std::sync::mpsc::Receiver<Option<DialogResult>>>,
+    search_worker: worker::SearchWorker,
+    next_search_id: u64,
+    // Search waiting for the worker, results of earlier ones are ignored
+    pending_search: Option<PendingSearch>,
+    // Graph version the shown result was computed for, so auto-recalc skips unchanged graphs
+    last_searched: Option<u64>,
+    // Graph version the highlighted path was found for, it's outdated after any change
+    path_version: Option<u64>,
+    // Time of the last auto-recalc search, they are spaced out while a node is dragged
+    last_auto_search: f64,
+    // In milliseconds, 0 searches on every change. Kept across sessions.
+    recalc_interval: u64,
+    // A change is waiting for the interval to pass, the shown path may be behind
+    recalc_debounced: bool,
+    // Timings are always taken, the window showing them is hidden by default
+    show_profiling: bool,
+    show_log: bool,
+    show_path_summary: bool,
+    fit_view: Option<FitView>,
+    // Kept across sessions, starts out as the system theme
+    theme: egui::Theme,
+    language: Language,
+    appearance: Appearance,
+    show_appearance: bool,
+    // Opens by itself on the first launch
+    show_help: bool,
+    // Only the graph and a few controls are shown, toggled with F11
+    presentation: bool,
+    // Find window, opened with Ctrl+F, Enter steps through the matches
+    show_find: bool,
+    find_query: String,
+    find_index: usize,
+    // The query field takes the focus on the next frame
+    find_focus: bool,
+    // Node picked in the Find window and when
+    flash: Option<(NodeId, f64)>,
+    show_inspector: bool,
+    // Last node selected on its own, kept while the selection changes, e.g. during a drag
+    inspected: Option<NodeId>,
+    // Least severe level shown in the Log window
+    log_level: log::LevelFilter,
+    frame_times: FrameTimes,
+    // How long the worker took for the last result that was shown
+    search_time: Option<Duration>,
+}
+
+impl DijkstraApp {
+    // A file given on the command line replaces restoring the last session
+    fn new(cc: &CreationContext<'_>, file: Option<std::path::PathBuf>) -> Self {
+        // Picked before anything below shows a message
+        let language: Language = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, LANGUAGE_KEY))
+            .unwrap_or_default();
+        i18n::set_language(language);
+        let mut ss = SnarlStyle::new();
+        ss.pin_placement = Some(egui_snarl::ui::PinPlacement::Edge);
+        ss.min_scale = Some(MIN_SCALE);
+        ss.max_scale = Some(MAX_SCALE);
+        let mut viewer = DijkstraViewer::new();
+        if let Some(settings) = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, NOTIFICATIONS_KEY))
+        {
+            viewer.toasts.settings = settings;
+        }
+        let settings = settings::load_preferences().unwrap_or_else(|err| {
+            viewer.add_warning_notification(trf(Text::FailedToReadPreferences, &[&err]));
+            settings::GraphSettings::default()
+        });
+        let mut app = DijkstraApp {
+            snarl: Snarl::new(),
+            style: ss,
+            viewer,
+            settings,
+            total_cost: None,
+            path_error: None,
+            grid_dialog: None,
+            random_dialog: None,
+            complete_dialog: None,
+            maze_dialog: None,
+            matrix_import: None,
+            edge_list_import: None,
+            mermaid_direction: mermaid::Direction::LeftRight,
+            last_run: None,
+            distances_by_cost: false,
+            distance_row: None,
+            current_file: None,
+            record_trace: false,
+            trace: Vec::new(),
+            graph_paste_requested: None,
+            graph_paste_confirm: None,
+            remove_all_confirm: false,
+            reopen_session: true,
+            file_mtime: None,
+            saved_fingerprint: None,
+            last_file_check: 0.0,
+            external_change: false,
+            pending_dialog: None,
+            search_worker: worker::SearchWorker::spawn(cc.egui_ctx.clone()),
+            next_search_id: 0,
+            pending_search: None,
+            last_searched: None,
+            path_version: None,
+            show_profiling: false,
+            show_path_summary: false,
+            fit_view: None,
+            theme: cc
+                .storage
+                .and_then(|storage| eframe::get_value(storage, THEME_KEY))
+                .unwrap_or(cc.egui_ctx.theme()),
+            language,
+            appearance: cc
+                .storage
+                .and_then(|storage| eframe::get_value(storage, APPEARANCE_KEY))
+                .unwrap_or_default(),
+            show_appearance: false,
+            show_inspector: true,
+            inspected: None,
+            show_help: !cc
+                .storage
+                .and_then(|storage| eframe::get_value(storage, HELP_SEEN_KEY))
+                .unwrap_or(false),
+            presentation: false,
+            show_find: false,
+            find_query: String::new(),
+            find_index: 0,
+            find_focus: false,
+            flash: None,
+            show_log: false,
+            log_level: log::LevelFilter::Info,
+            frame_times: FrameTimes::default(),
+            search_time: None,
+            last_auto_search: f64::NEG_INFINITY,
+            recalc_interval: cc
+                .storage
+                .and_then(|storage| eframe::get_value(storage, RECALC_INTERVAL_KEY))
+                .unwrap_or(DEFAULT_RECALC_INTERVAL_MS),
+            recalc_debounced: false,
+        };
+        cc.egui_ctx.set_theme(app.theme);
+        let session: Option<Session> = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, SESSION_KEY));
+        match file {
+            Some(path) => {
+                app.reopen_session = session.is_none_or(|session| session.reopen);
+                app.open_path(path);
+            }
+            None => app.restore_session(session.unwrap_or_default()),
+        }
+        // After the session, the flag last used wins over the one of the restored graph
+        if let Some(layout) = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, LAYOUT_KEY))
+        {
+            app.apply_layout(layout);
+        }
+        app
+    }
+
+    fn layout(&self) -> Layout {
+        Layout {
+            show_log: self.show_log,
+            show_profiling: self.show_profiling,
+            show_path_summary: self.show_path_summary,
+            show_appearance: self.show_appearance,
+            show_inspector: self.show_inspector,
+            show_weights: self.viewer.show_weights,
+            show_notes: self.viewer.show_notes,
+            auto_recalc: Some(self.settings.auto_recalc),
+        }
+    }
+
+    fn apply_layout(&mut self, layout: Layout) {
+        self.show_log = layout.show_log;
+        self.show_profiling = layout.show_profiling;
+        self.show_path_summary = layout.show_path_summary;
+        self.show_appearance = layout.show_appearance;
+        self.show_inspector = layout.show_inspector;
+        self.viewer.show_weights = layout.show_weights;
+        self.viewer.show_notes = layout.show_notes;
+        if let Some(auto_recalc) = layout.auto_recalc {
+            self.settings.auto_recalc = auto_recalc;
+        }
+    }
+
+    // Windows go back to where they first open and the panels to their defaults
+    fn reset_layout(&mut self, ctx: &egui::Context) {
+        ctx.memory_mut(|memory| memory.reset_areas());
+        self.apply_layout(Layout::default());
+    }
+
+    // Bring back the graph the app was closed with, or at least the file it came from
+    fn restore_session(&mut self, session: Session) {
+        self.reopen_session = session.reopen;
+        if !session.reopen {
+            return;
+        }
+        let file = session.file.filter(|path| {
+            let exists = path.exists();
+            if !exists {
+                self.viewer
+                    .add_warning_notification(trf(Text::LastFileMissing, &[&path.display()]));
+            }
+            exists
+        });
+
+        if let Some(graph) = session.graph {
+            match save::load(&graph) {
+                Ok(save) => {
+                    self.apply_save_file(save);
+                    self.fit_view = Some(FitView::Requested);
+                    self.file_mtime = file.as_deref().and_then(modified_time);
+                    self.viewer.add_info_notification(match &file {
+                        Some(path) => trf(Text::RestoredSessionOf, &[&path.display()]),
+                        None => tr(Text::RestoredSession).to_string(),
+                    });
+                    self.current_file = file;
+                    return;
+                }
+                Err(err) => self
+                    .viewer
+                    .add_warning_notification(trf(Text::FailedToRestoreSession, &[&err])),
+            }
+        }
+        if let Some(path) = file {
+            self.open_path(path);
+            if let Some(path) = &self.current_file {
+                self.viewer
+                    .add_info_notification(trf(Text::Reopened, &[&path.display()]));
+            }
+        }
+    }
+
+    // Grid of Distance nodes connected both ways along the corridors of a generated maze,
+    // entered through the Start in the top-left cell and left through the Finish in the bottom-right
+    fn generate_maze(&mut self, params: &MazeParams) {
+        self.clear_graph();
+        self.fit_view = Some(FitView::Requested);
+
+        let size = egui::vec2(
+            (params.width - 1) as f32 * params.spacing,
+            (params.height - 1) as f32 * params.spacing,
+        );
+        let graph = generate::maze(
+            params.width,
+            params.height,
+            params.spacing,
+            params.braid,
+            params.seed,
+            self.view_center() - size / 2.0,
+        );
+        self.insert_generated(graph);
+    }
+
+    fn show_maze_dialog(&mut self, ctx: &egui::Context) {
+        let Some(mut params) = self.maze_dialog.take() else {
+            return;
+        };
+        let mut open = true;
+        let mut generate = false;
+        egui::Window::new(tr(Text::GenerateMaze))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("maze_params").show(ui, |ui| {
+                    // Start can't be connected straight to Finish, a single row needs a
+                    // cell between them
+                    let min_width = if params.height == 1 { 3 } else { 2 };
+                    ui.label(tr(Text::Width));
+                    ui.add(egui::DragValue::new(&mut params.width).range(min_width..=40));
+                    ui.end_row();
+                    ui.label(tr(Text::Height));
+                    ui.add(egui::DragValue::new(&mut params.height).range(1..=40));
+                    ui.end_row();
+                    ui.label(tr(Text::Spacing));
+                    ui.add(egui::DragValue::new(&mut params.spacing).range(50.0..=500.0));
+                    ui.end_row();
+                    ui.label(tr(Text::Seed));
+                    ui.add(egui::DragValue::new(&mut params.seed));
+                    ui.end_row();
+                    ui.label(tr(Text::Braid));
+                    ui.add(
+                        egui::Slider::new(&mut params.braid, 0.0..=1.0)
+                            .custom_formatter(|value, _| format!("{:.0}%", value * 100.0)),
+                    );
+                    ui.end_row();
+                });
+                generate = generate_button(ui, &mut params.confirm_clear, self.clear_warning());
+            });
+
+        if generate {
+            self.generate_maze(&params);
+        }
+        if open {
+            self.maze_dialog = Some(params);
+        }
+    }
+
+    // Place the nodes on a circle and connect every ordered pair,
+    // with Start and Finish on opposite sides
+    fn generate_complete(&mut self, params: &CompleteParams) {
+        self.clear_graph();
+        self.fit_view = Some(FitView::Requested);
+
+        let graph = generate::complete(params.nodes, params.radius, self.view_center());
+        self.insert_generated(graph);
+    }
+
+    fn show_complete_dialog(&mut self, ctx: &egui::Context) {
+        let Some(mut params) = self.complete_dialog.take() else {
+            return;
+        };
+        let mut open = true;
+        let mut generate = false;
+        egui::Window::new(tr(Text::GenerateComplete))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("complete_params").show(ui, |ui| {
+                    ui.label(tr(Text::Nodes));
+                    ui.add(egui::DragValue::new(&mut params.nodes).range(2..=100));
+                    ui.end_row();
+                    ui.label(tr(Text::Radius));
+                    ui.add(egui::DragValue::new(&mut params.radius).range(100.0..=5000.0));
+                    ui.end_row();
+                });
+                if params.nodes > COMPLETE_GRAPH_WARN_NODES {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        trf(Text::SlowToEdit, &[&(params.nodes * (params.nodes - 1))]),
+                    );
+                }
+
+                generate = generate_button(ui, &mut params.confirm_clear, self.clear_warning());
+            });
+
+        if generate {
+            self.generate_complete(&params);
+            return;
+        }
+        if open {
+            self.complete_dialog = Some(params);
+        }
+    }
+
+    // Place the matrix nodes on a circle and load them with the picked Start and Finish
+    fn import_matrix(&mut self, import: MatrixImport) {
+        let count = import.matrix.labels.len();
+        let center = self.view_center();
+        let radius =
+            (count as f32 * LAYOUT_SPACING.x / std::f32::consts::TAU).max(LAYOUT_SPACING.x);
+        let mut graph = model::Graph::default();
+        for (idx, label) in import.matrix.labels.into_iter().enumerate() {
+            let angle = std::f32::consts::TAU * idx as f32 / count as f32;
+            let role = match idx {
+                idx if idx == import.start => model::Role::Start,
+                idx if idx == import.finish => model::Role::Finish,
+                _ => model::Role::Distance,
+            };
+            let idx = graph.add_node(role, label);
+            graph.nodes[idx].pos = Some(center + egui::Vec2::angled(angle) * radius);
+        }
+        for (from, to, cost) in import.matrix.edges {
+            graph.add_edge(from, to, Some(cost));
+        }
+        self.load_imported(graph);
+    }
+
+    fn show_matrix_import_dialog(&mut self, ctx: &egui::Context) {
+        let Some(mut import) = self.matrix_import.take() else {
+            return;
+        };
+        let mut open = true;
+        let mut confirmed = false;
+        egui::Window::new(tr(Text::ImportCsvMatrix))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(trf(
+                    Text::NodesAndConnections,
+                    &[&import.matrix.labels.len(), &import.matrix.edges.len()],
+                ));
+                egui::Grid::new("matrix_import").show(ui, |ui| {
+                    for (name, index) in [
+                        (Text::Start, &mut import.start),
+                        (Text::Finish, &mut import.finish),
+                    ] {
+                        ui.label(tr(name));
+                        egui::ComboBox::from_id_salt(name)
+                            .selected_text(&import.matrix.labels[*index])
+                            .show_ui(ui, |ui| {
+                                for (idx, label) in import.matrix.labels.iter().enumerate() {
+                                    ui.selectable_value(index, idx, label);
+                                }
+                            });
+                        ui.end_row();
+                    }
+                });
+                if import.start == import.finish {
+                    ui.colored_label(ui.visuals().warn_fg_color, tr(Text::EndpointsMustDiffer));
+                }
+                ui.add_enabled_ui(import.start != import.finish, |ui| {
+                    confirmed = ui.button(tr(Text::Import)).clicked();
+                });
+            });
+
+        if confirmed {
+            self.import_matrix(import);
+            return;
+        }
+        if open {
+            self.matrix_import = Some(import);
+        }
+    }
+
+    fn show_edge_list_dialog(&mut self, ctx: &egui::Context) {
+        let Some(mut import) = self.edge_list_import.take() else {
+            return;
+        };
+        let mut open = true;
+        let mut open_file = false;
+        let mut confirmed = false;
+        let dialog_closed = self.pending_dialog.is_none();
+        egui::Window::new(tr(Text::ImportEdgeList))
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(tr(Text::EdgeListHelp));
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut import.text)
+                                .code_editor()
+                                .desired_rows(12)
+                                .desired_width(f32::INFINITY),
+                        );
+                    });
+                if let Some(err) = &import.error {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+                ui.horizontal(|ui| {
+                    open_file = ui
+                        .add_enabled(dialog_closed, egui::Button::new(tr(Text::OpenFile)))
+                        .clicked();
+                    confirmed = ui.button(tr(Text::Import)).clicked();
+                });
+            });
+
+        if open_file {
+            self.import_file(ctx, ImportFormat::EdgeList);
+        }
+        if confirmed {
+            match edgelist::parse(&import.text) {
+                Ok(graph) => {
+                    self.load_imported(graph);
+                    return;
+                }
+                Err(err) => import.error = Some(err),
+            }
+        }
+        if open {
+            self.edge_list_import = Some(import);
+        }
+    }
+
+    // Move the selected nodes so their rects line up, without changing node sizes
+    fn align_selected(&mut self, alignment: Alignment) {
+        let mut rects: Vec<(NodeId, Rect)> = self
+            .viewer
+            .selected
+            .iter()
+            .filter(|node| self.snarl.get_node(**node).is_some_and(|n| !n.meta.locked))
+            .filter_map(|node| Some((*node, *self.viewer.stored_nodes.get(node)?)))
+            .collect();
+        let Some(bounds) = rects
+            .iter()
+            .map(|(_, rect)| *rect)
+            .reduce(|a, b| a.union(b))
+        else {
+            return;
+        };
+
+        let mut moves: Vec<(NodeId, Vec2)> = rects
+            .iter()
+            .map(|(node, rect)| {
+                let delta = match alignment {
+                    Alignment::Left => egui::vec2(bounds.left() - rect.left(), 0.0),
+                    Alignment::Right => egui::vec2(bounds.right() - rect.right(), 0.0),
+                    Alignment::Top => egui::vec2(0.0, bounds.top() - rect.top()),
+                    Alignment::Bottom => egui::vec2(0.0, bounds.bottom() - rect.bottom()),
+                    Alignment::CenterX => egui::vec2(bounds.center().x - rect.center().x, 0.0),
+                    Alignment::CenterY => egui::vec2(0.0, bounds.center().y - rect.center().y),
+                    Alignment::DistributeX | Alignment::DistributeY => Vec2::ZERO,
+                };
+                (*node, delta)
+            })
+            .collect();
+
+        // Spread the centers evenly between the two outermost nodes
+        if let Alignment::DistributeX | Alignment::DistributeY = alignment {
+            let axis = match alignment {
+                Alignment::DistributeX => egui::vec2(1.0, 0.0),
+                _ => egui::vec2(0.0, 1.0),
+            };
+            rects.sort_by(|(_, a), (_, b)| {
+                a.center()
+                    .to_vec2()
+                    .dot(axis)
+                    .total_cmp(&b.center().to_vec2().dot(axis))
+            });
+            let first = rects[0].1.center().to_vec2().dot(axis);
+            let last = rects[rects.len() - 1].1.center().to_vec2().dot(axis);
+            let step = (last - first) / (rects.len() - 1).max(1) as f32;
+            moves = rects
+                .iter()
+                .enumerate()
+                .map(|(idx, (node, rect))| {
+                    let target = first + step * idx as f32;
+                    (*node, axis * (target - rect.center().to_vec2().dot(axis)))
+                })
+                .collect();
+        }
+
+        for (node, delta) in moves {
+            if let Some(info) = self.snarl.get_node_info_mut(node) {
+                info.pos += delta;
+            }
+            if let Some(rect) = self.viewer.stored_nodes.get_mut(&node) {
+                *rect = rect.translate(delta);
+            }
+            // The rects drawn next frame match the stored ones, so nothing else notices the move
+            self.viewer.moved_nodes.insert(node);
+        }
+        self.viewer.mark_changed();
+    }
+
+    fn show_align_tools(&mut self, ui: &mut egui::Ui) {
+        let selected = self.viewer.selected.len();
+        ui.label(tr(Text::AlignSelected));
+        ui.add_enabled_ui(selected >= 2, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for (label, alignment) in [
+                    (Text::Left, Alignment::Left),
+                    (Text::Right, Alignment::Right),
+                    (Text::Top, Alignment::Top),
+                    (Text::Bottom, Alignment::Bottom),
+                    (Text::CenterX, Alignment::CenterX),
+                    (Text::CenterY, Alignment::CenterY),
+                ] {
+                    if ui.button(tr(label)).clicked() {
+                        self.align_selected(alignment);
+                    }
+                }
+            });
+        });
+        ui.label(tr(Text::DistributeSelected));
+        ui.add_enabled_ui(selected >= 3, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button(tr(Text::Horizontally)).clicked() {
+                    self.align_selected(Alignment::DistributeX);
+                }
+                if ui.button(tr(Text::Vertically)).clicked() {
+                    self.align_selected(Alignment::DistributeY);
+                }
+            });
+        });
+    }
+
+    fn show_notification_settings(&mut self, ui: &mut egui::Ui) {
+        let settings = &mut self.viewer.toasts.settings;
+        ui.label(tr(Text::Notifications));
+        ui.add(
+            egui::Slider::new(&mut settings.duration, 1..=30)
+                .suffix(" s")
+                .text(tr(Text::NotificationDuration)),
+        );
+        ui.checkbox(&mut settings.sticky_errors, tr(Text::StickyErrors));
+        egui::ComboBox::from_label(tr(Text::NotificationPosition))
+            .selected_text(tr(settings.corner.text()))
+            .show_ui(ui, |ui| {
+                for corner in notifications::Corner::ALL {
+                    ui.selectable_value(&mut settings.corner, corner, tr(corner.text()));
+                }
+            });
+    }
+
+    fn clear_warning(&self) -> Option<String> {
+        let count = self.snarl.nodes().count();
+        (count > 0).then(|| trf(Text::RemovesCurrentNodes, &[&count]))
+    }
+
+    fn clear_graph(&mut self) {
+        let nodes: Vec<NodeId> = self.snarl.node_ids().map(|(id, _)| id).collect();
+        self.viewer.remove_nodes(&nodes, &mut self.snarl);
+        self.reset_results();
+    }
+
+    // Forget everything computed for the previous graph, its node ids may be reused
+    fn reset_results(&mut self) {
+        self.viewer.path_nodes.clear();
+        self.total_cost = None;
+        self.path_error = None;
+        self.last_run = None;
+        self.distance_row = None;
+        self.trace.clear();
+        self.last_searched = None;
+    }
+
+    fn clear_path(&mut self) {
+        self.viewer.path_nodes.clear();
+        self.total_cost = None;
+        self.path_error = None;
+        self.last_run = None;
+        self.distance_row = None;
+        self.last_searched = None;
+    }
+
+    // Without auto-recalc nothing catches up with edits made after the run
+    fn path_outdated(&self) -> bool {
+        !self.settings.auto_recalc
+            && !self.viewer.path_nodes.is_empty()
+            && self.path_version != Some(self.viewer.graph_version)
+    }
+
+    // Auto-recalc results don't show toasts, this keeps the outcome of the latest one in view
+    fn show_recalc_status(&self, ui: &mut egui::Ui) {
+        let (color, text) = if self.pending_search.is_some() || self.recalc_debounced {
+            (
+                ui.visuals().warn_fg_color,
+                tr(Text::Recalculating).to_string(),
+            )
+        } else if let Some(err) = &self.path_error {
+            (
+                ui.visuals().error_fg_color,
+                trf(Text::NoPathBecause, &[err]),
+            )
+        } else if let Some(cost) = self.total_cost
+            && !self.viewer.path_nodes.is_empty()
+        {
+            (STATUS_OK_COLOR, trf(Text::PathOk, &[&format_cost(cost)]))
+        } else {
+            return;
+        };
+        egui::Frame::new()
+            .fill(color.gamma_multiply(0.15))
+            .stroke(egui::Stroke::new(1.0, color))
+            .corner_radius(8.0)
+            .inner_margin(egui::vec2(6.0, 2.0))
+            .show(ui, |ui| {
+                ui.colored_label(color, text);
+            });
+    }
+
+    fn show_outdated_path_banner(&mut self, ctx: &egui::Context) {
+        if self.presentation || !self.path_outdated() {
+            return;
+        }
+        egui::TopBottomPanel::top("path_outdated").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.colored_label(ui.visuals().warn_fg_color, tr(Text::PathOutdated));
+                if ui.button(tr(Text::RunDijkstra)).clicked() {
+                    self.start_search(true);
+                }
+                if ui.button(tr(Text::ClearPath)).clicked() {
+                    self.clear_path();
+                }
+            });
+        });
+    }
+
+    fn notify_loaded(&mut self, name: &str) {
+        self.viewer.add_success_notification(trf(
+            Text::Loaded,
+            &[
+                &name,
+                &self.snarl.nodes().count(),
+                &self.snarl.wires().count(),
+            ],
+        ));
+    }
+
+    // Scatter Distance nodes over the area and connect ordered pairs with the given probability,
+    // the two nodes furthest apart become Start and Finish
+    fn generate_random(&mut self, params: &RandomParams) {
+        self.clear_graph();
+        self.fit_view = Some(FitView::Requested);
+        let graph = generate::random(
+            params.nodes,
+            params.edge_probability,
+            params.area,
+            params.seed,
+            self.view_center(),
+        );
+        self.insert_generated(graph);
+    }
+
+    fn show_random_dialog(&mut self, ctx: &egui::Context) {
+        let Some(mut params) = self.random_dialog.take() else {
+            return;
+        };
+        let mut open = true;
+        let mut generate = false;
+        egui::Window::new(tr(Text::GenerateRandom))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("random_params").show(ui, |ui| {
+                    ui.label(tr(Text::Nodes));
+                    ui.add(egui::DragValue::new(&mut params.nodes).range(2..=500));
+                    ui.end_row();
+                    ui.label(tr(Text::EdgeProbability));
+                    ui.add(egui::Slider::new(&mut params.edge_probability, 0.0..=1.0));
+                    ui.end_row();
+                    ui.label(tr(Text::Area));
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut params.area.x).range(100.0..=10000.0));
+                        ui.label("×");
+                        ui.add(egui::DragValue::new(&mut params.area.y).range(100.0..=10000.0));
+                    });
+                    ui.end_row();
+                    ui.label(tr(Text::Seed));
+                    ui.add(egui::DragValue::new(&mut params.seed));
+                    ui.end_row();
+                });
+
+                generate = generate_button(ui, &mut params.confirm_clear, self.clear_warning());
+            });
+
+        if generate {
+            self.generate_random(&params);
+        }
+        if open {
+            self.random_dialog = Some(params);
+        }
+    }
+
+    fn has_endpoints(&self) -> bool {
+        self.snarl
+            .nodes()
+            .any(|node| matches!(node.kind, NodeKind::Start | NodeKind::Finish(_)))
+    }
+
+    // Turn the current Start and Finish into Distance nodes, e.g. before generating new ones
+    fn demote_endpoints(&mut self) {
+        let endpoints: Vec<NodeId> = self
+            .snarl
+            .nodes_ids_data()
+            .filter(|(_, node)| matches!(node.value.kind, NodeKind::Start | NodeKind::Finish(_)))
+            .map(|(id, _)| id)
+            .collect();
+        for node in endpoints {
+            let kind = self.snarl[node]
+                .kind
+                .converted(&NodeKind::Distance(HashMap::new()));
+            self.snarl[node].kind = kind;
+            drop_invalid_wires(node, &mut self.snarl);
+        }
+        self.viewer.path_nodes.clear();
+        self.viewer.mark_changed();
+    }
+
+    // Connect two nodes through the same validation as a wire dragged by hand
+    fn connect_nodes(&mut self, from: NodeId, to: NodeId) {
+        let out_pin = self.snarl.out_pin(OutPinId {
+            node: from,
+            output: 0,
+        });
+        let in_pin = self.snarl.in_pin(InPinId { node: to, input: 0 });
+        self.viewer.connect(&out_pin, &in_pin, &mut self.snarl);
+    }
+
+    // Insert the nodes of a generated graph, its connections go through `connect_nodes`
+    fn insert_generated(&mut self, mut graph: model::Graph) {
+        let edges = std::mem::take(&mut graph.edges);
+        let (ids, _, _) = graph.insert_into(&mut self.snarl);
+        let index: HashMap<NodeId, NodeId> = graph
+            .nodes
+            .iter()
+            .zip(ids)
+            .map(|(node, id)| (node.id, id))
+            .collect();
+        for edge in edges {
+            self.connect_nodes(index[&edge.from], index[&edge.to]);
+        }
+        self.viewer.mark_changed();
+    }
+
+    // Graph position in the middle of the view, where generated graphs are placed
+    fn view_center(&self) -> Pos2 {
+        self.viewer
+            .view
+            .map(|view| view.screen_pos_to_graph(view.rect.center()))
+            .unwrap_or_default()
+    }
+
+    // Insert a lattice of Distance nodes with Start and Finish in opposite corners
+    fn generate_grid(&mut self, params: &GridParams) {
+        self.fit_view = Some(FitView::Requested);
+        self.demote_endpoints();
+
+        let size = egui::vec2(
+            (params.columns - 1) as f32 * params.spacing,
+            (params.rows - 1) as f32 * params.spacing,
+        );
+        let graph = generate::grid(
+            params.rows,
+            params.columns,
+            params.spacing,
+            params.two_way,
+            self.view_center() - size / 2.0,
+        );
+        self.insert_generated(graph);
+    }
+
+    fn show_grid_dialog(&mut self, ctx: &egui::Context) {
+        let Some(mut params) = self.grid_dialog.take() else {
+            return;
+        };
+        let mut open = true;
+        let mut generate = false;
+        egui::Window::new(tr(Text::GenerateGrid))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("grid_params").show(ui, |ui| {
+                    ui.label(tr(Text::Rows));
+                    ui.add(egui::DragValue::new(&mut params.rows).range(1..=50));
+                    ui.end_row();
+                    ui.label(tr(Text::Columns));
+                    ui.add(egui::DragValue::new(&mut params.columns).range(2..=50));
+                    ui.end_row();
+                    ui.label(tr(Text::Spacing));
+                    ui.add(egui::DragValue::new(&mut params.spacing).range(50.0..=500.0));
+                    ui.end_row();
+                });
+                ui.checkbox(&mut params.two_way, tr(Text::ConnectBothWays));
+
+                let warning = self
+                    .has_endpoints()
+                    .then(|| tr(Text::EndpointsWillBeDemoted).to_string());
+                generate = generate_button(ui, &mut params.confirm_replace, warning);
+            });
+
+        if generate {
+            self.generate_grid(&params);
+            return;
+        }
+        if open {
+            self.grid_dialog = Some(params);
+        }
+    }
+
+    fn copy_nodes(&self, selected: &[NodeId]) -> Option<String> {
+        let origin = selected
+            .iter()
+            .filter_map(|node| self.snarl.get_node_info(*node))
+            .map(|info| info.pos)
+            .reduce(|a, b| a.min(b))?;
+        let nodes = selected
+            .iter()
+            .filter_map(|node| Some((*node, self.snarl.get_node_info(*node)?)))
+            .map(|(node, info)| (node, info.pos - origin, info.value.clone()))
+            .collect();
+        let wires = self
+            .snarl
+            .wires()
+            .filter(|(out_pin, in_pin)| {
+                selected.contains(&out_pin.node) && selected.contains(&in_pin.node)
+            })
+            .map(|(out_pin, in_pin)| (out_pin.node, in_pin.node))
+            .collect();
+
+        let copied = ClipboardNodes { nodes, wires };
+        serde_json::to_string(&copied).ok()
+    }
+
+    fn paste_nodes(&mut self, text: &str, pos: Pos2) {
+        let copied: ClipboardNodes = match serde_json::from_str(text) {
+            Ok(copied) => copied,
+            Err(_) => {
+                self.viewer.add_error_notification(tr(Text::NoCopiedNodes));
+                return;
+            }
+        };
+
+        let mut has_start = self
+            .snarl
+            .nodes()
+            .any(|node| matches!(node.kind, NodeKind::Start));
+        let mut has_finish = self
+            .snarl
+            .nodes()
+            .any(|node| matches!(node.kind, NodeKind::Finish(_)));
+        let mut demoted = 0;
+
+        self.viewer.mark_changed();
+        let mut new_ids = HashMap::new();
+        for (old_id, offset, mut value) in copied.nodes {
+            // Keep the single Start/Finish invariant by demoting duplicates
+            value.kind = match value.kind {
+                NodeKind::Start if has_start => {
+                    demoted += 1;
+                    NodeKind::Distance(HashMap::new())
+                }
+                NodeKind::Finish(costs) if has_finish => {
+                    demoted += 1;
+                    NodeKind::Distance(costs)
+                }
+                kind => kind,
+            };
+            match value.kind {
+                NodeKind::Start => has_start = true,
+                NodeKind::Finish(_) => has_finish = true,
+                NodeKind::Distance(_) => {}
+            }
+            let new_id = self.snarl.insert_node(pos + PASTE_OFFSET + offset, value);
+            new_ids.insert(old_id, new_id);
+        }
+
+        for new_id in new_ids.values() {
+            match &mut self.snarl[*new_id].kind {
+                NodeKind::Distance(costs) | NodeKind::Finish(costs) => {
+                    *costs = remap_costs(costs, &new_ids);
+                }
+                NodeKind::Start => {}
+            }
+            let meta = &mut self.snarl[*new_id].meta;
+            meta.overrides = remap_costs(&meta.overrides, &new_ids);
+            meta.mirrored = meta
+                .mirrored
+                .iter()
+                .filter_map(|remote| new_ids.get(remote).copied())
+                .collect();
+        }
+
+        // Rebuild the connections between the pasted nodes
+        for (from, to) in copied.wires {
+            let (Some(&from), Some(&to)) = (new_ids.get(&from), new_ids.get(&to)) else {
+                continue;
+            };
+            // A demoted node may no longer accept the copied connection
+            if can_connect(&self.snarl[from].kind, &self.snarl[to].kind) {
+                self.snarl.connect(
+                    OutPinId {
+                        node: from,
+                        output: 0,
+                    },
+                    InPinId { node: to, input: 0 },
+                );
+            }
+        }
+
+        if demoted > 0 {
+            self.viewer
+                .add_warning_notification(trf(Text::PastedEndpointsDemoted, &[&demoted]));
+        }
+    }
+
+    fn handle_clipboard(&mut self, ui: &mut egui::Ui) {
+        let events = ui.input(|i| i.events.clone());
+        for event in events {
+            match event {
+                egui::Event::Copy => {
+                    let selected: Vec<NodeId> = self.viewer.selected.iter().copied().collect();
+                    if let Some(text) = self.copy_nodes(&selected) {
+                        ui.ctx().copy_text(text);
+                    }
+                }
+                egui::Event::Paste(text) => {
+                    // Paste under the cursor, or in the middle of the view when it's outside
+                    let hover = ui.input(|i| i.pointer.hover_pos());
+                    let pos = self
+                        .viewer
+                        .view
+                        .map(|view| {
+                            view.screen_pos_to_graph(
+                                hover
+                                    .filter(|pos| view.rect.contains(*pos))
+                                    .unwrap_or(view.rect.center()),
+                            )
+                        })
+                        .unwrap_or_default();
+                    self.paste_nodes(&text, pos);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Ask for a path to write an exported file to, it's written once the dialog is done
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_file(
+        &mut self,
+        ctx: &egui::Context,
+        file_name: &str,
+        filter: &'static str,
+        extension: &'static str,
+        contents: String,
+    ) {
+        self.export_files(
+            ctx,
+            file_name,
+            Export {
+                formats: vec![(filter, extension, contents)],
+                sidecar: None,
+            },
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_files(&mut self, ctx: &egui::Context, file_name: &str, export: Export) {
+        let mut dialog = async_file_dialog().set_file_name(file_name);
+        for (filter, extension, _) in &export.formats {
+            dialog = dialog.add_filter(*filter, &[*extension]);
+        }
+        let dialog = dialog.save_file();
+        self.open_dialog(ctx, async move {
+            dialog
+                .await
+                .map(|file| DialogResult::Export(file.path().to_path_buf(), export))
+        });
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn export_file(
+        &mut self,
+        _ctx: &egui::Context,
+        file_name: &str,
+        _filter: &str,
+        _extension: &str,
+        contents: String,
+    ) {
+        download(file_name.to_string(), contents.into_bytes());
+    }
+
+    // Write the format matching the picked extension, and the sidecar next to it
+    #[cfg(not(target_arch = "wasm32"))]
+    fn write_export(&mut self, path: &std::path::Path, mut export: Export) {
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        let idx = export
+            .formats
+            .iter()
+            .position(|(_, ext, _)| Some(*ext) == extension)
+            .unwrap_or(0);
+        let (_, _, contents) = export.formats.swap_remove(idx);
+        if let Err(err) = std::fs::write(path, contents) {
+            self.viewer
+                .add_error_notification(trf(Text::FailedToExport, &[&err]));
+            return;
+        }
+        self.viewer
+            .add_success_notification(trf(Text::ExportedTo, &[&path.display()]));
+        if let Some((extension, contents)) = export.sidecar {
+            let sidecar = path.with_extension(extension);
+            if let Err(err) = std::fs::write(&sidecar, contents) {
+                self.viewer
+                    .add_error_notification(trf(Text::FailedToWrite, &[&sidecar.display(), &err]));
+            }
+        }
+    }
+
+    // Saves ending in .gz are compact and gzip compressed, others pretty-printed JSON
+    fn write_save(&mut self, path: &std::path::Path) -> Result<(), String> {
+        let compressed = path.extension().is_some_and(|ext| ext == "gz");
+        let bytes = self.save_bytes(compressed)?;
+        log::debug!("Writing {} bytes to {}", bytes.len(), path.display());
+        std::fs::write(path, bytes).map_err(|err| trf(Text::FailedToSave, &[&err]))?;
+        self.mark_saved(path);
+        Ok(())
+    }
+
+    fn save_bytes(&self, compressed: bool) -> Result<Vec<u8>, String> {
+        save::encode(&self.to_save_file(), compressed)
+    }
+
+    // Remember the state of the file, so changes by other programs can be told apart
+    fn mark_saved(&mut self, path: &std::path::Path) {
+        self.file_mtime = modified_time(path);
+        self.saved_fingerprint = Some(graph_fingerprint(&self.snarl));
+        self.external_change = false;
+    }
+
+    fn has_unsaved_changes(&self) -> bool {
+        self.saved_fingerprint != Some(graph_fingerprint(&self.snarl))
+    }
+
+    // Polled about once a second, the file can't be watched for changes more cheaply
+    fn check_file_changes(&mut self, ctx: &egui::Context) {
+        let Some(path) = &self.current_file else {
+            return;
+        };
+        ctx.request_repaint_after(Duration::from_secs(1));
+        let now = ctx.input(|i| i.time);
+        if now - self.last_file_check < 1.0 {
+            return;
+        }
+        self.last_file_check = now;
+        let Some(modified) = modified_time(path) else {
+            return;
+        };
+        if self.file_mtime != Some(modified) {
+            self.file_mtime = Some(modified);
+            self.external_change = true;
+        }
+    }
+
+    fn show_file_changed_banner(&mut self, ctx: &egui::Context) {
+        if !self.external_change {
+            return;
+        }
+        let Some(path) = self.current_file.clone() else {
+            self.external_change = false;
+            return;
+        };
+        let unsaved = self.has_unsaved_changes();
+        egui::TopBottomPanel::top("file_changed").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                if unsaved {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        trf(Text::ChangedElsewhereUnsaved, &[&name]),
+                    );
+                } else {
+                    ui.label(trf(Text::ChangedElsewhere, &[&name]));
+                }
+                let reload = if unsaved {
+                    Text::ReloadAndDiscard
+                } else {
+                    Text::Reload
+                };
+                if ui.button(tr(reload)).clicked() {
+                    // Unlike opening, the camera stays where the user left it
+                    self.load_path(path.clone());
+                    self.external_change = false;
+                }
+                let keep = if unsaved {
+                    Text::KeepMine
+                } else {
+                    Text::Dismiss
+                };
+                if ui.button(tr(keep)).clicked() {
+                    self.external_change = false;
+                }
+            });
+        });
+    }
+
+    // Write to the current file, asking for a path when there is none or it can't be used
+    fn save(&mut self, ctx: &egui::Context) {
+        let Some(path) = self.current_file.clone() else {
+            self.save_as(ctx);
+            return;
+        };
+        if !path.exists() {
+            self.viewer
+                .add_warning_notification(trf(Text::NoLongerExists, &[&path.display()]));
+            self.save_as(ctx);
+            return;
+        }
+        match self.write_save(&path) {
+            Ok(()) => self
+                .viewer
+                .add_success_notification(trf(Text::SavedTo, &[&path.display()])),
+            Err(err) => {
+                self.viewer
+                    .add_warning_notification(trf(Text::ChooseAnotherFile, &[&err]));
+                self.save_as(ctx);
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_as(&mut self, ctx: &egui::Context) {
+        let dialog = async_file_dialog()
+            .set_file_name(".json")
+            .add_filter("JSON", &["json"])
+            .add_filter("Compressed JSON", &["json.gz", "gz"])
+            .save_file();
+        self.open_dialog(ctx, async move {
+            dialog
+                .await
+                .map(|file| DialogResult::SaveAs(file.path().to_path_buf()))
+        });
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load(&mut self, ctx: &egui::Context) {
+        let dialog = async_file_dialog()
+            .add_filter("JSON", &["json", "gz"])
+            .pick_file();
+        self.open_dialog(ctx, async move {
+            dialog
+                .await
+                .map(|file| DialogResult::Load(file.path().to_path_buf()))
+        });
+    }
+
+    // Downloads a pretty-printed save, the browser asks where to put it
+    #[cfg(target_arch = "wasm32")]
+    fn save_as(&mut self, ctx: &egui::Context) {
+        let bytes = match self.save_bytes(false) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                self.viewer.add_error_notification(err);
+                return;
+            }
+        };
+        let dialog = rfd::AsyncFileDialog::new()
+            .set_file_name("graph.json")
+            .save_file();
+        self.open_dialog(ctx, async move {
+            let file = dialog.await?;
+            file.write(&bytes).await.ok()?;
+            Some(DialogResult::Downloaded(file.file_name()))
+        });
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn load(&mut self, ctx: &egui::Context) {
+        let dialog = async_file_dialog()
+            .add_filter("JSON", &["json", "gz"])
+            .pick_file();
+        self.open_dialog(ctx, async move {
+            let file = dialog.await?;
+            Some(DialogResult::Opened(file.file_name(), file.read().await))
+        });
+    }
+
+    // Ask for a file to import, it's read and parsed once the dialog is done
+    fn import_file(&mut self, ctx: &egui::Context, format: ImportFormat) {
+        let (filter, extensions) = format.filter();
+        let dialog = async_file_dialog()
+            .add_filter(filter, extensions)
+            .pick_file();
+        self.open_dialog(ctx, async move {
+            let file = dialog.await?;
+            #[cfg(not(target_arch = "wasm32"))]
+            let contents = std::fs::read(file.path()).map_err(|err| err.to_string());
+            #[cfg(target_arch = "wasm32")]
+            let contents = Ok(file.read().await);
+            Some(DialogResult::Import(format, contents))
+        });
+    }
+
+    fn import_text(&mut self, format: ImportFormat, text: String) {
+        match format {
+            ImportFormat::Dot => match dot::parse(&text) {
+                Ok(graph) => self.load_imported(graph),
+                Err(err) => self
+                    .viewer
+                    .add_error_notification(trf(Text::FailedToImportDot, &[&err])),
+            },
+            ImportFormat::GraphMl => match graphml::parse(&text) {
+                Ok(graph) => self.load_imported(graph),
+                Err(err) => self
+                    .viewer
+                    .add_error_notification(trf(Text::FailedToImportGraphMl, &[&err])),
+            },
+            ImportFormat::Matrix => match matrix::parse(&text) {
+                Ok(matrix) => {
+                    let finish = matrix.labels.len().saturating_sub(1);
+                    self.matrix_import = Some(MatrixImport {
+                        matrix,
+                        start: 0,
+                        finish,
+                    });
+                }
+                Err(err) => self
+                    .viewer
+                    .add_error_notification(trf(Text::FailedToImportMatrix, &[&err])),
+            },
+            ImportFormat::EdgeList => {
+                if let Some(import) = &mut self.edge_list_import {
+                    import.text = text;
+                    import.error = None;
+                }
+            }
+        }
+    }
+
+    // Wait for the dialog on its own thread, so the app keeps drawing while it's open
+    #[cfg(not(target_arch = "wasm32"))]
+    fn open_dialog(
+        &mut self,
+        ctx: &egui::Context,
+        dialog: impl std::future::Future<Output = Option<DialogResult>> + Send + 'static,
+    ) {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send(pollster::block_on(dialog));
+            ctx.request_repaint();
+        });
+        self.pending_dialog = Some(receiver);
+    }
+
+    // The browser runs the dialog's future alongside drawing
+    #[cfg(target_arch = "wasm32")]
+    fn open_dialog(
+        &mut self,
+        ctx: &egui::Context,
+        dialog: impl std::future::Future<Output = Option<DialogResult>> + 'static,
+    ) {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let ctx = ctx.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = sender.send(dialog.await);
+            ctx.request_repaint();
+        });
+        self.pending_dialog = Some(receiver);
+    }
+
+    fn poll_dialog(&mut self) {
+        let Some(receiver) = &self.pending_dialog else {
+            return;
+        };
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => None,
+        };
+        self.pending_dialog = None;
+        match result {
+            #[cfg(not(target_arch = "wasm32"))]
+            Some(DialogResult::SaveAs(path)) => match self.write_save(&path) {
+                Ok(()) => self.current_file = Some(path),
+                Err(err) => self.viewer.add_error_notification(err),
+            },
+            #[cfg(not(target_arch = "wasm32"))]
+            Some(DialogResult::Load(path)) => self.open_path(path),
+            #[cfg(not(target_arch = "wasm32"))]
+            Some(DialogResult::Export(path, export)) => self.write_export(&path, export),
+            #[cfg(target_arch = "wasm32")]
+            Some(DialogResult::Downloaded(name)) => {
+                self.saved_fingerprint = Some(graph_fingerprint(&self.snarl));
+                self.viewer
+                    .add_success_notification(trf(Text::SavedTo, &[&name]));
+            }
+            #[cfg(target_arch = "wasm32")]
+            Some(DialogResult::Opened(name, bytes)) => match save::decode(bytes) {
+                Ok(text) => {
+                    if let Some(file) = self.parse_save(&text, Text::SourceFile) {
+                        self.apply_save_file(file);
+                        self.fit_view = Some(FitView::Requested);
+                        self.saved_fingerprint = Some(graph_fingerprint(&self.snarl));
+                        self.notify_loaded(&name);
+                    }
+                }
+                Err(err) => self
+                    .viewer
+                    .add_error_notification(trf(Text::FailedToRead, &[&name, &err])),
+            },
+            Some(DialogResult::Import(format, contents)) => {
+                match contents
+                    .and_then(|bytes| String::from_utf8(bytes).map_err(|err| err.to_string()))
+                {
+                    Ok(text) => self.import_text(format, text),
+                    Err(err) => self
+                        .viewer
+                        .add_error_notification(trf(Text::FailedToReadFile, &[&err])),
+                }
+            }
+            // Cancelled
+            None => {}
+        }
+    }
+
+    // Opened files are shown whole, the camera is moved to fit them
+    fn open_path(&mut self, path: std::path::PathBuf) {
+        if self.load_path(path) {
+            self.fit_view = Some(FitView::Requested);
+        }
+    }
+
+    // Returns whether the file was loaded. A file that can't be read or parsed leaves the
+    // current graph and file alone, only the error is shown.
+    fn load_path(&mut self, path: std::path::PathBuf) -> bool {
+        log::debug!("Reading {}", path.display());
+        let serialized = match read_save(&path) {
+            Ok(serialized) => serialized,
+            Err(err) => {
+                self.viewer
+                    .add_error_notification(trf(Text::FailedToRead, &[&path.display(), &err]));
+                return false;
+            }
+        };
+
+        // Deserialize the snarl data from the string
+        match self.parse_save(&serialized, Text::SourceFile) {
+            Some(file) => {
+                self.apply_save_file(file);
+                self.mark_saved(&path);
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                self.notify_loaded(&name);
+                self.current_file = Some(path);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Shared by loading files and pasting graphs, so both report bad data the same way
+    fn parse_save(&mut self, text: &str, source: Text) -> Option<save::SaveFile> {
+        save::load(text)
+            .map_err(|err| {
+                self.viewer
+                    .add_error_notification(trf(Text::FailedToLoad, &[&tr(source), &err]));
+            })
+            .ok()
+    }
+
+    fn copy_graph(&mut self, ctx: &egui::Context) {
+        match serde_json::to_string_pretty(&self.to_save_file()) {
+            Ok(text) => {
+                ctx.copy_text(text);
+                self.viewer.add_success_notification(tr(Text::GraphCopied));
+            }
+            Err(err) => self
+                .viewer
+                .add_error_notification(trf(Text::FailedToSerialize, &[&err])),
+        }
+    }
+
+    // The clipboard contents arrive as a paste event in one of the next passes
+    fn request_graph_paste(&mut self, ctx: &egui::Context) {
+        ctx.send_viewport_cmd(egui::ViewportCommand::RequestPaste);
+        self.graph_paste_requested = Some(ctx.cumulative_pass_nr());
+    }
+
+    // Take the requested paste event before node pasting sees it
+    fn handle_graph_paste(&mut self, ctx: &egui::Context) {
+        let Some(requested) = self.graph_paste_requested else {
+            return;
+        };
+        let text = ctx.input_mut(|i| {
+            let idx = i
+                .events
+                .iter()
+                .position(|event| matches!(event, egui::Event::Paste(_)))?;
+            match i.events.remove(idx) {
+                egui::Event::Paste(text) => Some(text),
+                _ => None,
+            }
+        });
+        let Some(text) = text else {
+            // No event is sent when the clipboard has no text
+            if ctx.cumulative_pass_nr() > requested + 2 {
+                self.graph_paste_requested = None;
+                self.viewer
+                    .add_warning_notification(tr(Text::NoTextInClipboard));
+            } else {
+                ctx.request_repaint();
+            }
+            return;
+        };
+        self.graph_paste_requested = None;
+
+        let Some(file) = self.parse_save(&text, Text::SourceClipboard) else {
+            return;
+        };
+        if self.snarl.nodes().next().is_none() {
+            self.apply_pasted_graph(file);
+        } else {
+            self.graph_paste_confirm = Some(file);
+        }
+    }
+
+    // One line for the clipboard, e.g. "Start → Distance 4 (cost 12, cum 12) → Finish (cost 5, cum 17)"
+    fn path_summary(&self, graph: &model::Graph, steps: &[(NodeId, Cost, Cost)]) -> String {
+        steps
+            .iter()
+            .enumerate()
+            .map(|(idx, (node, cost, cumulative))| {
+                let label = shown_label(graph, *node);
+                if idx == 0 {
+                    label
+                } else {
+                    format!(
+                        "{} ({})",
+                        label,
+                        trf(
+                            Text::StepCost,
+                            &[&format_cost(*cost), &format_cost(*cumulative)]
+                        )
+                    )
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" → ")
+    }
+
+    // Highlighted path: node, cost of the wire leading to it and the cost so far
+    fn path_steps(&self) -> Vec<(NodeId, Cost, Cost)> {
+        let mut previous = None;
+        let mut total = 0.0;
+        self.viewer
+            .path_nodes
+            .iter()
+            .filter(|node| self.snarl.get_node(**node).is_some())
+            .map(|node| {
+                let cost = previous.map_or(0.0, |previous| edge_cost(&self.snarl, previous, *node));
+                total += cost;
+                previous = Some(*node);
+                (*node, cost, total)
+            })
+            .collect()
+    }
+
+    // Rebuilt every frame, so it follows auto-recalc and picks in the distance table
+    fn show_path_summary_window(&mut self, ctx: &egui::Context) {
+        if !self.show_path_summary {
+            return;
+        }
+        let steps = self.path_steps();
+        let graph = model::Graph::from_snarl(&self.snarl);
+        let mut show_node = None;
+        let mut open = true;
+        egui::Window::new(tr(Text::Path))
+            .open(&mut open)
+            .default_size([300.0, 300.0])
+            .show(ctx, |ui| {
+                if steps.is_empty() {
+                    ui.weak(tr(Text::RunFirst));
+                    return;
+                }
+                if ui.button(tr(Text::Copy)).clicked() {
+                    ui.ctx().copy_text(self.path_summary(&graph, &steps));
+                    self.viewer
+                        .add_success_notification(tr(Text::PathSummaryCopied));
+                }
+                ui.separator();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("path_summary")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (idx, (node, cost, cumulative)) in steps.iter().enumerate() {
+                                ui.label(shown_label(&graph, *node));
+                                if idx == 0 {
+                                    ui.label("");
+                                } else {
+                                    ui.label(trf(
+                                        Text::StepCost,
+                                        &[&format_cost(*cost), &format_cost(*cumulative)],
+                                    ));
+                                }
+                                if ui.small_button(tr(Text::Show)).clicked() {
+                                    show_node = Some(*node);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+        self.show_path_summary = open;
+        if let Some(node) = show_node {
+            self.center_on(node);
+        }
+    }
+
+    fn center_on(&mut self, node: NodeId) {
+        let Some(pos) = self.snarl.get_node_info(node).map(|info| info.pos) else {
+            return;
+        };
+        let center = self
+            .viewer
+            .stored_nodes
+            .get(&node)
+            .map_or(pos, |rect| rect.center());
+        self.shift_view(self.view_center() - center);
+    }
+
+    // Shifts the view so the graph's bounds end up centered once it is zoomed around the pivot
+    fn plan_fit_view(&mut self, ui: &egui::Ui) {
+        self.fit_view = None;
+        let Some(view) = self.viewer.view else {
+            return;
+        };
+        let Some(bounds) = self
+            .snarl
+            .nodes_pos_ids()
+            .map(|(id, pos, _)| {
+                self.viewer
+                    .stored_nodes
+                    .get(&id)
+                    .copied()
+                    .unwrap_or(Rect::from_min_size(pos, Vec2::ZERO))
+            })
+            .reduce(Rect::union)
+        else {
+            return;
+        };
+        let bounds = bounds.expand(FIT_VIEW_MARGIN);
+
+        // egui_snarl rounds its scale to hundredths
+        let fit = (view.rect.width() / bounds.width()).min(view.rect.height() / bounds.height());
+        let scale = ((fit * 100.0).floor() / 100.0).clamp(MIN_SCALE, MAX_SCALE);
+        // The zoom only reaches the snarl where no window is on top of it
+        let pivot = (1..8)
+            .flat_map(|y| (1..8).map(move |x| egui::vec2(x as f32, y as f32) / 8.0))
+            .map(|at| view.rect.min + view.rect.size() * at)
+            .find(|pos| ui.ctx().layer_id_at(*pos) == Some(ui.layer_id()));
+        let (scale, pivot) = match pivot {
+            Some(pivot) if (scale - view.scale).abs() > 0.001 => (scale, pivot),
+            _ => (view.scale, view.rect.center()),
+        };
+
+        // The graph point under the pivot stays in place while zooming
+        let anchor = view.screen_pos_to_graph(pivot);
+        let target = anchor + (view.rect.center() - pivot) / scale;
+        self.shift_view(target - bounds.center());
+        if scale != view.scale {
+            self.fit_view = Some(FitView::Zoom {
+                factor: scale / view.scale,
+                pivot,
+            });
+            ui.ctx().request_repaint();
+        }
+    }
+
+    // Moves what the view shows by `delta` in graph space, the nodes keep their positions
+    fn shift_view(&mut self, delta: Vec2) {
+        self.viewer.view_shift += delta;
+        if let Some(view) = &mut self.viewer.view {
+            view.shift += delta;
+        }
+    }
+
+    // Model positions of the nodes and whether they are locked, the nodes are then moved
+    // to where they are drawn. Nothing to do until the view was moved by the app.
+    fn shift_for_drawing(&mut self) -> Option<HashMap<NodeId, (Pos2, bool)>> {
+        let shift = self.viewer.view_shift;
+        if shift == Vec2::ZERO {
+            return None;
+        }
+        let positions: HashMap<NodeId, (Pos2, bool)> = self
+            .snarl
+            .nodes_pos_ids()
+            .map(|(node, pos, value)| (node, (pos, value.meta.locked)))
+            .collect();
+        for node in positions.keys() {
+            if let Some(info) = self.snarl.get_node_info_mut(*node) {
+                info.pos += shift;
+            }
+        }
+        Some(positions)
+    }
+
+    // Back from drawn to model positions. Nodes that weren't dragged get theirs exactly, so
+    // shifting doesn't slowly move them, and locked ones get theirs back even if dragged.
+    fn unshift_after_drawing(&mut self, positions: &HashMap<NodeId, (Pos2, bool)>) {
+        let shift = self.viewer.view_shift;
+        let nodes: Vec<NodeId> = self.snarl.node_ids().map(|(id, _)| id).collect();
+        for node in nodes {
+            let Some(info) = self.snarl.get_node_info_mut(node) else {
+                continue;
+            };
+            info.pos = match positions.get(&node) {
+                Some((pos, locked)) if *locked || info.pos == *pos + shift => *pos,
+                _ => info.pos - shift,
+            };
+        }
+    }
+
+    // Timings of the previous frame, the current one isn't done yet
+    fn show_profiling_window(&mut self, ctx: &egui::Context) {
+        if !self.show_profiling {
+            return;
+        }
+        let ms = |duration: Duration| format!("{:.2} ms", duration.as_secs_f64() * 1000.0);
+        let times = &self.frame_times;
+        egui::Window::new(tr(Text::Profiling))
+            .open(&mut self.show_profiling)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("profiling").show(ui, |ui| {
+                    ui.label(tr(Text::Frame));
+                    ui.label(ms(times.update));
+                    ui.end_row();
+                    ui.label(tr(Text::DrawingGraph));
+                    ui.label(ms(times.snarl_show));
+                    ui.end_row();
+                    ui.label(tr(Text::CostRecalculation));
+                    ui.label(ms(times.costs));
+                    ui.end_row();
+                    ui.label(tr(Text::LastSearch));
+                    ui.label(self.search_time.map_or("-".to_string(), ms));
+                    ui.end_row();
+                    ui.label(tr(Text::Nodes));
+                    ui.label(format!("{}", self.snarl.nodes().count()));
+                    ui.end_row();
+                    ui.label(tr(Text::Connections));
+                    ui.label(format!("{}", self.snarl.wires().count()));
+                    ui.end_row();
+                });
+            });
+    }
+
+    // Details of the inspected node, edits are written to the node right away
+    fn show_inspector(&mut self, ctx: &egui::Context) {
+        if !self.show_inspector {
+            return;
+        }
+        if self.viewer.selected.len() == 1 {
+            self.inspected = self.viewer.selected.iter().next().copied();
+        }
+        self.inspected = self
+            .inspected
+            .filter(|node| self.snarl.get_node(*node).is_some());
+
+        egui::SidePanel::right("inspector").show(ctx, |ui| {
+            ui.heading(tr(Text::Inspector));
+            let Some(node) = self.inspected else {
+                ui.weak(tr(Text::SelectANode));
+                return;
+            };
+            let distance = self
+                .last_run
+                .as_ref()
+                .and_then(|run| run.distances.get(&node).copied());
+            let incoming: Vec<NodeId> = self
+                .snarl
+                .wires()
+                .filter(|(_, in_pin)| in_pin.node == node)
+                .map(|(out_pin, _)| out_pin.node)
+                .collect();
+            let outgoing: Vec<NodeId> = self
+                .snarl
+                .wires()
+                .filter(|(out_pin, _)| out_pin.node == node)
+                .map(|(_, in_pin)| in_pin.node)
+                .collect();
+
+            egui::Grid::new("inspector").num_columns(2).show(ui, |ui| {
+                ui.label(tr(Text::Type));
+                ui.label(self.snarl[node].kind.name());
+                ui.end_row();
+                ui.label(tr(Text::Label));
+                ui.text_edit_singleline(&mut self.snarl[node].meta.label);
+                ui.end_row();
+                ui.label(tr(Text::Position));
+                // Typed positions are kept as they are, `final_node_rect` picks up the move
+                // and the costs follow
+                if let Some(info) = self.snarl.get_node_info_mut(node) {
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::DragValue::new(&mut info.pos.x)
+                                .prefix("X: ")
+                                .fixed_decimals(0),
+                        );
+                        ui.add(
+                            egui::DragValue::new(&mut info.pos.y)
+                                .prefix("Y: ")
+                                .fixed_decimals(0),
+                        );
+                    });
+                }
+                ui.end_row();
+                ui.label(tr(Text::Color));
+                ui.horizontal(|ui| {
+                    let meta = &mut self.snarl[node].meta;
+                    let mut color = meta.color.unwrap_or(self.viewer.palette.node);
+                    if egui::color_picker::color_edit_button_srgba(
+                        ui,
+                        &mut color,
+                        egui::color_picker::Alpha::Opaque,
+                    )
+                    .changed()
+                    {
+                        meta.color = Some(color);
+                    }
+                    if meta.color.is_some() && ui.button(tr(Text::Reset)).clicked() {
+                        meta.color = None;
+                    }
+                });
+                ui.end_row();
+                ui.label(tr(Text::DistanceFromStart));
+                match distance {
+                    Some(distance) if distance.is_infinite() => ui.label("∞"),
+                    Some(distance) => ui.label(format_cost(distance)),
+                    None => ui.weak("-"),
+                };
+                ui.end_row();
+                ui.label(tr(Text::OnPath));
+                let on_path = self.viewer.path_nodes.contains(&node);
+                ui.label(tr(if on_path { Text::Yes } else { Text::No }));
+                ui.end_row();
+            });
+
+            ui.separator();
+            ui.strong(tr(Text::IncomingConnections));
+            if incoming.is_empty() {
+                ui.weak(tr(Text::NoConnections));
+            }
+            egui::Grid::new("inspector_incoming").show(ui, |ui| {
+                for from in incoming {
+                    ui.label(node_label(&self.snarl, from));
+                    let mut cost = edge_cost(&self.snarl, from, node);
+                    // Manual costs replace the wire length until they are reset
+                    // Whole numbers unless the graph keeps fractional costs
+                    let decimals = if self.settings.cost_scale.fractional {
+                        1
+                    } else {
+                        0
+                    };
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut cost)
+                                .range(0.0..=Cost::MAX)
+                                .fixed_decimals(decimals),
+                        )
+                        .changed()
+                    {
+                        self.snarl[node].meta.overrides.insert(from, cost);
+                        self.viewer.costs_dirty = true;
+                    }
+                    if self.snarl[node].meta.overrides.contains_key(&from)
+                        && ui.small_button(tr(Text::Reset)).clicked()
+                    {
+                        self.snarl[node].meta.overrides.remove(&from);
+                        self.viewer.costs_dirty = true;
+                    }
+                    ui.end_row();
+                }
+            });
+
+            ui.separator();
+            ui.strong(tr(Text::OutgoingConnections));
+            if outgoing.is_empty() {
+                ui.weak(tr(Text::NoConnections));
+            }
+            egui::Grid::new("inspector_outgoing").show(ui, |ui| {
+                for to in outgoing {
+                    ui.label(node_label(&self.snarl, to));
+                    ui.label(trf(
+                        Text::Cost,
+                        &[&format_cost(edge_cost(&self.snarl, node, to))],
+                    ));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn show_help_window(&mut self, ctx: &egui::Context) {
+        if !self.show_help {
+            return;
+        }
+        egui::Window::new(tr(Text::Help))
+            .id(egui::Id::new("help"))
+            .open(&mut self.show_help)
+            .default_size([420.0, 480.0])
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for (title, body) in [
+                        (Text::HelpAddingNodes, Text::HelpAddingNodesBody),
+                        (Text::HelpConnecting, Text::HelpConnectingBody),
+                        (Text::HelpCosts, Text::HelpCostsBody),
+                        (Text::HelpRunning, Text::HelpRunningBody),
+                        (Text::HelpSaving, Text::HelpSavingBody),
+                    ] {
+                        ui.strong(tr(title));
+                        ui.label(tr(body));
+                        ui.add_space(8.0);
+                    }
+                    ui.strong(tr(Text::HelpShortcuts));
+                    egui::Grid::new("help_shortcuts")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (keys, action) in [
+                                ("S / D / F", Text::ShortcutAddNode),
+                                ("Delete", Text::ShortcutDelete),
+                                ("Ctrl+C / Ctrl+V", Text::ShortcutCopyPaste),
+                                ("Ctrl+S", Text::ShortcutSave),
+                                ("Ctrl+F", Text::ShortcutFind),
+                                ("F11", Text::ShortcutPresentation),
+                                ("Home", Text::FitViewHover),
+                            ] {
+                                ui.monospace(keys);
+                                ui.label(tr(action));
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+    }
+
+    // Counts are cached until the graph changes, the zoom is the one of the last frame
+    fn show_status_bar(&mut self, ctx: &egui::Context) {
+        if !self.appearance.status_bar {
+            return;
+        }
+        let stats = self.viewer.stats(&self.snarl);
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(trf(
+                    Text::StatusNodes,
+                    &[&stats.starts, &stats.distances, &stats.finishes],
+                ));
+                ui.separator();
+                ui.label(trf(Text::StatusConnections, &[&stats.connections]));
+                if let Some(view) = self.viewer.view {
+                    ui.separator();
+                    ui.label(trf(Text::StatusZoom, &[&(view.scale * 100.0).round()]));
+                    // In graph space, the same coordinates the inspector shows
+                    if let Some(hover) = ui
+                        .input(|i| i.pointer.hover_pos())
+                        .filter(|pos| view.rect.contains(*pos))
+                    {
+                        let pos = view.screen_pos_to_graph(hover);
+                        ui.separator();
+                        ui.label(trf(
+                            Text::StatusCursor,
+                            &[&(pos.x.round() as i32), &(pos.y.round() as i32)],
+                        ));
+                    }
+                }
+                if let Some(cost) = self.total_cost
+                    && !self.viewer.path_nodes.is_empty()
+                {
+                    ui.separator();
+                    ui.label(trf(
+                        Text::StatusPath,
+                        &[&format_cost(cost), &(self.viewer.path_nodes.len() - 1)],
+                    ));
+                }
+            });
+        });
+    }
+
+    // Edits apply right away, the view is restyled every frame
+    fn show_appearance_window(&mut self, ctx: &egui::Context) {
+        if !self.show_appearance {
+            return;
+        }
+        let appearance = &mut self.appearance;
+        let wire_style = &mut self.settings.wire_style;
+        egui::Window::new(tr(Text::Appearance))
+            .id(egui::Id::new("appearance"))
+            .open(&mut self.show_appearance)
+            .resizable(false)
+            .show(ctx, |ui| {
+                wire_style_combo(ui, wire_style);
+                egui::ComboBox::from_label(tr(Text::Palette))
+                    .selected_text(tr(appearance.palette.text()))
+                    .show_ui(ui, |ui| {
+                        for preset in palette::Preset::ALL {
+                            ui.selectable_value(&mut appearance.palette, preset, tr(preset.text()));
+                        }
+                    });
+                egui::Grid::new("appearance").show(ui, |ui| {
+                    ui.label(tr(Text::WireWidth));
+                    ui.add(egui::Slider::new(&mut appearance.wire_width, 0.5..=8.0));
+                    ui.end_row();
+                    ui.label(tr(Text::GridSize));
+                    ui.add(egui::Slider::new(
+                        &mut appearance.grid_spacing,
+                        10.0..=200.0,
+                    ));
+                    ui.end_row();
+                    ui.label(tr(Text::HideCostsBelowZoom));
+                    ui.add(
+                        egui::Slider::new(&mut appearance.cost_label_scale, 0.2..=1.5)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+                    ui.label(tr(Text::PathWidth));
+                    ui.add(egui::Slider::new(&mut appearance.path_width, 1.0..=4.0).suffix("×"));
+                    ui.end_row();
+                });
+                let palette = palette::Palette::new(appearance.palette, ui.visuals().dark_mode);
+                theme_color_edit(
+                    ui,
+                    tr(Text::PathColor),
+                    &mut appearance.path_color,
+                    palette.path,
+                );
+                ui.checkbox(&mut appearance.dim_off_path, tr(Text::DimOffPath));
+                appearance_preview(ui, appearance, palette);
+                ui.checkbox(&mut appearance.collapsible, tr(Text::CollapsibleHeaders));
+                ui.checkbox(&mut appearance.status_bar, tr(Text::ShowStatusBar));
+                ui.checkbox(&mut appearance.path_pulse, tr(Text::AnimatePath));
+                ui.checkbox(
+                    &mut appearance.highlight_neighbors,
+                    tr(Text::HighlightNeighbors),
+                );
+                ui.checkbox(
+                    &mut appearance.distance_emphasis,
+                    tr(Text::EmphasizeByDistance),
+                );
+                ui.checkbox(&mut appearance.arrowheads, tr(Text::ShowArrowheads));
+                ui.checkbox(&mut appearance.legend, tr(Text::ShowLegend));
+                ui.checkbox(&mut appearance.legend_in_exports, tr(Text::LegendInExports));
+                if ui.button(tr(Text::ResetToDefaults)).clicked() {
+                    *appearance = Appearance::default();
+                }
+            });
+    }
+
+    fn show_log_window(&mut self, ctx: &egui::Context) {
+        if !self.show_log {
+            return;
+        }
+        let level = &mut self.log_level;
+        egui::Window::new(tr(Text::Log))
+            .open(&mut self.show_log)
+            .default_size([500.0, 300.0])
+            .show(ctx, |ui| {
+                egui::ComboBox::from_label(tr(Text::Level))
+                    .selected_text(level.as_str())
+                    .show_ui(ui, |ui| {
+                        for filter in [
+                            log::LevelFilter::Error,
+                            log::LevelFilter::Warn,
+                            log::LevelFilter::Info,
+                            log::LevelFilter::Debug,
+                        ] {
+                            ui.selectable_value(level, filter, filter.as_str());
+                        }
+                    });
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .auto_shrink(false)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for entry in logging::recent(*level) {
+                            let color = match entry.level {
+                                log::Level::Error => ui.visuals().error_fg_color,
+                                log::Level::Warn => ui.visuals().warn_fg_color,
+                                log::Level::Info => ui.visuals().text_color(),
+                                _ => ui.visuals().weak_text_color(),
+                            };
+                            // Only the time of day of the UTC timestamp
+                            let time = report::format_timestamp(entry.time);
+                            ui.colored_label(
+                                color,
+                                format!(
+                                    "{} {:5} {}: {}",
+                                    &time[11..19],
+                                    entry.level,
+                                    entry.target,
+                                    entry.message
+                                ),
+                            );
+                        }
+                    });
+            });
+    }
+
+    // Every node of the last run, clicking a reachable one highlights its route from Start
+    fn show_distance_table(&mut self, ui: &mut egui::Ui) {
+        let Some(result) = &self.last_run else {
+            return;
+        };
+        let graph = model::Graph::from_snarl(&self.snarl);
+        let label = |node: NodeId| shown_label(&graph, node);
+        let mut picked = None;
+        egui::CollapsingHeader::new(tr(Text::Distances))
+            .id_salt("distances")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr(Text::SortBy));
+                    ui.selectable_value(&mut self.distances_by_cost, false, tr(Text::Node));
+                    ui.selectable_value(
+                        &mut self.distances_by_cost,
+                        true,
+                        tr(Text::DistanceColumn),
+                    );
+                });
+                // Nodes removed since the run are left out
+                let mut rows: Vec<(NodeId, Cost)> = result
+                    .distances
+                    .iter()
+                    .filter(|(node, _)| graph.node(**node).is_some())
+                    .map(|(node, dist)| (*node, *dist))
+                    .collect();
+                if self.distances_by_cost {
+                    rows.sort_by(|(a, a_dist), (b, b_dist)| {
+                        a_dist.total_cmp(b_dist).then(a.0.cmp(&b.0))
+                    });
+                } else {
+                    rows.sort_by_key(|(node, _)| node.0);
+                }
+
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("distance_table")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.strong(tr(Text::Node));
+                                ui.strong(tr(Text::DistanceColumn));
+                                ui.strong(tr(Text::Previous));
+                                ui.end_row();
+                                for (node, dist) in rows {
+                                    let reachable = dist.is_finite();
+                                    let row = ui.add_enabled(
+                                        reachable,
+                                        egui::SelectableLabel::new(
+                                            self.distance_row == Some(node),
+                                            label(node),
+                                        ),
+                                    );
+                                    if row.clicked() {
+                                        picked = Some(node);
+                                    }
+                                    if reachable {
+                                        ui.label(format_cost(dist));
+                                    } else {
+                                        ui.label("∞");
+                                    }
+                                    match result.predecessors.get(&node) {
+                                        Some(prev) => ui.label(label(*prev)),
+                                        None => ui.weak("-"),
+                                    };
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+
+        if let Some(node) = picked {
+            self.viewer.path_nodes = result.route_to(node);
+            self.total_cost = result.distances.get(&node).copied();
+            self.distance_row = Some(node);
+        }
+    }
+
+    fn show_graph_paste_confirm(&mut self, ctx: &egui::Context) {
+        let Some(file) = self.graph_paste_confirm.take() else {
+            return;
+        };
+        let mut open = true;
+        let mut replace = false;
+        let mut cancel = false;
+        egui::Window::new(tr(Text::PasteGraph))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(trf(Text::ReplaceWithPasted, &[&file.snarl.nodes().count()]));
+                ui.horizontal(|ui| {
+                    replace = ui.button(tr(Text::Replace)).clicked();
+                    cancel = ui.button(tr(Text::Cancel)).clicked();
+                });
+            });
+
+        if replace {
+            self.apply_pasted_graph(file);
+        } else if open && !cancel {
+            self.graph_paste_confirm = Some(file);
+        }
+    }
+
+    // Found again only after the graph changed. Quick fixes apply once the list is drawn.
+    fn show_issues(&mut self, ui: &mut egui::Ui) {
+        enum Fix {
+            Show(NodeId),
+            Remove(NodeId),
+            DropStaleCosts,
+            Add(NodeKind),
+        }
+        let issues = self.viewer.issues(&self.snarl);
+        let mut fix = None;
+        egui::CollapsingHeader::new(trf(Text::IssuesCount, &[&issues.len()]))
+            .id_salt("issues")
+            .show(ui, |ui| {
+                if issues.is_empty() {
+                    ui.weak(tr(Text::NoIssues));
+                }
+                for issue in &issues {
+                    ui.horizontal(|ui| {
+                        let text = match issue {
+                            issues::Issue::NoStart => tr(Text::IssueNoStart).to_string(),
+                            issues::Issue::NoFinish => tr(Text::IssueNoFinish).to_string(),
+                            issues::Issue::Isolated(node) => {
+                                trf(Text::IssueIsolated, &[&node_label(&self.snarl, *node)])
+                            }
+                            issues::Issue::DeadEnd(node) => {
+                                trf(Text::IssueDeadEnd, &[&node_label(&self.snarl, *node)])
+                            }
+                            issues::Issue::StaleCosts(node, count) => trf(
+                                Text::IssueStaleCosts,
+                                &[&node_label(&self.snarl, *node), count],
+                            ),
+                        };
+                        ui.label(text);
+                        if let Some(node) = issue.node()
+                            && ui.small_button(tr(Text::Show)).clicked()
+                        {
+                            fix = Some(Fix::Show(node));
+                        }
+                        let quick_fix = match issue {
+                            issues::Issue::NoStart => {
+                                Some((Text::AddStart, Fix::Add(NodeKind::Start)))
+                            }
+                            issues::Issue::NoFinish => {
+                                Some((Text::AddFinish, Fix::Add(NodeKind::Finish(HashMap::new()))))
+                            }
+                            issues::Issue::Isolated(node) => {
+                                Some((Text::Remove, Fix::Remove(*node)))
+                            }
+                            issues::Issue::StaleCosts(..) => {
+                                Some((Text::DropStaleCosts, Fix::DropStaleCosts))
+                            }
+                            issues::Issue::DeadEnd(_) => None,
+                        };
+                        if let Some((text, quick_fix)) = quick_fix
+                            && ui.small_button(tr(text)).clicked()
+                        {
+                            fix = Some(quick_fix);
+                        }
+                    });
+                }
+            });
+
+        match fix {
+            Some(Fix::Show(node)) => self.center_on(node),
+            Some(Fix::Remove(node)) => self.viewer.remove_nodes(&[node], &mut self.snarl),
+            Some(Fix::DropStaleCosts) => {
+                let dropped = drop_stale_costs(&mut self.snarl);
+                self.viewer
+                    .add_info_notification(trf(Text::DroppedStaleCosts, &[&dropped]));
+                self.viewer.mark_changed();
+            }
+            Some(Fix::Add(kind)) => {
+                self.snarl
+                    .insert_node(self.view_center(), DijkstraNode::new(kind));
+                self.viewer.mark_changed();
+            }
+            None => {}
+        }
+    }
+
+    // Nodes whose label or note contains the query, ignoring case, or with the id it names
+    fn find_matches(&self) -> Vec<NodeId> {
+        let query = self.find_query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let id = query.parse::<usize>().ok();
+        let mut matches: Vec<NodeId> = self
+            .snarl
+            .node_ids()
+            .filter(|(node, value)| {
+                id == Some(node.0)
+                    || value.meta.label.to_lowercase().contains(&query)
+                    || value.meta.note.to_lowercase().contains(&query)
+            })
+            .map(|(node, _)| node)
+            .collect();
+        matches.sort_by_key(|node| node.0);
+        matches
+    }
+
+    fn show_find_window(&mut self, ctx: &egui::Context) {
+        if !self.show_find {
+            return;
+        }
+        let mut open = true;
+        let mut picked = None;
+        egui::Window::new(tr(Text::Find))
+            .id(egui::Id::new("find"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.find_query).hint_text(tr(Text::FindHint)),
+                );
+                if std::mem::take(&mut self.find_focus) {
+                    response.request_focus();
+                }
+                if response.changed() {
+                    self.find_index = 0;
+                }
+                let matches = self.find_matches();
+                if response.lost_focus()
+                    && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                    && !matches.is_empty()
+                {
+                    self.find_index %= matches.len();
+                    picked = Some(matches[self.find_index]);
+                    self.find_index += 1;
+                    response.request_focus();
+                }
+                if matches.is_empty() {
+                    if !self.find_query.trim().is_empty() {
+                        ui.weak(tr(Text::NoMatches));
+                    }
+                    return;
+                }
+                ui.weak(trf(Text::MatchCount, &[&matches.len()]));
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        for (index, node) in matches.iter().enumerate() {
+                            let current = self.flash.is_some_and(|(flash, _)| flash == *node);
+                            let text = format!("{}: {}", node.0, node_label(&self.snarl, *node));
+                            if ui.selectable_label(current, text).clicked() {
+                                picked = Some(*node);
+                                self.find_index = index + 1;
+                            }
+                        }
+                    });
+            });
+        self.show_find = open;
+        if let Some(node) = picked {
+            self.center_on(node);
+            self.flash = Some((node, ctx.input(|i| i.time)));
+        }
+    }
+
+    // The found node blinks for FLASH_DURATION, lit every other quarter second
+    fn flashing_node(&mut self, ctx: &egui::Context) -> Option<NodeId> {
+        let (node, since) = self.flash?;
+        let elapsed = ctx.input(|i| i.time) - since;
+        if elapsed > FLASH_DURATION || self.snarl.get_node(node).is_none() {
+            self.flash = None;
+            return None;
+        }
+        ctx.request_repaint();
+        ((elapsed * 4.0) as u32).is_multiple_of(2).then_some(node)
+    }
+
+    fn toggle_presentation(&mut self) {
+        self.presentation = !self.presentation;
+        self.viewer.toasts.set_closable(!self.presentation);
+    }
+
+    // All that is left while presenting, in the top right corner of the graph
+    fn show_presentation_controls(&mut self, ctx: &egui::Context) {
+        egui::Area::new(egui::Id::new("presentation"))
+            .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-8.0, 8.0))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        if ui.button(tr(Text::RunDijkstra)).clicked() {
+                            self.start_search(true);
+                        }
+                        if self.pending_search.is_some() {
+                            ui.spinner();
+                        }
+                        if ui
+                            .button(tr(Text::ExitPresentation))
+                            .on_hover_text("F11")
+                            .clicked()
+                        {
+                            self.toggle_presentation();
+                        }
+                    });
+                });
+            });
+    }
+
+    fn show_remove_all_confirm(&mut self, ctx: &egui::Context) {
+        if !self.remove_all_confirm {
+            return;
+        }
+        let mut open = true;
+        let mut confirm = false;
+        let mut cancel = false;
+        egui::Window::new(tr(Text::RemoveAll))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(trf(
+                    Text::ConfirmRemoveAll,
+                    &[&self.snarl.nodes().count(), &self.snarl.wires().count()],
+                ));
+                ui.horizontal(|ui| {
+                    confirm = ui.button(tr(Text::Confirm)).clicked();
+                    cancel = ui.button(tr(Text::Cancel)).clicked();
+                });
+            });
+
+        if confirm {
+            let count = self.snarl.nodes().count();
+            self.clear_graph();
+            self.viewer
+                .add_info_notification(trf(Text::RemovedNodes, &[&count]));
+        }
+        self.remove_all_confirm = open && !confirm && !cancel;
+    }
+
+    fn apply_pasted_graph(&mut self, file: save::SaveFile) {
+        let count = file.snarl.nodes().count();
+        self.apply_save_file(file);
+        self.fit_view = Some(FitView::Requested);
+        // The graph no longer matches the file, Save should ask where to write it
+        self.current_file = None;
+        self.viewer
+            .add_success_notification(trf(Text::PastedGraph, &[&count]));
+    }
+
+    fn to_save_file(&self) -> save::SaveFile {
+        let path_nodes = self.viewer.path_nodes.clone();
+        save::SaveFile {
+            version: save::SAVE_VERSION,
+            snarl: self.snarl.clone(),
+            total_cost: self
+                .total_cost
+                .filter(|_| !path_nodes.is_empty())
+                .map(save::CostNumber),
+            path_nodes,
+            settings: Some(self.settings),
+        }
+    }
+
+    fn apply_save_file(&mut self, file: save::SaveFile) {
+        self.snarl = file.snarl;
+        // Files edited by hand or saved by older builds may carry costs of removed nodes
+        let dropped = drop_stale_costs(&mut self.snarl);
+        if dropped > 0 {
+            self.viewer
+                .add_info_notification(trf(Text::DroppedStaleCosts, &[&dropped]));
+        }
+        self.viewer.mark_changed();
+        // Files from before settings were saved keep the current ones
+        if let Some(settings) = file.settings {
+            let changes = self.settings.changes(&settings);
+            if !changes.is_empty() {
+                self.viewer
+                    .add_info_notification(trf(Text::SettingsFromFile, &[&changes.join(", ")]));
+            }
+            self.settings = settings;
+        }
+        self.reset_results();
+        // Rects of the old graph would be matched to the new nodes with the same ids
+        self.viewer.stored_nodes.clear();
+        self.viewer.selected.clear();
+
+        // Only restore the path if it still runs along existing connections
+        let resolves = file
+            .path_nodes
+            .iter()
+            .all(|node| self.snarl.get_node(*node).is_some())
+            && file
+                .path_nodes
+                .windows(2)
+                .all(|pair| is_connected(&self.snarl, pair[0], pair[1]));
+        if !resolves {
+            self.viewer
+                .add_warning_notification(tr(Text::SavedPathDiscarded));
+            return;
+        }
+        self.viewer.path_nodes = file.path_nodes;
+        self.total_cost = file.total_cost.map(|total| total.0);
+        self.path_version = Some(self.viewer.graph_version);
+    }
+
+    // Write the last run as JSON or CSV, depending on the picked extension
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_result(&mut self, ctx: &egui::Context) {
+        let Some(result) = &self.last_run else {
+            return;
+        };
+        let graph = model::Graph::from_snarl(&self.snarl);
+        let formats = vec![
+            ("JSON", "json", report::to_json(&graph, result)),
+            ("CSV", "csv", report::to_csv(&graph, result)),
+        ];
+        self.export_files(
+            ctx,
+            "result.json",
+            Export {
+                formats,
+                sidecar: None,
+            },
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_trace(&mut self, ctx: &egui::Context) {
+        let graph = model::Graph::from_snarl(&self.snarl);
+        let formats = vec![
+            ("Text", "txt", trace::to_text(&graph, &self.trace)),
+            ("JSON", "json", trace::to_json(&graph, &self.trace)),
+        ];
+        self.export_files(
+            ctx,
+            "trace.txt",
+            Export {
+                formats,
+                sidecar: None,
+            },
+        );
+    }
+
+    // Downloads can't be told apart by the name the user picks, so the formats are fixed
+    #[cfg(target_arch = "wasm32")]
+    fn export_result(&mut self, ctx: &egui::Context) {
+        let Some(result) = &self.last_run else {
+            return;
+        };
+        let json = report::to_json(&model::Graph::from_snarl(&self.snarl), result);
+        self.export_file(ctx, "result.json", "JSON", "json", json);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn export_trace(&mut self, ctx: &egui::Context) {
+        let text = trace::to_text(&model::Graph::from_snarl(&self.snarl), &self.trace);
+        self.export_file(ctx, "trace.txt", "Text", "txt", text);
+    }
+
+    // Replace the graph with an imported one, keeping a single Start/Finish and only valid connections
+    fn load_imported(&mut self, graph: model::Graph) {
+        let count = graph.nodes.len();
+        let model::Conversion {
+            snarl,
+            demoted,
+            skipped,
+            ..
+        } = graph.to_snarl();
+        self.clear_graph();
+        self.snarl = snarl;
+        self.fit_view = Some(FitView::Requested);
+        self.viewer.mark_changed();
+
+        if demoted > 0 {
+            self.viewer
+                .add_warning_notification(trf(Text::ExtraEndpointsDemoted, &[&demoted]));
+        }
+        if skipped > 0 {
+            self.viewer
+                .add_warning_notification(trf(Text::SkippedConnections, &[&skipped]));
+        }
+        self.viewer
+            .add_success_notification(trf(Text::ImportedNodes, &[&count]));
+    }
+
+    fn handle_add_shortcuts(&mut self, ui: &mut egui::Ui) {
+        let Some(view) = self.viewer.view else {
+            return;
+        };
+        let Some(hover) = ui
+            .input(|i| i.pointer.hover_pos())
+            .filter(|pos| view.rect.contains(*pos))
+        else {
+            return;
+        };
+        let pressed = ui.input(|i| {
+            if !i.modifiers.is_none() {
+                return None;
+            }
+            if i.key_pressed(egui::Key::D) {
+                Some(NodeKind::Distance(HashMap::new()))
+            } else if i.key_pressed(egui::Key::S) {
+                Some(NodeKind::Start)
+            } else if i.key_pressed(egui::Key::F) {
+                Some(NodeKind::Finish(HashMap::new()))
+            } else {
+                None
+            }
+        });
+        let Some(kind) = pressed else {
+            return;
+        };
+        // Same single Start / single Finish rule as the graph menu
+        let taken = !matches!(kind, NodeKind::Distance(_))
+            && self
+                .snarl
+                .nodes()
+                .any(|node| std::mem::discriminant(&node.kind) == std::mem::discriminant(&kind));
+        if taken {
+            self.viewer
+                .add_error_notification(trf(Text::AlreadyExists, &[&kind.name()]));
+            return;
+        }
+        self.snarl
+            .insert_node(view.screen_pos_to_graph(hover), DijkstraNode::new(kind));
+        self.viewer.mark_changed();
+    }
+
+    // Connections of the hovered pin with their length and the cost the search uses, for
+    // checking the cost scale. Hidden while dragging, e.g. a new wire, and under windows.
+    fn show_pin_tooltip(&self, ui: &mut egui::Ui) {
+        if !ui.ui_contains_pointer() || ui.input(|i| i.pointer.any_down()) {
+            return;
+        }
+        let Some(hover) = ui.input(|i| i.pointer.hover_pos()) else {
+            return;
+        };
+        let hovered = self
+            .viewer
+            .pin_rects
+            .borrow()
+            .iter()
+            .find(|(_, rect)| rect.expand(2.0).contains(hover))
+            .map(|(key, _)| *key);
+        let Some((node, side)) = hovered else {
+            return;
+        };
+
+        let connections: Vec<(NodeId, NodeId)> = self
+            .snarl
+            .wires()
+            .map(|(out_pin, in_pin)| (out_pin.node, in_pin.node))
+            .filter(|(from, to)| match side {
+                PinSide::Input => *to == node,
+                PinSide::Output => *from == node,
+            })
+            .collect();
+        egui::show_tooltip_at_pointer(
+            ui.ctx(),
+            ui.layer_id(),
+            egui::Id::new(("pin_tooltip", node, side)),
+            |ui| {
+                ui.strong(tr(match side {
+                    PinSide::Input => Text::IncomingConnections,
+                    PinSide::Output => Text::OutgoingConnections,
+                }));
+                if connections.is_empty() {
+                    ui.weak(tr(Text::NoConnections));
+                }
+                for (from, to) in connections {
+                    let other = if side == PinSide::Input { from } else { to };
+                    let distance = match (
+                        self.viewer.stored_nodes.get(&from),
+                        self.viewer.stored_nodes.get(&to),
+                    ) {
+                        (Some(from), Some(to)) => {
+                            format!("{:.0}", geometric_distance(*from, *to))
+                        }
+                        _ => "-".to_string(),
+                    };
+                    let manual = self.snarl[to].meta.overrides.contains_key(&from);
+                    let suffix = if manual { tr(Text::ManualSuffix) } else { "" };
+                    ui.label(
+                        trf(
+                            Text::PinConnection,
+                            &[
+                                &node_label(&self.snarl, other),
+                                &distance,
+                                &format_cost(edge_cost(&self.snarl, from, to)),
+                            ],
+                        ) + suffix,
+                    );
+                }
+            },
+        );
+    }
+
+    // Draw each connection's cost at the middle of its wire
+    fn show_weight_labels(&self, ui: &mut egui::Ui) {
+        let Some(view) = self.viewer.view else {
+            return;
+        };
+        let pins = self.viewer.pin_rects.borrow();
+        let font = egui::FontId::proportional(12.0 * view.scale);
+        let painter = ui.painter_at(view.rect);
+        let mut placed: Vec<Rect> = Vec::new();
+        for (out_pin, in_pin) in self.snarl.wires() {
+            let (from, to) = (out_pin.node, in_pin.node);
+            let (Some(from_rect), Some(to_rect)) = (
+                pins.get(&(from, PinSide::Output)),
+                pins.get(&(to, PinSide::Input)),
+            ) else {
+                continue;
+            };
+            let cost = match &self.snarl[to].kind {
+                NodeKind::Distance(costs) | NodeKind::Finish(costs) => costs.get(&from),
+                NodeKind::Start => None,
+            };
+            let Some(cost) = cost else {
+                continue;
+            };
+            let on_path = self
+                .viewer
+                .path_nodes
+                .windows(2)
+                .any(|pair| pair[0] == from && pair[1] == to);
+            let (color, font) = if on_path {
+                (
+                    self.viewer.palette.path,
+                    egui::FontId::proportional(font.size * 1.2),
+                )
+            } else {
+                (ui.visuals().text_color(), font.clone())
+            };
+            let galley = painter.layout_no_wrap(format_cost(*cost), font, color);
+
+            // The wire is symmetric, so its middle is halfway between the pins
+            let middle = from_rect.center().lerp(to_rect.center(), 0.5);
+            let mut rect = Rect::from_center_size(middle, galley.size()).expand(2.0);
+            // Move the label down until it stops covering earlier ones
+            while placed.iter().any(|other| other.intersects(rect)) {
+                rect = rect.translate(egui::vec2(0.0, rect.height()));
+            }
+            placed.push(rect);
+
+            painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+            painter.galley(rect.min + egui::vec2(2.0, 2.0), galley, color);
+        }
+    }
+
+    // Key to the colors in the bottom left corner, only those that show up right now
+    fn show_legend(&mut self, ui: &mut egui::Ui) {
+        let Some(view) = self.viewer.view else {
+            return;
+        };
+        let stats = self.viewer.stats(&self.snarl);
+        let palette = self.viewer.palette;
+        let mut rows = Vec::new();
+        if stats.starts > 0 {
+            rows.push((self.viewer.start_color, tr(Text::Start)));
+        }
+        if stats.distances > 0 {
+            rows.push((palette.node, tr(Text::Distance)));
+        }
+        if stats.finishes > 0 {
+            rows.push((self.viewer.finish_color, tr(Text::Finish)));
+        }
+        if !self.viewer.path_nodes.is_empty() {
+            rows.push((palette.path, tr(Text::LegendPath)));
+        }
+        if let Some((predecessors, successors)) = &self.viewer.hovered_neighbors {
+            if !predecessors.is_empty() {
+                rows.push((palette.predecessor, tr(Text::LegendPredecessor)));
+            }
+            if !successors.is_empty() {
+                rows.push((palette.successor, tr(Text::LegendSuccessor)));
+            }
+        }
+        if rows.is_empty() {
+            return;
+        }
+
+        let painter = ui.painter_at(view.rect);
+        let font = egui::FontId::proportional(12.0);
+        let text_color = ui.visuals().text_color();
+        let galleys: Vec<_> = rows
+            .iter()
+            .map(|(color, text)| {
+                (
+                    *color,
+                    painter.layout_no_wrap(text.to_string(), font.clone(), text_color),
+                )
+            })
+            .collect();
+        let row_height = 18.0;
+        let width = galleys
+            .iter()
+            .map(|(_, galley)| galley.size().x)
+            .fold(0.0, f32::max)
+            + 28.0;
+        let size = egui::vec2(width, row_height * galleys.len() as f32 + 8.0);
+        let rect = Rect::from_min_size(
+            view.rect.left_bottom() + egui::vec2(8.0, -8.0 - size.y),
+            size,
+        );
+        painter.rect_filled(rect, 4.0, ui.visuals().extreme_bg_color.gamma_multiply(0.9));
+        for (idx, (color, galley)) in galleys.into_iter().enumerate() {
+            let y = rect.min.y + 4.0 + row_height * (idx as f32 + 0.5);
+            painter.circle_filled(egui::pos2(rect.min.x + 12.0, y), 5.0, color);
+            painter.galley(
+                egui::pos2(rect.min.x + 24.0, y - galley.size().y / 2.0),
+                galley,
+                text_color,
+            );
+        }
+    }
+
+    // Which way each connection goes, pointing into the input pin along the end of the wire
+    // Drawn over the snarl's own wire, which can only be as wide as all the others
+    fn show_path_wires(&self, ui: &mut egui::Ui) {
+        let Some(view) = self.viewer.view else {
+            return;
+        };
+        let pins = self.viewer.pin_rects.borrow();
+        let painter = ui.painter_at(view.rect);
+        let frame_size = wire::frame_size(ui.style(), view.scale);
+        let stroke = egui::Stroke::new(
+            self.appearance.wire_width * self.appearance.path_width * view.scale,
+            self.viewer.palette.path,
+        );
+        for pair in self.viewer.path_nodes.windows(2) {
+            let (Some(from), Some(to)) = (
+                pins.get(&(pair[0], PinSide::Output)),
+                pins.get(&(pair[1], PinSide::Input)),
+            ) else {
+                continue;
+            };
+            let points = (0..=PATH_WIRE_SAMPLES)
+                .map(|idx| {
+                    wire::point_on_wire(
+                        self.settings.wire_style,
+                        frame_size,
+                        from.center(),
+                        to.center(),
+                        idx as f32 / PATH_WIRE_SAMPLES as f32,
+                    )
+                })
+                .collect();
+            painter.add(egui::Shape::line(points, stroke));
+        }
+    }
+
+    fn show_arrowheads(&self, ui: &mut egui::Ui) {
+        let Some(view) = self.viewer.view else {
+            return;
+        };
+        let pins = self.viewer.pin_rects.borrow();
+        let painter = ui.painter_at(view.rect);
+        let frame_size = wire::frame_size(ui.style(), view.scale);
+        let size = ARROW_SIZE * view.scale;
+        let color = ui.visuals().widgets.noninteractive.fg_stroke.color;
+        for (out_pin, in_pin) in self.snarl.wires() {
+            let (from, to) = (out_pin.node, in_pin.node);
+            let (Some(from_rect), Some(to_rect)) = (
+                pins.get(&(from, PinSide::Output)),
+                pins.get(&(to, PinSide::Input)),
+            ) else {
+                continue;
+            };
+            let (start, end) = (from_rect.center(), to_rect.center());
+            let style = self.settings.wire_style;
+            let before = wire::point_on_wire(style, frame_size, start, end, 0.95);
+            let direction = end - before;
+            if direction.length() <= f32::EPSILON {
+                continue;
+            }
+            let direction = direction.normalized();
+            // The tip touches the pin instead of disappearing under it
+            let tip = end - direction * to_rect.width() / 2.0;
+            let back = tip - direction * size;
+            let side = direction.rot90() * size / 2.0;
+            let on_path = self
+                .viewer
+                .path_nodes
+                .windows(2)
+                .any(|pair| pair[0] == from && pair[1] == to);
+            let color = if on_path {
+                self.viewer.palette.path
+            } else {
+                color
+            };
+            painter.add(egui::Shape::convex_polygon(
+                vec![tip, back + side, back - side],
+                color,
+                egui::Stroke::NONE,
+            ));
+        }
+    }
+
+    // A dot running along the path from Start to Finish, slower on the expensive edges
+    fn show_path_pulse(&self, ui: &mut egui::Ui) {
+        let Some(view) = self.viewer.view else {
+            return;
+        };
+        let path = &self.viewer.path_nodes;
+        if path.len() < 2 || path.iter().any(|node| self.snarl.get_node(*node).is_none()) {
+            return;
+        }
+        let costs: Vec<f64> = path
+            .windows(2)
+            .map(|pair| edge_cost(&self.snarl, pair[0], pair[1]).max(PULSE_MIN_EDGE_COST))
+            .collect();
+        let total: f64 = costs.iter().sum();
+        let mut left = (ui.input(|i| i.time) % PULSE_LOOP_SECONDS) / PULSE_LOOP_SECONDS * total;
+        let Some((edge, t)) = costs.iter().enumerate().find_map(|(edge, cost)| {
+            if left <= *cost {
+                Some((edge, left / cost))
+            } else {
+                left -= cost;
+                None
+            }
+        }) else {
+            return;
+        };
+
+        let pins = self.viewer.pin_rects.borrow();
+        let (Some(from), Some(to)) = (
+            pins.get(&(path[edge], PinSide::Output)),
+            pins.get(&(path[edge + 1], PinSide::Input)),
+        ) else {
+            return;
+        };
+        let pos = wire::point_on_wire(
+            self.settings.wire_style,
+            wire::frame_size(ui.style(), view.scale),
+            from.center(),
+            to.center(),
+            t as f32,
+        );
+        let radius = 2.0 * self.appearance.wire_width.max(2.0) * view.scale;
+        let painter = ui.painter_at(view.rect);
+        painter.circle_filled(
+            pos,
+            radius * 1.8,
+            self.viewer.palette.path.gamma_multiply(0.3),
+        );
+        painter.circle_filled(pos, radius, self.viewer.palette.path);
+        ui.ctx().request_repaint();
+    }
+
+    // Cost the wire being dragged would get if dropped on the hovered input pin
+    fn show_cost_preview(&mut self, ui: &mut egui::Ui) {
+        let (origin, hover, down) = ui.input(|i| {
+            (
+                i.pointer.press_origin(),
+                i.pointer.hover_pos(),
+                i.pointer.primary_down(),
+            )
+        });
+        let cost = match (origin, hover) {
+            (Some(origin), Some(hover)) if down => {
+                let pins = self.viewer.pin_rects.borrow();
+                let find = |side: PinSide, pos: Pos2| {
+                    pins.iter()
+                        .find(|((_, pin_side), rect)| *pin_side == side && rect.contains(pos))
+                        .map(|((node, _), _)| *node)
+                };
+                find(PinSide::Output, origin)
+                    .zip(find(PinSide::Input, hover))
+                    // Only where dropping the wire would add a connection
+                    .filter(|(from, to)| {
+                        from != to
+                            && can_connect(&self.snarl[*from].kind, &self.snarl[*to].kind)
+                            && !is_connected(&self.snarl, *from, *to)
+                    })
+                    .and_then(|(from, to)| {
+                        Some(geometric_cost(
+                            *self.viewer.stored_nodes.get(&from)?,
+                            *self.viewer.stored_nodes.get(&to)?,
+                            self.settings.cost_scale,
+                        ))
+                    })
+            }
+            _ => None,
+        };
+        let Some(cost) = cost else {
+            self.viewer.cost_preview = None;
+            return;
+        };
+        if self.viewer.cost_preview.as_ref().map(|(c, _)| *c) != Some(cost) {
+            self.viewer.cost_preview = Some((cost, trf(Text::Cost, &[&format_cost(cost)])));
+        }
+        if let (Some((_, label)), Some(hover)) = (&self.viewer.cost_preview, hover) {
+            ui.painter().text(
+                hover + egui::vec2(12.0, -12.0),
+                egui::Align2::LEFT_BOTTOM,
+                label,
+                egui::FontId::proportional(14.0),
+                ui.visuals().strong_text_color(),
+            );
+        }
+    }
+
+    // The graph and endpoints a search runs on
+    fn search_input(&mut self) -> Result<SearchInput, String> {
+        let (start, finish) = endpoints(&self.snarl)?;
+        let adjacency = self.viewer.adjacency(&self.snarl);
+        Ok((search_graph(&self.snarl, adjacency), start, finish))
+    }
+
+    // Runs started by the user report their result, auto-recalc ones stay quiet
+    fn start_search(&mut self, manual: bool) {
+        match self.search_input() {
+            Ok(input) => self.send_search(input, manual),
+            Err(err) => {
+                self.pending_search = None;
+                self.viewer.path_nodes.clear();
+                self.total_cost = None;
+                self.path_error = Some(err.clone());
+                self.last_run = None;
+                if manual {
+                    self.viewer.add_error_notification(err);
+                }
+            }
+        }
+    }
+
+    fn send_search(&mut self, input: SearchInput, manual: bool) {
+        self.next_search_id += 1;
+        let (graph, start, finish) = input;
+        log::debug!(
+            "Search {} started on {} nodes and {} connections",
+            self.next_search_id,
+            graph.nodes.len(),
+            graph.edges.values().map(Vec::len).sum::<usize>()
+        );
+        let progress = Arc::new(algorithm::Progress::default());
+        self.search_worker.send(worker::SearchRequest {
+            id: self.next_search_id,
+            graph,
+            start,
+            finish,
+            record_trace: self.record_trace,
+            progress: Arc::clone(&progress),
+        });
+        // The replaced search's reply would be ignored anyway, it stops early. A replaced
+        // manual run still reports when the newer one is done.
+        if let Some(replaced) = &self.pending_search {
+            replaced.progress.cancelled.store(true, Ordering::Relaxed);
+        }
+        let manual = manual || self.pending_search.as_ref().is_some_and(|p| p.manual);
+        self.pending_search = Some(PendingSearch {
+            id: self.next_search_id,
+            version: self.viewer.graph_version,
+            manual,
+            progress,
+        });
+    }
+
+    fn poll_search(&mut self) {
+        while let Some(response) = self.search_worker.try_recv() {
+            let Some(pending) = self
+                .pending_search
+                .take_if(|pending| pending.id == response.id)
+            else {
+                continue;
+            };
+            // The shown results stay, auto-recalc waits for the next change. Checked first,
+            // a search may finish before it notices the cancel or the graph may have changed.
+            if pending.progress.cancelled.load(Ordering::Relaxed)
+                || matches!(response.result, Err(algorithm::PathError::Cancelled))
+            {
+                log::debug!("Search {} was cancelled", response.id);
+                self.viewer.add_info_notification(tr(Text::SearchCancelled));
+                self.last_searched = Some(pending.version);
+                continue;
+            }
+            // The graph was edited while searching, a manual run is started again
+            if pending.version != self.viewer.graph_version {
+                log::debug!("Search {} is stale, the graph changed", response.id);
+                if pending.manual {
+                    self.start_search(true);
+                }
+                continue;
+            }
+
+            self.search_time = Some(response.elapsed);
+            match &response.result {
+                Ok(result) => log::debug!(
+                    "Search {} found a path of {} nodes costing {} in {:.2} ms",
+                    response.id,
+                    result.path.len(),
+                    result.total,
+                    response.elapsed.as_secs_f64() * 1000.0
+                ),
+                Err(err) => log::debug!("Search {} failed: {}", response.id, err),
+            }
+            self.trace = response.trace;
+            match response.result {
+                Ok(result) => {
+                    self.viewer.path_nodes = result.path.clone();
+                    self.path_version = Some(pending.version);
+                    self.total_cost = Some(result.total);
+                    self.path_error = None;
+                    self.distance_row = None;
+                    self.last_run = Some(RunResult {
+                        algorithm: "dijkstra",
+                        path: result.path,
+                        distances: result.distances,
+                        predecessors: result.predecessors,
+                        total: result.total,
+                        finished_at: web_time::SystemTime::now(),
+                    });
+                    if pending.manual {
+                        self.viewer.add_success_notification(trf(
+                            Text::PathFound,
+                            &[&format_cost(result.total)],
+                        ));
+                    }
+                }
+                Err(err) => {
+                    let message = match err {
+                        algorithm::PathError::UnknownNode(node) => {
+                            trf(Text::UnknownNode, &[&node.0])
+                        }
+                        algorithm::PathError::NoPath => tr(Text::NoPathFound).to_string(),
+                        algorithm::PathError::Cancelled => tr(Text::SearchCancelled).to_string(),
+                    };
+                    self.viewer.path_nodes.clear();
+                    self.total_cost = None;
+                    self.last_run = None;
+                    if pending.manual {
+                        self.viewer.add_error_notification(message.clone());
+                    }
+                    self.path_error = Some(message);
+                }
+            }
+            self.last_searched = Some(pending.version);
+        }
+    }
+
+    // Everything around the graph, hidden while presenting
+    fn show_editor_windows(&mut self, ctx: &egui::Context) {
+        self.show_controls(ctx);
+        self.show_grid_dialog(ctx);
+        self.show_random_dialog(ctx);
+        self.show_complete_dialog(ctx);
+        self.show_maze_dialog(ctx);
+        self.show_matrix_import_dialog(ctx);
+        self.show_edge_list_dialog(ctx);
+        self.show_profiling_window(ctx);
+        self.show_log_window(ctx);
+        self.show_path_summary_window(ctx);
+        self.show_appearance_window(ctx);
+        self.show_help_window(ctx);
+        self.show_find_window(ctx);
+        self.show_status_bar(ctx);
+        self.show_inspector(ctx);
+    }
+
+    // The side panel and the Kalkulátor window
+    fn show_controls(&mut self, ctx: &egui::Context) {
+        egui::SidePanel::left("controls").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button(tr(Text::Help)).clicked() {
+                    self.show_help = true;
+                }
+                if ui
+                    .button(tr(Text::Presentation))
+                    .on_hover_text("F11")
+                    .clicked()
+                {
+                    self.toggle_presentation();
+                }
+            });
+            ui.add_enabled_ui(self.pending_dialog.is_none(), |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button(tr(Text::Save)).clicked() {
+                        self.save(ui.ctx());
+                    }
+                    if ui.button(tr(Text::SaveAs)).clicked() {
+                        self.save_as(ui.ctx());
+                    }
+                    if ui.button(tr(Text::Load)).clicked() {
+                        self.load(ui.ctx());
+                    }
+                });
+            });
+            ui.horizontal(|ui| {
+                if ui.button(tr(Text::CopyGraph)).clicked() {
+                    self.copy_graph(ui.ctx());
+                }
+                if ui.button(tr(Text::PasteGraphFromClipboard)).clicked() {
+                    self.request_graph_paste(ui.ctx());
+                }
+            });
+            match &self.current_file {
+                Some(path) => ui.label(trf(
+                    Text::File,
+                    &[&path.file_name().unwrap_or_default().to_string_lossy()],
+                )),
+                None => ui.weak(tr(Text::NotSavedYet)),
+            }
+            .on_hover_text(tr(Text::FileHover));
+            self.show_issues(ui);
+
+            // Only one file dialog can be open at a time
+            let dialog_closed = self.pending_dialog.is_none();
+            if ui
+                .add_enabled(dialog_closed, egui::Button::new(tr(Text::ExportDot)))
+                .clicked()
+            {
+                let dot = dot::to_dot(&model::Graph::from_snarl(&self.snarl));
+                self.export_file(ui.ctx(), "graph.dot", "Graphviz DOT", "dot", dot);
+            }
+            if ui
+                .add_enabled(dialog_closed, egui::Button::new(tr(Text::ImportDot)))
+                .clicked()
+            {
+                self.import_file(ui.ctx(), ImportFormat::Dot);
+            }
+            if ui
+                .add_enabled(dialog_closed, egui::Button::new(tr(Text::ExportSvg)))
+                .clicked()
+            {
+                self.viewer.update_costs(&mut self.snarl);
+                // The export has a white background, the light palette's path color fits it
+                let palette = palette::Palette::new(self.appearance.palette, false);
+                let svg = svg::to_svg(
+                    &model::Graph::from_snarl(&self.snarl),
+                    &self.viewer.stored_nodes,
+                    &self.viewer.path_nodes,
+                    &svg::Options {
+                        legend: self.appearance.legend_in_exports,
+                        path_color: self.appearance.path_color.unwrap_or(palette.path),
+                        path_width: self.appearance.path_width,
+                        dim_off_path: self.appearance.dim_off_path,
+                    },
+                );
+                self.export_file(ui.ctx(), "graph.svg", "SVG", "svg", svg);
+            }
+            if ui
+                .add_enabled(dialog_closed, egui::Button::new(tr(Text::ExportGraphMl)))
+                .clicked()
+            {
+                let xml = graphml::to_graphml(&model::Graph::from_snarl(&self.snarl));
+                self.export_file(ui.ctx(), "graph.graphml", "GraphML", "graphml", xml);
+            }
+            if ui
+                .add_enabled(dialog_closed, egui::Button::new(tr(Text::ImportGraphMl)))
+                .clicked()
+            {
+                self.import_file(ui.ctx(), ImportFormat::GraphMl);
+            }
+            if ui
+                .add_enabled(dialog_closed, egui::Button::new(tr(Text::ExportMatrix)))
+                .clicked()
+            {
+                // Costs are only refreshed while drawing, make sure none are stale
+                self.viewer.update_costs(&mut self.snarl);
+                let (csv, endpoints) = matrix::to_csv(&model::Graph::from_snarl(&self.snarl));
+                #[cfg(target_arch = "wasm32")]
+                {
+                    download("graph.endpoints.json".to_string(), endpoints.into_bytes());
+                    self.export_file(ui.ctx(), "graph.csv", "CSV", "csv", csv);
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                self.export_files(
+                    ui.ctx(),
+                    "graph.csv",
+                    Export {
+                        formats: vec![("CSV", "csv", csv)],
+                        sidecar: Some(("endpoints.json", endpoints)),
+                    },
+                );
+            }
+            if ui
+                .add_enabled(dialog_closed, egui::Button::new(tr(Text::ImportCsvMatrix)))
+                .clicked()
+            {
+                self.import_file(ui.ctx(), ImportFormat::Matrix);
+            }
+            if ui
+                .add_enabled(dialog_closed, egui::Button::new(tr(Text::ExportEdgeList)))
+                .clicked()
+            {
+                self.viewer.update_costs(&mut self.snarl);
+                let text = edgelist::to_text(&model::Graph::from_snarl(&self.snarl));
+                self.export_file(ui.ctx(), "graph.txt", "Edge list", "txt", text);
+            }
+            if ui.button(tr(Text::ImportEdgeList)).clicked() {
+                self.edge_list_import
+                    .get_or_insert_with(EdgeListImport::default);
+            }
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("mermaid_direction")
+                    .selected_text(self.mermaid_direction.keyword())
+                    .width(50.0)
+                    .show_ui(ui, |ui| {
+                        for direction in
+                            [mermaid::Direction::LeftRight, mermaid::Direction::TopDown]
+                        {
+                            ui.selectable_value(
+                                &mut self.mermaid_direction,
+                                direction,
+                                direction.keyword(),
+                            );
+                        }
+                    });
+                let copy = ui.button(tr(Text::ExportMermaid));
+                let save = ui
+                    .add_enabled(dialog_closed, egui::Button::new(tr(Text::SaveEllipsis)))
+                    .on_hover_text(tr(Text::SaveMermaidHover));
+                if copy.clicked() || save.clicked() {
+                    self.viewer.update_costs(&mut self.snarl);
+                    let chart = mermaid::to_mermaid(
+                        &model::Graph::from_snarl(&self.snarl),
+                        &self.viewer.path_nodes,
+                        self.mermaid_direction,
+                    );
+                    if save.clicked() {
+                        self.export_file(ui.ctx(), "graph.mmd", "Mermaid", "mmd", chart);
+                    } else {
+                        ui.ctx().copy_text(chart);
+                        self.viewer
+                            .add_success_notification(tr(Text::MermaidCopied));
+                    }
+                }
+            });
+
+            let export = ui
+                .add_enabled(
+                    self.last_run.is_some() && dialog_closed,
+                    egui::Button::new(tr(Text::ExportResult)),
+                )
+                .on_disabled_hover_text(tr(Text::RunFirst));
+            if export.clicked() {
+                self.export_result(ui.ctx());
+            }
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.record_trace, tr(Text::RecordTrace));
+                let export = ui
+                    .add_enabled(
+                        !self.trace.is_empty() && dialog_closed,
+                        egui::Button::new(tr(Text::ExportTrace)),
+                    )
+                    .on_disabled_hover_text(tr(Text::ExportTraceHover));
+                if export.clicked() {
+                    self.export_trace(ui.ctx());
+                }
+            });
+
+            self.show_distance_table(ui);
+
+            ui.separator();
+            self.show_align_tools(ui);
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label(tr(Text::Theme));
+                let dark = ui.selectable_value(&mut self.theme, egui::Theme::Dark, tr(Text::Dark));
+                let light =
+                    ui.selectable_value(&mut self.theme, egui::Theme::Light, tr(Text::Light));
+                if dark.changed() || light.changed() {
+                    ui.ctx().set_theme(self.theme);
+                }
+            });
+            if ui
+                .button(tr(Text::ResetLayout))
+                .on_hover_text(tr(Text::ResetLayoutHover))
+                .clicked()
+            {
+                self.reset_layout(ui.ctx());
+            }
+        });
+
+        // Fixed id, so the window keeps its place when the language changes its title
+        egui::Window::new(tr(Text::Calculator))
+            .id(egui::Id::new("calculator"))
+            .show(ctx, |ui| {
+                ui.label(tr(Text::Actions));
+                if ui.button(tr(Text::RemoveAll)).clicked() && self.snarl.nodes().count() > 0 {
+                    self.remove_all_confirm = true;
+                }
+
+                if ui
+                    .button(tr(Text::FitView))
+                    .on_hover_text(tr(Text::FitViewHover))
+                    .clicked()
+                {
+                    self.fit_view = Some(FitView::Requested);
+                }
+
+                if ui.button(tr(Text::GenerateGrid)).clicked() {
+                    self.grid_dialog.get_or_insert_with(GridParams::default);
+                }
+                if ui.button(tr(Text::GenerateRandom)).clicked() {
+                    self.random_dialog.get_or_insert_with(RandomParams::default);
+                }
+                if ui.button(tr(Text::GenerateComplete)).clicked() {
+                    self.complete_dialog
+                        .get_or_insert_with(CompleteParams::default);
+                }
+                if ui.button(tr(Text::GenerateMaze)).clicked() {
+                    self.maze_dialog.get_or_insert_with(MazeParams::default);
+                }
+
+                if ui.button(tr(Text::ClearPath)).clicked() {
+                    self.clear_path();
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button(tr(Text::RunDijkstra)).clicked() {
+                        self.start_search(true);
+                    }
+                    if let Some(pending) = &self.pending_search {
+                        ui.spinner();
+                        let settled = pending.progress.settled.load(Ordering::Relaxed);
+                        ui.label(trf(Text::RunningSettled, &[&settled]));
+                        if ui.button(tr(Text::Cancel)).clicked() {
+                            pending.progress.cancelled.store(true, Ordering::Relaxed);
+                        }
+                        // The counter changes without anything asking for a repaint
+                        ui.ctx().request_repaint_after(Duration::from_millis(100));
+                    } else if self.recalc_debounced {
+                        ui.weak(tr(Text::RecalcPending));
+                    }
+                });
+                if self.settings.auto_recalc {
+                    self.show_recalc_status(ui);
+                } else {
+                    match self.total_cost {
+                        Some(cost) if !self.viewer.path_nodes.is_empty() => {
+                            ui.label(trf(
+                                Text::CurrentPathCost,
+                                &[&format_cost(cost), &(self.viewer.path_nodes.len() - 1)],
+                            ));
+                        }
+                        _ if self.path_error.is_some() => {
+                            ui.label(tr(Text::NoPath));
+                        }
+                        _ => {}
+                    }
+                }
+                if ui
+                    .button(trf(Text::AutoRecalc, &[&on_off(self.settings.auto_recalc)]))
+                    .clicked()
+                {
+                    self.settings.auto_recalc = !self.settings.auto_recalc;
+                }
+                ui.add_enabled_ui(self.settings.auto_recalc, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(tr(Text::RecalcAtMostEvery));
+                        ui.add(
+                            egui::Slider::new(&mut self.recalc_interval, 0..=1000).suffix(" ms"),
+                        );
+                    });
+                });
+                ui.checkbox(&mut self.viewer.show_notes, tr(Text::ShowNotes));
+                ui.checkbox(&mut self.viewer.show_weights, tr(Text::ShowWeights));
+                ui.checkbox(&mut self.show_profiling, tr(Text::ShowProfiling));
+                ui.checkbox(&mut self.show_log, tr(Text::ShowLog));
+                ui.checkbox(&mut self.show_path_summary, tr(Text::ShowPathSummary));
+                ui.checkbox(&mut self.show_appearance, tr(Text::ShowAppearance));
+                ui.checkbox(&mut self.show_inspector, tr(Text::ShowInspector));
+                let all_locked = self.snarl.nodes().all(|node| node.meta.locked);
+                let label = if all_locked {
+                    Text::UnlockAll
+                } else {
+                    Text::LockAll
+                };
+                if ui.button(tr(label)).clicked() {
+                    for node in self.snarl.nodes_mut() {
+                        node.meta.locked = !all_locked;
+                    }
+                }
+
+                ui.separator();
+                ui.label(tr(Text::GraphSettings));
+                wire_style_combo(ui, &mut self.settings.wire_style);
+                let palette =
+                    palette::Palette::new(self.appearance.palette, ui.visuals().dark_mode);
+                theme_color_edit(
+                    ui,
+                    tr(Text::StartColor),
+                    &mut self.settings.start_color,
+                    palette.start,
+                );
+                theme_color_edit(
+                    ui,
+                    tr(Text::FinishColor),
+                    &mut self.settings.finish_color,
+                    palette.finish,
+                );
+                let scale = &mut self.settings.cost_scale;
+                ui.add(
+                    egui::Slider::new(&mut scale.pixels_per_cost, 1.0..=100.0)
+                        .text(tr(Text::PixelsPerCost)),
+                );
+                ui.add(egui::Slider::new(&mut scale.min_cost, 0..=100).text(tr(Text::MinimumCost)));
+                ui.checkbox(&mut scale.fractional, tr(Text::FractionalCosts))
+                    .on_hover_text(tr(Text::FractionalCostsHover));
+                ui.add_enabled_ui(!scale.fractional, |ui| {
+                    egui::ComboBox::from_label(tr(Text::CostRounding))
+                        .selected_text(tr(scale.rounding.text()))
+                        .show_ui(ui, |ui| {
+                            for rounding in settings::Rounding::ALL {
+                                ui.selectable_value(
+                                    &mut scale.rounding,
+                                    rounding,
+                                    tr(rounding.text()),
+                                );
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(tr(Text::SaveAsDefaults))
+                        .on_hover_text(tr(Text::SaveAsDefaultsHover))
+                        .clicked()
+                    {
+                        match settings::store_preferences(&self.settings) {
+                            Ok(()) => self
+                                .viewer
+                                .add_success_notification(tr(Text::DefaultsSaved)),
+                            Err(err) => self
+                                .viewer
+                                .add_error_notification(trf(Text::FailedToSaveDefaults, &[&err])),
+                        }
+                    }
+                    if ui.button(tr(Text::ResetToDefaults)).clicked() {
+                        match settings::load_preferences() {
+                            Ok(settings) => self.settings = settings,
+                            Err(err) => self.viewer.add_error_notification(trf(
+                                Text::FailedToReadPreferences,
+                                &[&err],
+                            )),
+                        }
+                    }
+                });
+                ui.separator();
+                ui.checkbox(&mut self.reopen_session, tr(Text::ReopenSession));
+                ui.horizontal(|ui| {
+                    ui.label(tr(Text::Language));
+                    for language in Language::ALL {
+                        if ui
+                            .selectable_value(&mut self.language, language, language.name())
+                            .changed()
+                        {
+                            i18n::set_language(language);
+                        }
+                    }
+                });
+                ui.separator();
+                self.show_notification_settings(ui);
+            });
+    }
+}
+
+impl EframeApp for DijkstraApp {
+    fn raw_input_hook(&mut self, _ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        if let Some(FitView::Zoom { factor, pivot }) = self
+            .fit_view
+            .take_if(|fit| matches!(fit, FitView::Zoom { .. }))
+        {
+            raw_input.events.push(egui::Event::PointerMoved(pivot));
+            raw_input.events.push(egui::Event::Zoom(factor));
+        }
+    }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        let has_graph = self.snarl.nodes().next().is_some();
+        let graph = (self.reopen_session && has_graph)
+            .then(|| serde_json::to_string(&self.to_save_file()).ok())
+            .flatten();
+        let session = Session {
+            reopen: self.reopen_session,
+            file: self.current_file.clone(),
+            graph,
+        };
+        eframe::set_value(storage, SESSION_KEY, &session);
+        eframe::set_value(storage, THEME_KEY, &self.theme);
+        eframe::set_value(storage, LANGUAGE_KEY, &self.language);
+        eframe::set_value(storage, APPEARANCE_KEY, &self.appearance);
+        eframe::set_value(storage, NOTIFICATIONS_KEY, &self.viewer.toasts.settings);
+        eframe::set_value(storage, HELP_SEEN_KEY, &true);
+        eframe::set_value(storage, RECALC_INTERVAL_KEY, &self.recalc_interval);
+        eframe::set_value(storage, LAYOUT_KEY, &self.layout());
+    }
+
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let started = web_time::Instant::now();
+        self.viewer.costs_time = Duration::ZERO;
+        let mut snarl_show = Duration::ZERO;
+        self.viewer.toasts.show(ctx);
+        self.poll_dialog();
+        self.poll_search();
+        self.handle_graph_paste(ctx);
+        self.check_file_changes(ctx);
+        self.show_file_changed_banner(ctx);
+        self.show_outdated_path_banner(ctx);
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::F11)) {
+            self.toggle_presentation();
+        }
+        // Presenting only skips drawing the rest, what was open comes back as it was
+        if self.presentation {
+            self.show_presentation_controls(ctx);
+        } else {
+            self.show_editor_windows(ctx);
+        }
+        // Asked for by shortcuts that still work while presenting
+        self.show_graph_paste_confirm(ctx);
+        self.show_remove_all_confirm(ctx);
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            self.viewer.pin_rects.borrow_mut().clear();
+            self.style.wire_style = Some(self.settings.wire_style);
+            self.appearance.apply(&mut self.style);
+            let mut palette =
+                palette::Palette::new(self.appearance.palette, ui.visuals().dark_mode);
+            palette.path = self.appearance.path_color.unwrap_or(palette.path);
+            self.viewer.start_color = self.settings.start_color(&palette);
+            self.viewer.finish_color = self.settings.finish_color(&palette);
+            self.viewer.palette = palette;
+            // An outdated path stays visible, dimmed
+            if self.path_outdated() {
+                self.viewer.palette.path = palette.path.gamma_multiply(0.35);
+            }
+            self.viewer.cost_label_scale = self.appearance.cost_label_scale;
+            self.viewer.highlight_neighbors = self.appearance.highlight_neighbors;
+            self.viewer.dim_off_path = self.appearance.dim_off_path;
+            self.viewer.closeness = self
+                .last_run
+                .as_ref()
+                .filter(|_| self.appearance.distance_emphasis)
+                .map(RunResult::closeness);
+            self.viewer.flashing = self.flashing_node(ctx);
+            self.viewer.two_way_modifier = ctx.input(|i| i.modifiers.shift);
+            if self.viewer.cost_scale != self.settings.cost_scale {
+                self.viewer.cost_scale = self.settings.cost_scale;
+                self.viewer.costs_dirty = true;
+            }
+            let show_started = web_time::Instant::now();
+            let positions = self.shift_for_drawing();
+            self.snarl.show(&mut self.viewer, &self.style, "salty", ui);
+            if let Some(positions) = &positions {
+                self.unshift_after_drawing(positions);
+            }
+            snarl_show = show_started.elapsed();
+            self.viewer.update_hovered(ctx);
+            // Nodes were drawn with the old costs, show the new ones in the next frame
+            if self.viewer.update_costs(&mut self.snarl) {
+                ui.ctx().request_repaint();
+            }
+            if self.appearance.path_width > 1.0 {
+                self.show_path_wires(ui);
+            }
+            if self.appearance.arrowheads {
+                self.show_arrowheads(ui);
+            }
+            if self.viewer.show_weights {
+                self.show_weight_labels(ui);
+            }
+            if self.appearance.path_pulse {
+                self.show_path_pulse(ui);
+            }
+            if self.appearance.legend {
+                self.show_legend(ui);
+            }
+            self.show_pin_tooltip(ui);
+            self.show_cost_preview(ui);
+            if matches!(self.fit_view, Some(FitView::Requested)) {
+                self.plan_fit_view(ui);
+            }
+            self.viewer.selected =
+                HashSet::from_iter(Snarl::<DijkstraNode>::get_selected_nodes("salty", ui));
+
+            if ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::F)) {
+                self.show_find = true;
+                self.find_focus = true;
+            }
+            if ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::S))
+                && self.pending_dialog.is_none()
+            {
+                self.save(ctx);
+            }
+            if !ctx.wants_keyboard_input() {
+                self.handle_clipboard(ui);
+                self.handle_add_shortcuts(ui);
+                if ui.input(|i| i.key_pressed(egui::Key::Home)) {
+                    self.fit_view = Some(FitView::Requested);
+                }
+                if ui.input(|i| i.key_pressed(egui::Key::Delete))
+                    && !self.viewer.selected.is_empty()
+                {
+                    let selected: Vec<NodeId> = self.viewer.selected.iter().copied().collect();
+                    self.viewer.remove_nodes(&selected, &mut self.snarl);
+                }
+            }
+        });
+
+        // Only one auto-recalc search is out at a time and only when the graph changed. While
+        // dragging they are at most `recalc_interval` apart, letting go searches right away.
+        self.recalc_debounced = false;
+        if self.settings.auto_recalc
+            && self.pending_search.is_none()
+            && self.last_searched != Some(self.viewer.graph_version)
+        {
+            let (now, dragging) = ctx.input(|i| (i.time, i.pointer.primary_down()));
+            let wait = if dragging {
+                self.last_auto_search + self.recalc_interval as f64 / 1000.0 - now
+            } else {
+                0.0
+            };
+            if wait > 0.0 {
+                self.recalc_debounced = true;
+                // Come back for the change even if nothing else asks for a repaint
+                ctx.request_repaint_after(Duration::from_secs_f64(wait));
+            } else {
+                self.last_auto_search = now;
+                match self.search_input() {
+                    Ok(input) => self.send_search(input, false),
+                    Err(err) => {
+                        self.viewer.path_nodes.clear();
+                        self.total_cost = None;
+                        self.path_error = Some(err);
+                        self.last_searched = Some(self.viewer.graph_version);
+                    }
+                }
+            }
+        }
+
+        self.frame_times = FrameTimes {
+            update: started.elapsed(),
+            snarl_show,
+            costs: self.viewer.costs_time,
+        };
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn run() -> eframe::Result<()> {
+    let args: Vec<std::ffi::OsString> = std::env::args_os().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "--solve") {
+        std::process::exit(i32::from(solve::run(&args[1..])));
+    }
+    logging::init();
+    // Only for the window, scripts running --solve see the panic on stderr
+    crash::install();
+    // A graph to open, e.g. when started through a file association
+    let file = args.first().map(std::path::PathBuf::from);
+    // Window size and position come back with the rest of the stored state
+    let native_options = NativeOptions {
+        persist_window: true,
+        ..NativeOptions::default()
+    };
+    eframe::run_native(
+        "Visualize dijkstra's algorithm",
+        native_options,
+        Box::new(|cc| Ok(Box::new(DijkstraApp::new(cc, file)))),
+    )
+}
+
+// Draws into the canvas of index.html
+#[cfg(target_arch = "wasm32")]
+pub fn start_web() {
+    use eframe::wasm_bindgen::JsCast;
+
+    logging::init();
+    wasm_bindgen_futures::spawn_local(async {
+        let canvas = eframe::web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.get_element_by_id("the_canvas_id"))
+            .and_then(|element| {
+                element
+                    .dyn_into::<eframe::web_sys::HtmlCanvasElement>()
+                    .ok()
+            });
+        let Some(canvas) = canvas else {
+            eframe::web_sys::console::error_1(
+                &"index.html has no canvas with id the_canvas_id".into(),
+            );
+            return;
+        };
+        let started = eframe::WebRunner::new()
+            .start(
+                canvas,
+                eframe::WebOptions::default(),
+                Box::new(|cc| Ok(Box::new(DijkstraApp::new(cc, None)))),
+            )
+            .await;
+        if let Err(err) = started {
+            eframe::web_sys::console::error_1(&err);
+        }
+    });
+}
@@ -0,0 +1,83 @@
+// Log records kept for the Log window. Everything is also passed to env_logger, so RUST_LOG
+// still decides what reaches stderr.
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use web_time::SystemTime;
+
+// Older entries are dropped once there are this many
+const CAPACITY: usize = 500;
+
+#[derive(Clone)]
+pub struct Entry {
+    pub time: SystemTime,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+static ENTRIES: Mutex<VecDeque<Entry>> = Mutex::new(VecDeque::new());
+
+struct Logger {
+    stderr: env_logger::Logger,
+}
+
+// This crate down to debug, other crates only when something went wrong
+fn kept(metadata: &Metadata) -> bool {
+    if metadata.target().starts_with(env!("CARGO_CRATE_NAME")) {
+        metadata.level() <= Level::Debug
+    } else {
+        metadata.level() <= Level::Warn
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        kept(metadata) || self.stderr.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.stderr.enabled(record.metadata()) {
+            self.stderr.log(record);
+        }
+        if !kept(record.metadata()) {
+            return;
+        }
+        // Poisoned only by a panic while logging, the entries are still fine
+        let mut entries = ENTRIES.lock().unwrap_or_else(PoisonError::into_inner);
+        if entries.len() == CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(Entry {
+            time: SystemTime::now(),
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        });
+    }
+
+    fn flush(&self) {
+        self.stderr.flush();
+    }
+}
+
+pub fn init() {
+    let stderr = env_logger::Builder::from_default_env().build();
+    let max_level = stderr.filter().max(LevelFilter::Debug);
+    if log::set_boxed_logger(Box::new(Logger { stderr })).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+// Copies, so nothing holds the lock while drawing, which may log itself
+pub fn recent(level: LevelFilter) -> Vec<Entry> {
+    ENTRIES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .filter(|entry| entry.level <= level)
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,144 @@
+// Weighted adjacency matrices in CSV, cell (i, j) is the cost from node i to node j
+
+use std::collections::HashMap;
+
+use egui_snarl::NodeId;
+
+use crate::algorithm::{Cost, format_cost};
+use crate::model::{Graph, Role};
+
+// Matrix read from CSV, labels come from the header row when there is one
+pub struct Matrix {
+    pub labels: Vec<String>,
+    pub edges: Vec<(usize, usize, Cost)>,
+}
+
+fn is_number(cell: &str) -> bool {
+    cell.parse::<f32>().is_ok()
+}
+
+// Split a CSV line, cells may be quoted to contain commas and doubled quotes
+fn split_row(line: &str) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.next_if_eq(&'"').is_some() => cell.push('"'),
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            c => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+fn quote_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+// Write the matrix with a header row and column of node labels, together with a
+// small JSON sidecar giving the indices of Start and Finish
+pub fn to_csv(graph: &Graph) -> (String, String) {
+    let costs: HashMap<(NodeId, NodeId), Cost> = graph
+        .edges
+        .iter()
+        .filter_map(|edge| Some(((edge.from, edge.to), edge.cost?)))
+        .collect();
+    let mut csv = String::new();
+    for node in &graph.nodes {
+        csv.push(',');
+        csv.push_str(&quote_cell(&node.display_label()));
+    }
+    csv.push('\n');
+    for from in &graph.nodes {
+        csv.push_str(&quote_cell(&from.display_label()));
+        for to in &graph.nodes {
+            let cost = costs.get(&(from.id, to.id)).copied().unwrap_or(0.0);
+            csv.push_str(&format!(",{}", format_cost(cost)));
+        }
+        csv.push('\n');
+    }
+
+    let index = |role: Role| {
+        graph
+            .nodes
+            .iter()
+            .position(|node| node.role == role)
+            .map_or("null".to_string(), |idx| idx.to_string())
+    };
+    let endpoints = format!(
+        "{{\n  \"start\": {},\n  \"finish\": {}\n}}\n",
+        index(Role::Start),
+        index(Role::Finish)
+    );
+    (csv, endpoints)
+}
+
+// Parse a square matrix, 0 or an empty cell means no connection. A first row with
+// non-numeric cells is taken as a header, then every row starts with its label too.
+pub fn parse(text: &str) -> Result<Matrix, String> {
+    let rows: Vec<(usize, Vec<String>)> = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| (idx + 1, split_row(line)))
+        .collect();
+    let Some((_, first)) = rows.first() else {
+        return Err("The file is empty".to_string());
+    };
+
+    let has_header = first
+        .iter()
+        .any(|cell| !cell.is_empty() && !is_number(cell));
+    let (labels, data) = if has_header {
+        let labels: Vec<String> = first[1..].to_vec();
+        (labels, &rows[1..])
+    } else {
+        let labels = (0..first.len()).map(|idx| idx.to_string()).collect();
+        (labels, &rows[..])
+    };
+    let size = labels.len();
+    if data.len() != size {
+        return Err(format!(
+            "The matrix has {} column(s) but {} row(s)",
+            size,
+            data.len()
+        ));
+    }
+
+    // Skip the label column of files with a header
+    let skip = usize::from(has_header);
+    let mut edges = Vec::new();
+    for (from, (line, cells)) in data.iter().enumerate() {
+        if cells.len() != size + skip {
+            return Err(format!(
+                "Row {}: expected {} columns, found {}",
+                line,
+                size + skip,
+                cells.len()
+            ));
+        }
+        for (to, cell) in cells[skip..].iter().enumerate() {
+            if cell.is_empty() {
+                continue;
+            }
+            let cost: Cost = cell
+                .parse()
+                .map_err(|_| format!("Row {}: '{}' is not a number", line, cell))?;
+            if cost < 0.0 {
+                return Err(format!("Row {}: costs can't be negative", line));
+            }
+            if cost > 0.0 {
+                edges.push((from, to, cost));
+            }
+        }
+    }
+    Ok(Matrix { labels, edges })
+}
@@ -0,0 +1,168 @@
+// Mermaid flowchart export for Markdown notes, the found path is highlighted with linkStyle
+
+use std::collections::{HashMap, HashSet};
+
+use egui_snarl::NodeId;
+
+use crate::algorithm::format_cost;
+use crate::model::{Graph, Role};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Direction {
+    LeftRight,
+    TopDown,
+}
+
+impl Direction {
+    pub fn keyword(self) -> &'static str {
+        match self {
+            Direction::LeftRight => "LR",
+            Direction::TopDown => "TD",
+        }
+    }
+}
+
+// Letters, digits and underscores only, not starting with a digit. `end` is a Mermaid
+// keyword in any case and breaks the chart when used as an id.
+fn identifier(label: &str) -> String {
+    let mut id: String = label
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if id.is_empty() || id.starts_with(|c: char| c.is_ascii_digit()) {
+        id.insert(0, 'n');
+    }
+    if id.eq_ignore_ascii_case("end") {
+        id.push('_');
+    }
+    id
+}
+
+// Quotes can't be escaped with a backslash, Mermaid takes HTML entity codes instead
+fn text(label: &str) -> String {
+    label.replace('"', "#quot;").replace('\n', " ")
+}
+
+pub fn to_mermaid(graph: &Graph, path: &[NodeId], direction: Direction) -> String {
+    let mut nodes: Vec<_> = graph.nodes.iter().collect();
+    nodes.sort_by_key(|node| node.id.0);
+
+    let mut used = HashSet::new();
+    let mut ids = HashMap::new();
+    let mut chart = format!("graph {}\n", direction.keyword());
+    for node in nodes {
+        let label = node.display_label();
+        let base = identifier(&label);
+        let mut id = base.clone();
+        let mut suffix = 2;
+        while !used.insert(id.clone()) {
+            id = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        // Start and Finish are drawn as stadiums so they stand out
+        let (open, close) = match node.role {
+            Role::Start | Role::Finish => ("([", "])"),
+            Role::Distance => ("[", "]"),
+        };
+        chart.push_str(&format!(
+            "    {}{}\"{}\"{}\n",
+            id,
+            open,
+            text(&label),
+            close
+        ));
+        ids.insert(node.id, id);
+    }
+
+    // Links are numbered in the order they are written, sort them to keep the output stable
+    let mut edges: Vec<_> = graph.edges.iter().collect();
+    edges.sort_by_key(|edge| (edge.from.0, edge.to.0));
+    edges.dedup_by_key(|edge| (edge.from, edge.to));
+
+    let mut path_links = Vec::new();
+    for (idx, edge) in edges.iter().enumerate() {
+        let (from, to) = (&ids[&edge.from], &ids[&edge.to]);
+        match edge.cost {
+            Some(cost) => {
+                chart.push_str(&format!("    {} -->|{}| {}\n", from, format_cost(cost), to))
+            }
+            None => chart.push_str(&format!("    {} --> {}\n", from, to)),
+        }
+        if path
+            .windows(2)
+            .any(|pair| pair[0] == edge.from && pair[1] == edge.to)
+        {
+            path_links.push(idx.to_string());
+        }
+    }
+    if !path_links.is_empty() {
+        chart.push_str(&format!(
+            "    linkStyle {} stroke:red,stroke-width:3px\n",
+            path_links.join(",")
+        ));
+    }
+    chart
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/mermaid/path.mmd");
+
+    // Labels that need sanitizing as ids and as text
+    fn graph() -> Graph {
+        let mut graph = Graph::default();
+        graph.add_node(Role::Start, "Home".to_string());
+        graph.add_node(Role::Distance, "end".to_string());
+        graph.add_node(Role::Distance, "a b".to_string());
+        graph.add_node(Role::Distance, "a_b".to_string());
+        graph.add_node(Role::Finish, "Say \"hi\"\nthere".to_string());
+        graph.add_edge(0, 1, Some(4.0));
+        graph.add_edge(0, 2, Some(2.5));
+        graph.add_edge(2, 3, None);
+        graph.add_edge(1, 4, Some(3.0));
+        graph.add_edge(3, 4, Some(9.0));
+        // Written once
+        graph.add_edge(1, 4, Some(3.0));
+        graph
+    }
+
+    #[test]
+    fn matches_the_fixture() {
+        let path = [NodeId(0), NodeId(1), NodeId(4)];
+        assert_eq!(to_mermaid(&graph(), &path, Direction::LeftRight), FIXTURE);
+    }
+
+    #[test]
+    fn identifiers_are_sanitized() {
+        assert_eq!(identifier("Home"), "Home");
+        assert_eq!(identifier("a b-c"), "a_b_c");
+        assert_eq!(identifier("Köln"), "K_ln");
+        assert_eq!(identifier("3rd stop"), "n3rd_stop");
+        assert_eq!(identifier(""), "n");
+        assert_eq!(identifier("end"), "end_");
+        assert_eq!(identifier("END"), "END_");
+        assert_eq!(identifier("ending"), "ending");
+    }
+
+    #[test]
+    fn text_is_escaped() {
+        assert_eq!(text("plain"), "plain");
+        assert_eq!(text("say \"hi\""), "say #quot;hi#quot;");
+        assert_eq!(text("two\nlines"), "two lines");
+    }
+
+    #[test]
+    fn clashing_identifiers_get_suffixes() {
+        let mut graph = Graph::default();
+        for label in ["a b", "a_b", "a-b"] {
+            graph.add_node(Role::Distance, label.to_string());
+        }
+        let chart = to_mermaid(&graph, &[], Direction::TopDown);
+        assert_eq!(
+            chart,
+            "graph TD\n    a_b[\"a b\"]\n    a_b_2[\"a_b\"]\n    a_b_3[\"a-b\"]\n"
+        );
+    }
+}
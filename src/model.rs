@@ -0,0 +1,288 @@
+// Plain graph independent of the editor widget. Imports produce it and exports read it,
+// the app keeps its snarl and converts with `from_snarl` and `to_snarl`.
+
+use std::collections::{HashMap, HashSet};
+
+use egui::{Color32, Pos2};
+use egui_snarl::{InPinId, NodeId, OutPinId, Snarl};
+
+use crate::{Cost, DijkstraNode, NodeKind, NodeMeta, can_connect, layered_layout};
+
+// Node kind without the costs, those are kept on the edges
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Start,
+    Distance,
+    Finish,
+}
+
+impl Role {
+    pub fn of(kind: &NodeKind) -> Self {
+        match kind {
+            NodeKind::Start => Role::Start,
+            NodeKind::Distance(_) => Role::Distance,
+            NodeKind::Finish(_) => Role::Finish,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::Start => "Start",
+            Role::Distance => "Distance",
+            Role::Finish => "Finish",
+        }
+    }
+
+    pub(crate) fn kind(self) -> NodeKind {
+        match self {
+            Role::Start => NodeKind::Start,
+            Role::Distance => NodeKind::Distance(HashMap::new()),
+            Role::Finish => NodeKind::Finish(HashMap::new()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+    // Snarl id when taken from the editor, the position in `nodes` for imports
+    pub id: NodeId,
+    pub role: Role,
+    // Empty for unlabeled nodes
+    pub label: String,
+    // Imports may leave it out, the nodes are then laid out along their connections
+    pub pos: Option<Pos2>,
+    pub color: Option<Color32>,
+    pub note: String,
+    pub locked: bool,
+}
+
+impl Node {
+    // Label if it has one, otherwise the node type
+    pub fn title(&self) -> &str {
+        if self.label.is_empty() {
+            self.role.name()
+        } else {
+            &self.label
+        }
+    }
+
+    // Name used in exports, falling back to the type and id for unlabeled nodes
+    pub fn display_label(&self) -> String {
+        if self.label.is_empty() {
+            format!("{} {}", self.role.name(), self.id.0)
+        } else {
+            self.label.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Edge {
+    pub from: NodeId,
+    pub to: NodeId,
+    // None until the editor computed one from the wire length
+    pub cost: Option<Cost>,
+    // Set by hand or given in an imported file, kept instead of the wire length
+    pub manual: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Graph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+// Snarl built by `to_snarl` with what had to be dropped to make it valid
+pub struct Conversion {
+    pub snarl: Snarl<DijkstraNode>,
+    // Snarl id of each graph node, in the graph's order
+    pub ids: Vec<NodeId>,
+    // Start/Finish nodes after the first one, turned into Distance nodes
+    pub demoted: usize,
+    // Self-loops, duplicates and connections the node roles don't allow
+    pub skipped: usize,
+}
+
+impl Graph {
+    // Adds a node with its index as the id, returns the index
+    pub fn add_node(&mut self, role: Role, label: String) -> usize {
+        let idx = self.nodes.len();
+        self.nodes.push(Node {
+            id: NodeId(idx),
+            role,
+            label,
+            pos: None,
+            color: None,
+            note: String::new(),
+            locked: false,
+        });
+        idx
+    }
+
+    // Connects two nodes added with `add_node`, a given cost counts as a manual one
+    pub fn add_edge(&mut self, from: usize, to: usize, cost: Option<Cost>) {
+        self.edges.push(Edge {
+            from: NodeId(from),
+            to: NodeId(to),
+            cost,
+            manual: cost.is_some(),
+        });
+    }
+
+    pub fn node(&self, id: NodeId) -> Option<&Node> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    // Nodes and edges are sorted by id, so the same snarl always gives the same graph
+    pub fn from_snarl(snarl: &Snarl<DijkstraNode>) -> Self {
+        let mut nodes: Vec<Node> = snarl
+            .nodes_pos_ids()
+            .map(|(id, pos, node)| Node {
+                id,
+                role: Role::of(&node.kind),
+                label: node.meta.label.clone(),
+                pos: Some(pos),
+                color: node.meta.color,
+                note: node.meta.note.clone(),
+                locked: node.meta.locked,
+            })
+            .collect();
+        nodes.sort_by_key(|node| node.id.0);
+
+        let mut edges: Vec<Edge> = snarl
+            .wires()
+            .map(|(out_pin, in_pin)| {
+                let (from, to) = (out_pin.node, in_pin.node);
+                let cost = match &snarl[to].kind {
+                    NodeKind::Distance(costs) | NodeKind::Finish(costs) => {
+                        costs.get(&from).copied()
+                    }
+                    NodeKind::Start => None,
+                };
+                Edge {
+                    from,
+                    to,
+                    cost,
+                    manual: snarl[to].meta.overrides.contains_key(&from),
+                }
+            })
+            .collect();
+        edges.sort_by_key(|edge| (edge.from.0, edge.to.0));
+
+        Self { nodes, edges }
+    }
+
+    // Build a snarl the editor accepts: one Start and one Finish, connections only where
+    // the roles allow them, and a layout when any node has no position
+    pub fn to_snarl(&self) -> Conversion {
+        let mut snarl = Snarl::new();
+        let (ids, demoted, skipped) = self.insert_into(&mut snarl);
+        Conversion {
+            snarl,
+            ids,
+            demoted,
+            skipped,
+        }
+    }
+
+    // Add the nodes to an existing snarl, returns their new ids and how many were demoted
+    // and skipped. Only the graph's own endpoints are counted, the snarl shouldn't have any.
+    pub fn insert_into(&self, snarl: &mut Snarl<DijkstraNode>) -> (Vec<NodeId>, usize, usize) {
+        let (mut has_start, mut has_finish) = (false, false);
+        let mut demoted = 0;
+        let roles: Vec<Role> = self
+            .nodes
+            .iter()
+            .map(|node| match node.role {
+                Role::Start if has_start => {
+                    demoted += 1;
+                    Role::Distance
+                }
+                Role::Finish if has_finish => {
+                    demoted += 1;
+                    Role::Distance
+                }
+                Role::Start => {
+                    has_start = true;
+                    Role::Start
+                }
+                Role::Finish => {
+                    has_finish = true;
+                    Role::Finish
+                }
+                Role::Distance => Role::Distance,
+            })
+            .collect();
+
+        let index: HashMap<NodeId, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| (node.id, idx))
+            .collect();
+        let mut seen = HashSet::new();
+        let edges: Vec<(usize, usize, &Edge)> = self
+            .edges
+            .iter()
+            .filter_map(|edge| Some((*index.get(&edge.from)?, *index.get(&edge.to)?, edge)))
+            .filter(|(from, to, _)| {
+                from != to
+                    && can_connect(&roles[*from].kind(), &roles[*to].kind())
+                    && seen.insert((*from, *to))
+            })
+            .collect();
+        let skipped = self.edges.len() - edges.len();
+
+        let layout = self.nodes.iter().any(|node| node.pos.is_none()).then(|| {
+            let pairs: Vec<(usize, usize)> =
+                edges.iter().map(|(from, to, _)| (*from, *to)).collect();
+            layered_layout(roles.len(), &pairs)
+        });
+
+        let ids: Vec<NodeId> = self
+            .nodes
+            .iter()
+            .zip(&roles)
+            .enumerate()
+            .map(|(idx, (node, role))| {
+                let pos = match &layout {
+                    Some(layout) => layout[idx],
+                    None => node.pos.unwrap_or_default(),
+                };
+                let value = DijkstraNode {
+                    kind: role.kind(),
+                    meta: NodeMeta {
+                        label: node.label.clone(),
+                        color: node.color,
+                        note: node.note.clone(),
+                        locked: node.locked,
+                        overrides: HashMap::new(),
+                        mirrored: HashSet::new(),
+                    },
+                };
+                snarl.insert_node(pos, value)
+            })
+            .collect();
+        for (from, to, edge) in &edges {
+            let (from, to) = (ids[*from], ids[*to]);
+            snarl.connect(
+                OutPinId {
+                    node: from,
+                    output: 0,
+                },
+                InPinId { node: to, input: 0 },
+            );
+            let Some(cost) = edge.cost else {
+                continue;
+            };
+            if edge.manual {
+                snarl[to].meta.overrides.insert(from, cost);
+            }
+            if let NodeKind::Distance(costs) | NodeKind::Finish(costs) = &mut snarl[to].kind {
+                costs.insert(from, cost);
+            }
+        }
+
+        (ids, demoted, skipped)
+    }
+}
@@ -0,0 +1,320 @@
+// Toasts with repeats folded together: a message that is already shown has its time
+// refreshed and a "×N" counter instead of stacking another copy
+
+use std::time::Duration;
+
+use egui_notify::{Anchor, Toast, Toasts};
+use web_time::Instant;
+
+use crate::i18n::Text;
+
+// Older toasts are dismissed to make room beyond this
+const MAX_SHOWN: usize = 5;
+// egui_notify can't change a shown toast, so they are all recreated for new counters, at
+// most this often while a message keeps repeating
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Level {
+    Error,
+    Warning,
+    Info,
+    Success,
+}
+
+#[derive(Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Corner {
+    pub const ALL: [Corner; 4] = [
+        Corner::TopLeft,
+        Corner::TopRight,
+        Corner::BottomLeft,
+        Corner::BottomRight,
+    ];
+
+    pub fn text(self) -> Text {
+        match self {
+            Corner::TopLeft => Text::TopLeft,
+            Corner::TopRight => Text::TopRight,
+            Corner::BottomLeft => Text::BottomLeft,
+            Corner::BottomRight => Text::BottomRight,
+        }
+    }
+
+    fn anchor(self) -> Anchor {
+        match self {
+            Corner::TopLeft => Anchor::TopLeft,
+            Corner::TopRight => Anchor::TopRight,
+            Corner::BottomLeft => Anchor::BottomLeft,
+            Corner::BottomRight => Anchor::BottomRight,
+        }
+    }
+}
+
+// Kept across sessions, changes apply to the toasts shown after them
+#[derive(Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    // In seconds
+    pub duration: u64,
+    // Errors stay until they are closed, so they aren't missed while busy editing
+    pub sticky_errors: bool,
+    pub corner: Corner,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            duration: 5,
+            sticky_errors: false,
+            corner: Corner::TopRight,
+        }
+    }
+}
+
+struct Shown {
+    level: Level,
+    text: String,
+    count: usize,
+    // None until closed by hand
+    expires: Option<Instant>,
+}
+
+impl Shown {
+    fn alive(&self, now: Instant) -> bool {
+        self.expires.is_none_or(|expires| expires > now)
+    }
+
+    fn toast(&self, now: Instant, closable: bool) -> Toast {
+        let caption = if self.count > 1 {
+            format!("{} ×{}", self.text, self.count)
+        } else {
+            self.text.clone()
+        };
+        let mut toast = match self.level {
+            Level::Error => Toast::error(caption),
+            Level::Warning => Toast::warning(caption),
+            Level::Info => Toast::info(caption),
+            Level::Success => Toast::success(caption),
+        };
+        toast
+            .duration(
+                self.expires
+                    .map(|expires| expires.saturating_duration_since(now)),
+            )
+            .closable(closable);
+        toast
+    }
+}
+
+pub struct Notifications {
+    pub settings: Settings,
+    toasts: Toasts,
+    // Off while presenting, the close buttons would show on the projector
+    closable: bool,
+    // Where the current toasts were anchored
+    corner: Option<Corner>,
+    shown: Vec<Shown>,
+    // A counter changed since the toasts were last recreated
+    stale: bool,
+    last_refresh: Option<Instant>,
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Self {
+            settings: Settings::default(),
+            toasts: Toasts::default(),
+            closable: true,
+            corner: None,
+            shown: Vec::new(),
+            stale: false,
+            last_refresh: None,
+        }
+    }
+}
+
+impl Notifications {
+    pub fn set_closable(&mut self, closable: bool) {
+        if self.closable != closable {
+            self.closable = closable;
+            // Shown toasts are recreated with the change on the next `show`
+            self.corner = None;
+        }
+    }
+
+    pub fn notify(&mut self, level: Level, text: &str) {
+        self.notify_at(level, text, Instant::now());
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        self.show_at(ctx, Instant::now());
+    }
+
+    // The time is passed in so tests don't have to wait for it
+    fn notify_at(&mut self, level: Level, text: &str, now: Instant) {
+        let expires = (level != Level::Error || !self.settings.sticky_errors)
+            .then(|| now + Duration::from_secs(self.settings.duration));
+        self.shown.retain(|shown| shown.alive(now));
+        if let Some(shown) = self
+            .shown
+            .iter_mut()
+            .find(|shown| shown.level == level && shown.text == text)
+        {
+            shown.count += 1;
+            shown.expires = expires;
+            self.stale = true;
+            return;
+        }
+
+        if self.shown.len() == MAX_SHOWN {
+            self.shown.remove(0);
+            self.toasts.dismiss_oldest_toast();
+        }
+        let shown = Shown {
+            level,
+            text: text.to_string(),
+            count: 1,
+            expires,
+        };
+        self.toasts.add(shown.toast(now, self.closable));
+        self.shown.push(shown);
+    }
+
+    fn show_at(&mut self, ctx: &egui::Context, now: Instant) {
+        if self.corner != Some(self.settings.corner) {
+            self.recreate(now);
+        } else if self.stale {
+            let next = self
+                .last_refresh
+                .map_or(now, |last| last + REFRESH_INTERVAL);
+            if next <= now {
+                self.recreate(now);
+            } else {
+                ctx.request_repaint_after(next - now);
+            }
+        }
+        self.toasts.show(ctx);
+        // egui_notify doesn't tell which toast was closed by hand. They usually go oldest
+        // first, forgetting those keeps closed ones from coming back when toasts are recreated.
+        let closed = self.shown.len().saturating_sub(self.toasts.len());
+        self.shown.drain(..closed);
+    }
+
+    fn recreate(&mut self, now: Instant) {
+        self.shown.retain(|shown| shown.alive(now));
+        self.toasts = Toasts::default().with_anchor(self.settings.corner.anchor());
+        for shown in &self.shown {
+            self.toasts.add(shown.toast(now, self.closable));
+        }
+        self.corner = Some(self.settings.corner);
+        self.stale = false;
+        self.last_refresh = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(notifications: &Notifications) -> Vec<(&str, usize)> {
+        notifications
+            .shown
+            .iter()
+            .map(|shown| (shown.text.as_str(), shown.count))
+            .collect()
+    }
+
+    fn show(notifications: &mut Notifications, now: Instant) {
+        let ctx = egui::Context::default();
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            notifications.show_at(ctx, now)
+        });
+    }
+
+    #[test]
+    fn repeats_are_counted() {
+        let (mut notifications, now) = (Notifications::default(), Instant::now());
+        notifications.notify_at(Level::Error, "No path", now);
+        notifications.notify_at(Level::Error, "No path", now);
+        // Same text at another level is a different message
+        notifications.notify_at(Level::Info, "No path", now);
+        notifications.notify_at(Level::Error, "No path", now);
+        assert_eq!(counts(&notifications), [("No path", 3), ("No path", 1)]);
+        assert_eq!(notifications.toasts.len(), 2);
+    }
+
+    #[test]
+    fn repeats_refresh_the_duration() {
+        let (mut notifications, now) = (Notifications::default(), Instant::now());
+        notifications.notify_at(Level::Error, "No path", now);
+        let later = now + Duration::from_secs(4);
+        notifications.notify_at(Level::Error, "No path", later);
+        assert_eq!(
+            notifications.shown[0].expires,
+            Some(later + Duration::from_secs(5))
+        );
+
+        // Once it has expired the message starts over
+        let expired = later + Duration::from_secs(6);
+        notifications.notify_at(Level::Error, "No path", expired);
+        assert_eq!(counts(&notifications), [("No path", 1)]);
+    }
+
+    #[test]
+    fn sticky_errors_dont_expire() {
+        let mut notifications = Notifications::default();
+        notifications.settings.sticky_errors = true;
+        let now = Instant::now();
+        notifications.notify_at(Level::Error, "No path", now);
+        notifications.notify_at(Level::Warning, "Unreachable", now);
+        assert_eq!(notifications.shown[0].expires, None);
+        let much_later = now + Duration::from_secs(3600);
+        notifications.notify_at(Level::Error, "No path", much_later);
+        assert_eq!(counts(&notifications), [("No path", 2)]);
+    }
+
+    #[test]
+    fn oldest_toasts_make_room() {
+        let (mut notifications, now) = (Notifications::default(), Instant::now());
+        let texts: Vec<String> = (0..MAX_SHOWN + 2)
+            .map(|i| format!("Message {}", i))
+            .collect();
+        for text in &texts {
+            notifications.notify_at(Level::Info, text, now);
+        }
+        let shown: Vec<&str> = counts(&notifications)
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect();
+        // The dismissed toasts fade out on their own
+        assert_eq!(shown, texts[2..]);
+    }
+
+    #[test]
+    fn counters_are_redrawn_at_most_every_interval() {
+        let (mut notifications, now) = (Notifications::default(), Instant::now());
+        notifications.notify_at(Level::Error, "No path", now);
+        show(&mut notifications, now);
+        assert_eq!(notifications.last_refresh, Some(now));
+
+        notifications.notify_at(Level::Error, "No path", now + Duration::from_millis(100));
+        assert!(notifications.stale);
+        let soon = now + REFRESH_INTERVAL / 2;
+        show(&mut notifications, soon);
+        assert!(notifications.stale);
+        assert_eq!(notifications.last_refresh, Some(now));
+
+        let later = now + REFRESH_INTERVAL;
+        show(&mut notifications, later);
+        assert!(!notifications.stale);
+        assert_eq!(notifications.last_refresh, Some(later));
+        assert_eq!(counts(&notifications), [("No path", 2)]);
+    }
+}
@@ -0,0 +1,128 @@
+// Colors the graph is drawn with, one set per theme. Wires take the color of their pins,
+// the background grid follows the egui visuals.
+
+use egui::Color32;
+
+use crate::i18n::Text;
+
+// Color sets to pick from in the appearance, the others avoid the hues some kinds of color
+// blindness confuse
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Preset {
+    Default,
+    // Red-green, blue and orange from the Okabe-Ito set
+    Deuteranopia,
+    // Blue-yellow, red against teal
+    Tritanopia,
+    HighContrast,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 4] = [
+        Preset::Default,
+        Preset::Deuteranopia,
+        Preset::Tritanopia,
+        Preset::HighContrast,
+    ];
+
+    pub fn text(self) -> Text {
+        match self {
+            Preset::Default => Text::PaletteDefault,
+            Preset::Deuteranopia => Text::PaletteDeuteranopia,
+            Preset::Tritanopia => Text::PaletteTritanopia,
+            Preset::HighContrast => Text::PaletteHighContrast,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Palette {
+    // Pins of Distance nodes without a custom color
+    pub node: Color32,
+    // Pins and weights of the shortest path
+    pub path: Color32,
+    // Endpoint headers and pins unless the graph settings pick their own
+    pub start: Color32,
+    pub finish: Color32,
+    // Outlines of the hovered node's neighbors, where its connections come from and go to
+    pub predecessor: Color32,
+    pub successor: Color32,
+    // Blinking outline of a node picked in the Find window
+    pub flash: Color32,
+}
+
+const DARK: Palette = Palette {
+    node: Color32::BLUE,
+    path: Color32::RED,
+    start: Color32::from_rgb(40, 120, 50),
+    finish: Color32::from_rgb(160, 90, 20),
+    predecessor: Color32::from_rgb(70, 170, 220),
+    successor: Color32::from_rgb(190, 110, 230),
+    flash: Color32::from_rgb(255, 220, 60),
+};
+
+// Darker pins that hold up against the light background, the endpoints are lighter so their
+// titles stay readable
+const LIGHT: Palette = Palette {
+    node: Color32::from_rgb(40, 90, 200),
+    path: Color32::from_rgb(210, 30, 30),
+    start: Color32::from_rgb(150, 215, 150),
+    finish: Color32::from_rgb(250, 190, 110),
+    predecessor: Color32::from_rgb(30, 120, 190),
+    successor: Color32::from_rgb(150, 60, 200),
+    flash: Color32::from_rgb(230, 150, 0),
+};
+
+// The same on both themes, the colors are mid-toned enough for either background
+const DEUTERANOPIA: Palette = Palette {
+    node: Color32::from_rgb(0, 114, 178),
+    path: Color32::from_rgb(230, 159, 0),
+    start: Color32::from_rgb(0, 158, 115),
+    finish: Color32::from_rgb(213, 94, 0),
+    predecessor: Color32::from_rgb(86, 180, 233),
+    successor: Color32::from_rgb(204, 121, 167),
+    flash: Color32::from_rgb(240, 228, 66),
+};
+
+const TRITANOPIA: Palette = Palette {
+    node: Color32::from_rgb(0, 140, 150),
+    path: Color32::from_rgb(220, 30, 60),
+    start: Color32::from_rgb(60, 150, 130),
+    finish: Color32::from_rgb(200, 90, 140),
+    predecessor: Color32::from_rgb(90, 200, 210),
+    successor: Color32::from_rgb(240, 120, 110),
+    flash: Color32::from_rgb(255, 80, 170),
+};
+
+const HIGH_CONTRAST_DARK: Palette = Palette {
+    node: Color32::WHITE,
+    path: Color32::from_rgb(255, 230, 0),
+    start: Color32::from_rgb(0, 170, 0),
+    finish: Color32::from_rgb(220, 70, 0),
+    predecessor: Color32::from_rgb(0, 255, 255),
+    successor: Color32::from_rgb(255, 0, 255),
+    flash: Color32::from_rgb(255, 120, 0),
+};
+
+const HIGH_CONTRAST_LIGHT: Palette = Palette {
+    node: Color32::BLACK,
+    path: Color32::from_rgb(200, 0, 0),
+    start: Color32::from_rgb(120, 230, 120),
+    finish: Color32::from_rgb(255, 170, 60),
+    predecessor: Color32::from_rgb(0, 70, 200),
+    successor: Color32::from_rgb(160, 0, 160),
+    flash: Color32::from_rgb(255, 120, 0),
+};
+
+impl Palette {
+    pub fn new(preset: Preset, dark_mode: bool) -> Self {
+        match (preset, dark_mode) {
+            (Preset::Default, true) => DARK,
+            (Preset::Default, false) => LIGHT,
+            (Preset::Deuteranopia, _) => DEUTERANOPIA,
+            (Preset::Tritanopia, _) => TRITANOPIA,
+            (Preset::HighContrast, true) => HIGH_CONTRAST_DARK,
+            (Preset::HighContrast, false) => HIGH_CONTRAST_LIGHT,
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// Export of a finished run: the path with per-edge and cumulative costs, and all distances
+
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use egui_snarl::NodeId;
+use serde_json::json;
+
+use crate::RunResult;
+use crate::algorithm::{Cost, format_cost};
+use crate::model::Graph;
+use crate::save::CostNumber;
+
+// Format as an ISO 8601 UTC timestamp
+pub fn format_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let (days, rem) = (secs / 86400, secs % 86400);
+
+    // Civil date from days since the epoch, see https://howardhinnant.github.io/date_algorithms.html
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+// Nodes may have been removed since the run
+fn label(graph: &Graph, node: NodeId) -> String {
+    graph
+        .node(node)
+        .map_or(format!("Removed {}", node.0), |value| value.display_label())
+}
+
+// Rows of the path: node, cost of the edge leading to it and the cost so far
+fn path_steps(result: &RunResult) -> Vec<(NodeId, Cost, Cost)> {
+    let mut previous = 0.0;
+    result
+        .path
+        .iter()
+        .map(|node| {
+            let cumulative = result.distances.get(node).copied().unwrap_or_default();
+            let step = (*node, cumulative - previous, cumulative);
+            previous = cumulative;
+            step
+        })
+        .collect()
+}
+
+fn sorted_distances(result: &RunResult) -> Vec<(NodeId, Option<Cost>)> {
+    let mut distances: Vec<(NodeId, Option<Cost>)> = result
+        .distances
+        .iter()
+        .map(|(node, dist)| (*node, dist.is_finite().then_some(*dist)))
+        .collect();
+    distances.sort_by_key(|(node, _)| node.0);
+    distances
+}
+
+pub fn to_json(graph: &Graph, result: &RunResult) -> String {
+    let path: Vec<_> = path_steps(result)
+        .into_iter()
+        .map(|(node, edge_cost, cumulative)| {
+            json!({
+                "id": node.0,
+                "label": label(graph, node),
+                "edge_cost": CostNumber(edge_cost),
+                "cumulative_cost": CostNumber(cumulative),
+            })
+        })
+        .collect();
+    let distances: Vec<_> = sorted_distances(result)
+        .into_iter()
+        .map(|(node, dist)| {
+            json!({
+                "id": node.0,
+                "label": label(graph, node),
+                "distance": dist.map(CostNumber),
+            })
+        })
+        .collect();
+    let report = json!({
+        "algorithm": result.algorithm,
+        "timestamp": format_timestamp(result.finished_at),
+        "total_cost": CostNumber(result.total),
+        "path": path,
+        "distances": distances,
+    });
+    serde_json::to_string_pretty(&report).unwrap_or_default()
+}
+
+fn csv_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+// Two tables separated by an empty line, with the run details as comment lines on top
+pub fn to_csv(graph: &Graph, result: &RunResult) -> String {
+    let mut csv = format!(
+        "# algorithm: {}\n# timestamp: {}\n# total_cost: {}\nstep,id,label,edge_cost,cumulative_cost\n",
+        result.algorithm,
+        format_timestamp(result.finished_at),
+        format_cost(result.total)
+    );
+    for (step, (node, edge_cost, cumulative)) in path_steps(result).into_iter().enumerate() {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            step,
+            node.0,
+            csv_cell(&label(graph, node)),
+            format_cost(edge_cost),
+            format_cost(cumulative)
+        ));
+    }
+    csv.push_str("\nid,label,distance\n");
+    for (node, dist) in sorted_distances(result) {
+        let dist = dist.map_or(String::new(), format_cost);
+        csv.push_str(&format!(
+            "{},{},{}\n",
+            node.0,
+            csv_cell(&label(graph, node)),
+            dist
+        ));
+    }
+    csv
+}
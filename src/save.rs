@@ -0,0 +1,387 @@
+// Save file format: the snarl together with the computed path and graph settings
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+use egui_snarl::{NodeId, Snarl};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Serialize, Serializer};
+
+use crate::DijkstraNode;
+use crate::algorithm::Cost;
+use crate::i18n::{Text, trf};
+use crate::settings::GraphSettings;
+
+// Version written by this build. Version 0 is the bare snarl saved before the wrapper existed.
+pub const SAVE_VERSION: u32 = 2;
+
+// Upgrades of the raw JSON, entry `n` turns version `n` into version `n + 1`
+const MIGRATIONS: [fn(serde_json::Value) -> serde_json::Value; SAVE_VERSION as usize] =
+    [wrap_snarl, settings_block];
+
+fn wrap_snarl(snarl: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "version": 1,
+        "snarl": snarl,
+    })
+}
+
+// Version 1 kept auto_recalc at the top level, the other settings were fixed then and
+// match their defaults
+fn settings_block(mut file: serde_json::Value) -> serde_json::Value {
+    if let Some(file) = file.as_object_mut() {
+        if let Some(auto_recalc) = file.remove("auto_recalc") {
+            file.insert(
+                "settings".to_string(),
+                serde_json::json!({ "auto_recalc": auto_recalc }),
+            );
+        }
+        file.insert("version".to_string(), serde_json::json!(2));
+    }
+    file
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub snarl: Snarl<DijkstraNode>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path_nodes: Vec<NodeId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_cost: Option<CostNumber>,
+    // Missing in files saved before settings were part of the format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<GraphSettings>,
+}
+
+// Whole costs are written as integers, so graphs without fractional costs are saved and
+// exported the same as before costs could have them. Both forms are read back as floats.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize)]
+#[serde(transparent)]
+pub struct CostNumber(pub Cost);
+
+impl Serialize for CostNumber {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Beyond 2^53 not every integer is a float anyway
+        if self.0.fract() == 0.0 && self.0.abs() < 9_007_199_254_740_992.0 {
+            serializer.serialize_i64(self.0 as i64)
+        } else {
+            serializer.serialize_f64(self.0)
+        }
+    }
+}
+
+pub fn write_costs<S: Serializer>(
+    costs: &HashMap<NodeId, Cost>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(costs.iter().map(|(node, cost)| (node, CostNumber(*cost))))
+}
+
+fn version(value: &serde_json::Value) -> Result<u32, String> {
+    match value.get("version") {
+        None => Ok(0),
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| format!("invalid version {}", version)),
+    }
+}
+
+// Read a save file of any version, migrating it to the current one
+pub fn load(text: &str) -> Result<SaveFile, String> {
+    let mut value: serde_json::Value = serde_json::from_str(text).map_err(|err| err.to_string())?;
+    let version = version(&value)?;
+    if version > SAVE_VERSION {
+        return Err(format!(
+            "the file was saved by a newer version of the app (format {}, this build reads up to {})",
+            version, SAVE_VERSION
+        ));
+    }
+    for migrate in &MIGRATIONS[version as usize..] {
+        value = migrate(value);
+    }
+    serde_json::from_value(value).map_err(|err| format!("format {}: {}", version, err))
+}
+
+// Compact and gzip compressed or pretty-printed JSON
+pub fn encode(file: &SaveFile, compressed: bool) -> Result<Vec<u8>, String> {
+    let serialized = if compressed {
+        serde_json::to_vec(file).map(|json| {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&json).and_then(|()| encoder.finish())
+        })
+    } else {
+        serde_json::to_vec_pretty(file).map(Ok)
+    };
+    serialized
+        .map_err(|err| trf(Text::FailedToSerialize, &[&err]))?
+        .map_err(|err| trf(Text::FailedToCompress, &[&err]))
+}
+
+// Decompress saves starting with the gzip magic bytes
+pub fn decode(bytes: Vec<u8>) -> std::io::Result<String> {
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut text = String::new();
+        GzDecoder::new(&bytes[..]).read_to_string(&mut text)?;
+        Ok(text)
+    } else {
+        String::from_utf8(bytes)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::Pos2;
+    use egui_snarl::{InPinId, OutPinId};
+
+    use super::*;
+    use crate::settings::Rounding;
+    use crate::{CostScale, NodeKind};
+
+    const V0: &str = include_str!("../tests/fixtures/save/v0.json");
+    const V1: &str = include_str!("../tests/fixtures/save/v1.json");
+    const V2: &str = include_str!("../tests/fixtures/save/v2.json");
+    const EVERY_KIND: &str = include_str!("../tests/fixtures/save/every_kind.json");
+
+    // Nodes with their positions and the wires between them, sorted
+    type Contents = (Vec<(NodeId, Pos2, DijkstraNode)>, Vec<(NodeId, NodeId)>);
+
+    // Snarl has no `PartialEq`, its nodes and wires are compared instead
+    fn contents(snarl: &Snarl<DijkstraNode>) -> Contents {
+        let mut nodes: Vec<_> = snarl
+            .nodes_pos_ids()
+            .map(|(id, pos, node)| (id, pos, node.clone()))
+            .collect();
+        nodes.sort_by_key(|(id, _, _)| *id);
+        let mut wires: Vec<_> = snarl
+            .wires()
+            .map(|(out_pin, in_pin)| (out_pin.node, in_pin.node))
+            .collect();
+        wires.sort();
+        (nodes, wires)
+    }
+
+    // The graph all fixtures hold: Start, a Distance node and Finish in a row
+    fn expected_nodes(label: &str) -> Vec<(NodeId, Pos2, DijkstraNode)> {
+        let mut middle = DijkstraNode::new(NodeKind::Distance(HashMap::from([(NodeId(0), 12.0)])));
+        middle.meta.label = label.to_string();
+        vec![
+            (
+                NodeId(0),
+                Pos2::new(0.0, 0.0),
+                DijkstraNode::new(NodeKind::Start),
+            ),
+            (NodeId(1), Pos2::new(200.0, 40.0), middle),
+            (
+                NodeId(2),
+                Pos2::new(400.0, 0.0),
+                DijkstraNode::new(NodeKind::Finish(HashMap::from([(NodeId(1), 7.0)]))),
+            ),
+        ]
+    }
+
+    fn expected_wires() -> Vec<(NodeId, NodeId)> {
+        vec![(NodeId(0), NodeId(1)), (NodeId(1), NodeId(2))]
+    }
+
+    #[test]
+    fn loads_a_bare_snarl() {
+        let file = load(V0).unwrap();
+        assert_eq!(file.version, SAVE_VERSION);
+        assert_eq!(
+            contents(&file.snarl),
+            (expected_nodes(""), expected_wires())
+        );
+        assert!(file.path_nodes.is_empty());
+        assert_eq!(file.total_cost, None);
+        assert_eq!(file.settings, None);
+    }
+
+    #[test]
+    fn moves_auto_recalc_into_the_settings() {
+        let file = load(V1).unwrap();
+        assert_eq!(file.version, SAVE_VERSION);
+        assert_eq!(
+            contents(&file.snarl),
+            (expected_nodes("Middle"), expected_wires())
+        );
+        assert_eq!(file.path_nodes, [NodeId(0), NodeId(1), NodeId(2)]);
+        assert_eq!(file.total_cost, Some(CostNumber(19.0)));
+        assert_eq!(
+            file.settings,
+            Some(GraphSettings {
+                auto_recalc: true,
+                ..GraphSettings::default()
+            })
+        );
+    }
+
+    #[test]
+    fn loads_the_current_version() {
+        let file = load(V2).unwrap();
+        assert_eq!(file.version, SAVE_VERSION);
+        assert_eq!(
+            contents(&file.snarl),
+            (expected_nodes("Middle"), expected_wires())
+        );
+        assert_eq!(file.path_nodes, [NodeId(0), NodeId(1), NodeId(2)]);
+        assert_eq!(file.total_cost, Some(CostNumber(19.0)));
+        assert_eq!(
+            file.settings,
+            Some(GraphSettings {
+                auto_recalc: true,
+                wire_style: egui_snarl::ui::WireStyle::Bezier3,
+                start_color: None,
+                finish_color: None,
+                cost_scale: CostScale {
+                    pixels_per_cost: 20.0,
+                    min_cost: 2,
+                    rounding: Rounding::Round,
+                    fractional: false,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn compressed_saves_load_the_same_graph() {
+        let file = load(V2).unwrap();
+        let bytes = encode(&file, true).unwrap();
+        assert!(bytes.starts_with(&[0x1f, 0x8b]));
+        assert!(bytes.len() < encode(&file, false).unwrap().len());
+
+        let loaded = load(&decode(bytes).unwrap()).unwrap();
+        assert_eq!(contents(&loaded.snarl), contents(&file.snarl));
+        assert_eq!(loaded.path_nodes, file.path_nodes);
+        assert_eq!(loaded.total_cost, file.total_cost);
+        assert_eq!(loaded.settings, file.settings);
+    }
+
+    #[test]
+    fn truncated_compressed_saves_are_errors() {
+        let bytes = encode(&load(V2).unwrap(), true).unwrap();
+        assert!(decode(bytes[..bytes.len() / 2].to_vec()).is_err());
+    }
+
+    #[test]
+    fn refuses_newer_versions() {
+        let newer = V2.replacen(
+            "\"version\": 2",
+            &format!("\"version\": {}", SAVE_VERSION + 1),
+            1,
+        );
+        assert_eq!(
+            load(&newer).err().unwrap(),
+            format!(
+                "the file was saved by a newer version of the app (format {}, this build reads up to {})",
+                SAVE_VERSION + 1,
+                SAVE_VERSION
+            )
+        );
+    }
+
+    #[test]
+    fn broken_files_are_errors() {
+        assert!(load("").is_err());
+        assert!(load(&V2[..V2.len() / 2]).is_err());
+        assert!(load("not json").is_err());
+        // Read as a bare snarl, which it isn't either
+        assert!(load("[1, 2, 3]").err().unwrap().starts_with("format 0: "));
+        assert!(load("42").err().unwrap().starts_with("format 0: "));
+    }
+
+    #[test]
+    fn mistyped_fields_are_errors() {
+        let mistyped = V2.replacen("\"path_nodes\": [0, 1, 2]", "\"path_nodes\": \"all\"", 1);
+        assert_ne!(mistyped, V2);
+        assert!(load(&mistyped).err().unwrap().starts_with("format 2: "));
+        let mistyped = V2.replacen("\"pixels_per_cost\": 20.0", "\"pixels_per_cost\": true", 1);
+        assert_ne!(mistyped, V2);
+        assert!(load(&mistyped).err().unwrap().starts_with("format 2: "));
+    }
+
+    #[test]
+    fn invalid_versions_are_errors() {
+        for version in ["\"two\"", "-1", "1.5", "4294967296"] {
+            let invalid = V2.replacen("\"version\": 2", &format!("\"version\": {}", version), 1);
+            assert_eq!(
+                load(&invalid).err().unwrap(),
+                format!("invalid version {}", version)
+            );
+        }
+    }
+
+    // Every kind of node with costs, a label and a manual cost, as the app would save it
+    fn every_kind() -> SaveFile {
+        let mut snarl = Snarl::new();
+        let start = snarl.insert_node(Pos2::new(0.0, 0.0), DijkstraNode::new(NodeKind::Start));
+        let mut upper = DijkstraNode::new(NodeKind::Distance(HashMap::from([(start, 12.0)])));
+        upper.meta.label = "Upper".to_string();
+        let upper = snarl.insert_node(Pos2::new(200.0, -60.0), upper);
+        let mut lower = DijkstraNode::new(NodeKind::Distance(HashMap::from([
+            (start, 15.0),
+            (upper, 5.0),
+        ])));
+        lower.meta.overrides.insert(upper, 5.0);
+        let lower = snarl.insert_node(Pos2::new(200.0, 80.0), lower);
+        let finish = snarl.insert_node(
+            Pos2::new(400.0, 0.0),
+            DijkstraNode::new(NodeKind::Finish(HashMap::from([
+                (upper, 7.0),
+                (lower, 9.0),
+            ]))),
+        );
+        for (from, to) in [
+            (start, upper),
+            (start, lower),
+            (upper, lower),
+            (upper, finish),
+            (lower, finish),
+        ] {
+            snarl.connect(
+                OutPinId {
+                    node: from,
+                    output: 0,
+                },
+                InPinId { node: to, input: 0 },
+            );
+        }
+        SaveFile {
+            version: SAVE_VERSION,
+            snarl,
+            path_nodes: vec![start, upper, finish],
+            total_cost: Some(CostNumber(19.0)),
+            settings: Some(GraphSettings::default()),
+        }
+    }
+
+    // The snarl keeps its wires in a set, they are written in any order
+    fn sorted_wires(mut file: serde_json::Value) -> serde_json::Value {
+        if let Some(wires) = file["snarl"]["wires"].as_array_mut() {
+            wires.sort_by_key(|wire| wire.to_string());
+        }
+        file
+    }
+
+    // A change of the format shows up as a change of the fixture
+    #[test]
+    fn writes_the_fixture() {
+        let written = serde_json::to_value(every_kind()).unwrap();
+        let fixture: serde_json::Value = serde_json::from_str(EVERY_KIND).unwrap();
+        assert_eq!(sorted_wires(written), sorted_wires(fixture));
+    }
+
+    #[test]
+    fn reads_the_fixture() {
+        let (file, expected) = (load(EVERY_KIND).unwrap(), every_kind());
+        assert_eq!(file.version, expected.version);
+        assert_eq!(contents(&file.snarl), contents(&expected.snarl));
+        assert_eq!(file.path_nodes, expected.path_nodes);
+        assert_eq!(file.total_cost, expected.total_cost);
+        assert_eq!(file.settings, expected.settings);
+    }
+}
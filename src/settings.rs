@@ -0,0 +1,177 @@
+// Settings that change how a graph is computed or drawn, saved with each graph, and the
+// user's preferred defaults for new graphs, kept in the config directory
+
+use std::path::PathBuf;
+
+use egui::Color32;
+use egui_snarl::ui::WireStyle;
+
+use crate::algorithm::Cost;
+use crate::i18n::{Text, tr, trf};
+use crate::palette::Palette;
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct GraphSettings {
+    pub auto_recalc: bool,
+    pub wire_style: WireStyle,
+    // Headers and pins of the endpoints, None follows the theme
+    pub start_color: Option<Color32>,
+    pub finish_color: Option<Color32>,
+    pub cost_scale: CostScale,
+}
+
+impl Default for GraphSettings {
+    fn default() -> Self {
+        Self {
+            auto_recalc: false,
+            wire_style: WireStyle::Bezier5,
+            start_color: None,
+            finish_color: None,
+            cost_scale: CostScale::default(),
+        }
+    }
+}
+
+// How the length of a wire becomes its cost, so the weights don't depend on how large the
+// graph happens to be drawn
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct CostScale {
+    pub pixels_per_cost: f32,
+    // Short wires still cost this much
+    pub min_cost: i32,
+    pub rounding: Rounding,
+    // Keep the fraction instead of rounding, so slightly different routes don't tie.
+    // Whole costs are simpler to follow by hand, so it's off unless chosen.
+    pub fractional: bool,
+}
+
+impl Default for CostScale {
+    fn default() -> Self {
+        Self {
+            pixels_per_cost: 10.0,
+            min_cost: 1,
+            rounding: Rounding::Floor,
+            fractional: false,
+        }
+    }
+}
+
+impl CostScale {
+    pub fn cost(&self, length: f32) -> Cost {
+        // Divided as a Cost, in f32 a fractional 12.1 would come out as 12.100000381
+        let units = Cost::from(length.round()) / Cost::from(self.pixels_per_cost);
+        let units = match self.rounding {
+            _ if self.fractional => units,
+            Rounding::Floor => units.floor(),
+            Rounding::Round => units.round(),
+            Rounding::Ceil => units.ceil(),
+        };
+        units.max(Cost::from(self.min_cost))
+    }
+}
+
+// How a length that isn't a whole number of cost units is turned into one
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Rounding {
+    Floor,
+    Round,
+    Ceil,
+}
+
+impl Rounding {
+    pub const ALL: [Rounding; 3] = [Rounding::Floor, Rounding::Round, Rounding::Ceil];
+
+    pub fn text(self) -> Text {
+        match self {
+            Rounding::Floor => Text::RoundDown,
+            Rounding::Round => Text::RoundNearest,
+            Rounding::Ceil => Text::RoundUp,
+        }
+    }
+}
+
+pub const WIRE_STYLES: [WireStyle; 4] = [
+    WireStyle::Line,
+    WireStyle::AxisAligned {
+        corner_radius: 10.0,
+    },
+    WireStyle::Bezier3,
+    WireStyle::Bezier5,
+];
+
+pub fn wire_style_name(style: WireStyle) -> &'static str {
+    match style {
+        WireStyle::Line => tr(Text::WireLine),
+        WireStyle::AxisAligned { .. } => tr(Text::WireAxisAligned),
+        WireStyle::Bezier3 => tr(Text::WireBezier3),
+        WireStyle::Bezier5 => tr(Text::WireBezier5),
+    }
+}
+
+impl GraphSettings {
+    // Readable list of what differs in `other`, for telling the user what a load changed
+    pub fn changes(&self, other: &GraphSettings) -> Vec<String> {
+        let mut changes = Vec::new();
+        if self.auto_recalc != other.auto_recalc {
+            let state = tr(if other.auto_recalc {
+                Text::On
+            } else {
+                Text::Off
+            });
+            changes.push(trf(Text::AutoRecalcChange, &[&state]));
+        }
+        if self.wire_style != other.wire_style {
+            let style = wire_style_name(other.wire_style).to_lowercase();
+            changes.push(trf(Text::WireStyleChange, &[&style]));
+        }
+        if self.start_color != other.start_color {
+            changes.push(tr(Text::StartColor).to_string());
+        }
+        if self.finish_color != other.finish_color {
+            changes.push(tr(Text::FinishColor).to_string());
+        }
+        if self.cost_scale != other.cost_scale {
+            changes.push(trf(
+                Text::CostScaleChange,
+                &[&other.cost_scale.pixels_per_cost],
+            ));
+        }
+        changes
+    }
+
+    pub fn start_color(&self, palette: &Palette) -> Color32 {
+        self.start_color.unwrap_or(palette.start)
+    }
+
+    pub fn finish_color(&self, palette: &Palette) -> Color32 {
+        self.finish_color.unwrap_or(palette.finish)
+    }
+}
+
+fn preferences_path() -> Option<PathBuf> {
+    Some(
+        dirs::config_dir()?
+            .join("dijkstra_visualization")
+            .join("preferences.json"),
+    )
+}
+
+// Defaults for new graphs, a missing file means the built-in defaults
+pub fn load_preferences() -> Result<GraphSettings, String> {
+    let Some(path) = preferences_path().filter(|path| path.exists()) else {
+        return Ok(GraphSettings::default());
+    };
+    let text = std::fs::read_to_string(&path).map_err(|err| err.to_string())?;
+    serde_json::from_str(&text).map_err(|err| err.to_string())
+}
+
+pub fn store_preferences(settings: &GraphSettings) -> Result<(), String> {
+    let path = preferences_path().ok_or("No config directory found".to_string())?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    }
+    let text = serde_json::to_string_pretty(settings).map_err(|err| err.to_string())?;
+    std::fs::write(&path, text).map_err(|err| err.to_string())
+}
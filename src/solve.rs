@@ -0,0 +1,119 @@
+// Headless `--solve FILE [--json]`: search a save file the way the app does and print the
+// path, e.g. for scripts checking submitted graphs. Nothing is drawn.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use egui::{Rect, Vec2};
+use egui_snarl::{NodeId, Snarl};
+
+use crate::algorithm::{self, PathError, format_cost};
+use crate::{
+    CostScale, DijkstraNode, NodeKind, RunResult, compute_incoming_costs, endpoints, model,
+    read_save, report, save, search_graph, wire_costs,
+};
+
+// Exit codes, 0 when a path was found
+pub const NO_PATH: u8 = 1;
+pub const INVALID_FILE: u8 = 2;
+pub const USAGE: u8 = 3;
+
+// Files saved by the app hold the costs shown in the editor. Connections without one, e.g.
+// in hand-written files, measure between the node positions as if the nodes were points.
+fn fill_costs(snarl: &mut Snarl<DijkstraNode>, scale: CostScale) {
+    let rects: HashMap<NodeId, Rect> = snarl
+        .nodes_pos_ids()
+        .map(|(id, pos, _)| (id, Rect::from_min_size(pos, Vec2::ZERO)))
+        .collect();
+    let mut incoming: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+    for (out_pin, in_pin) in snarl.wires() {
+        incoming.entry(in_pin.node).or_default().push(out_pin.node);
+    }
+    for (node, sources) in incoming {
+        let computed = compute_incoming_costs(node, &sources, &rects, snarl, scale);
+        let overrides = snarl[node].meta.overrides.clone();
+        if let NodeKind::Distance(costs) | NodeKind::Finish(costs) = &mut snarl[node].kind {
+            for (source, cost) in computed {
+                if overrides.contains_key(&source) {
+                    costs.insert(source, cost);
+                } else {
+                    costs.entry(source).or_insert(cost);
+                }
+            }
+        }
+    }
+}
+
+// The scale the file was saved with, so computed costs match the editor's
+fn load(path: &Path) -> Result<(Snarl<DijkstraNode>, CostScale), String> {
+    let text = read_save(path).map_err(|err| format!("Failed to read file: {}", err))?;
+    let file = save::load(&text).map_err(|err| format!("Failed to load file: {}", err))?;
+    let scale = file.settings.unwrap_or_default().cost_scale;
+    Ok((file.snarl, scale))
+}
+
+pub fn run(args: &[OsString]) -> u8 {
+    let json = args.iter().any(|arg| arg == "--json");
+    let files: Vec<&OsString> = args.iter().filter(|arg| *arg != "--json").collect();
+    let [file] = files.as_slice() else {
+        eprintln!("Usage: dijkstra --solve FILE [--json]");
+        return USAGE;
+    };
+    let path = PathBuf::from(file);
+
+    let (mut snarl, scale) = match load(&path) {
+        Ok(loaded) => loaded,
+        Err(err) => {
+            eprintln!("{}", err);
+            return INVALID_FILE;
+        }
+    };
+    fill_costs(&mut snarl, scale);
+    let (start, finish) = match endpoints(&snarl) {
+        Ok(endpoints) => endpoints,
+        Err(err) => {
+            eprintln!("{}", err);
+            return INVALID_FILE;
+        }
+    };
+
+    let graph = search_graph(&snarl, &wire_costs(&snarl));
+    let result = match algorithm::shortest_path(&graph, start, finish) {
+        Ok(result) => result,
+        Err(err @ PathError::NoPath) => {
+            eprintln!("{}", err);
+            return NO_PATH;
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            return INVALID_FILE;
+        }
+    };
+
+    let graph = model::Graph::from_snarl(&snarl);
+    if json {
+        let run = RunResult {
+            algorithm: "dijkstra",
+            path: result.path,
+            distances: result.distances,
+            predecessors: result.predecessors,
+            total: result.total,
+            finished_at: web_time::SystemTime::now(),
+        };
+        println!("{}", report::to_json(&graph, &run));
+    } else {
+        let labels: Vec<String> = result
+            .path
+            .iter()
+            .map(|node| {
+                graph
+                    .node(*node)
+                    .map_or(String::new(), |node| node.display_label())
+            })
+            .collect();
+        println!("Path: {}", labels.join(" -> "));
+        println!("Total cost: {}", format_cost(result.total));
+    }
+    0
+}
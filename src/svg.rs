@@ -0,0 +1,316 @@
+// Vector export of the graph, drawn from node rects and connections rather than a screenshot
+
+use std::collections::HashMap;
+
+use egui::{Color32, Pos2, Rect, Vec2};
+use egui_snarl::NodeId;
+use quick_xml::escape::escape;
+
+use crate::algorithm::format_cost;
+use crate::model::{Graph, Node};
+
+// Matches the default wire frame of the snarl style, three times the pin size
+const WIRE_FRAME_SIZE: f32 = 32.0;
+// Used for nodes that have never been drawn
+const FALLBACK_NODE_SIZE: Vec2 = Vec2::new(100.0, 50.0);
+const MARGIN: f32 = 40.0;
+const HEADER_HEIGHT: f32 = 22.0;
+const WIRE_SAMPLES: usize = 32;
+// Height of a legend row below the graph
+const LEGEND_ROW: f32 = 18.0;
+// Opacity of the nodes off the path with `dim_off_path`
+const DIMMED_OPACITY: f32 = 0.35;
+
+// Taken from the appearance settings, so exports look like the canvas
+pub struct Options {
+    // The colors are explained below the graph
+    pub legend: bool,
+    pub path_color: Color32,
+    // Multiplies the width of the path's connections
+    pub path_width: f32,
+    pub dim_off_path: bool,
+}
+
+fn hex(color: Color32) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+// Control points of the wire, following the shape of `WireStyle::Bezier5`
+fn wire_points(from: Pos2, to: Pos2) -> [Pos2; 6] {
+    let from_2 = Pos2::new(from.x + WIRE_FRAME_SIZE, from.y);
+    let to_2 = Pos2::new(to.x - WIRE_FRAME_SIZE, to.y);
+    if from_2.x <= to_2.x {
+        let middle_1 = from_2 + (to_2 - from_2).normalized() * WIRE_FRAME_SIZE;
+        let middle_2 = to_2 + (from_2 - to_2).normalized() * WIRE_FRAME_SIZE;
+        [from, from_2, middle_1, middle_2, to_2, to]
+    } else {
+        // Loop around when the target is behind the source
+        let dir = if to_2.y >= from_2.y { 1.0 } else { -1.0 };
+        let middle_1 = Pos2::new(from_2.x, from_2.y + WIRE_FRAME_SIZE * dir);
+        let middle_2 = Pos2::new(to_2.x, to_2.y - WIRE_FRAME_SIZE * dir);
+        [from, from_2, middle_1, middle_2, to_2, to]
+    }
+}
+
+// Point on the curve with de Casteljau's algorithm
+fn bezier_at(points: &[Pos2; 6], t: f32) -> Pos2 {
+    let mut points = *points;
+    for len in (1..points.len()).rev() {
+        for idx in 0..len {
+            points[idx] = points[idx].lerp(points[idx + 1], t);
+        }
+    }
+    points[0]
+}
+
+pub fn to_svg(
+    graph: &Graph,
+    rects: &HashMap<NodeId, Rect>,
+    path: &[NodeId],
+    options: &Options,
+) -> String {
+    let path_color = hex(options.path_color);
+    let rect_of = |node: &Node| {
+        rects.get(&node.id).copied().unwrap_or(Rect::from_min_size(
+            node.pos.unwrap_or_default(),
+            FALLBACK_NODE_SIZE,
+        ))
+    };
+    let nodes: Vec<(Rect, &Node)> = graph
+        .nodes
+        .iter()
+        .map(|node| (rect_of(node), node))
+        .collect();
+    let node_rects: HashMap<NodeId, Rect> =
+        nodes.iter().map(|(rect, node)| (node.id, *rect)).collect();
+    let bounds = nodes
+        .iter()
+        .map(|(rect, _)| *rect)
+        .reduce(|a, b| a.union(b))
+        .unwrap_or(Rect::from_min_size(Pos2::ZERO, FALLBACK_NODE_SIZE))
+        .expand(MARGIN);
+    let mut legend_rows = vec![("#555555", "Connection")];
+    if !path.is_empty() {
+        legend_rows.push((path_color.as_str(), "Shortest path"));
+    }
+    let legend_top = bounds.max.y;
+    let bounds = if options.legend {
+        Rect::from_min_max(
+            bounds.min,
+            bounds.max + Vec2::new(0.0, LEGEND_ROW * legend_rows.len() as f32 + MARGIN / 2.0),
+        )
+    } else {
+        bounds
+    };
+
+    let mut svg = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\" width=\"{}\" height=\"{}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+        bounds.min.x,
+        bounds.min.y,
+        bounds.width(),
+        bounds.height(),
+        bounds.width(),
+        bounds.height()
+    );
+    svg.push_str(&format!(
+        "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"white\"/>\n",
+        bounds.min.x,
+        bounds.min.y,
+        bounds.width(),
+        bounds.height()
+    ));
+
+    let on_path =
+        |from: NodeId, to: NodeId| path.windows(2).any(|pair| pair[0] == from && pair[1] == to);
+    let mut labels = String::new();
+    for edge in &graph.edges {
+        let (from, to) = (edge.from, edge.to);
+        let (Some(from_rect), Some(to_rect)) = (node_rects.get(&from), node_rects.get(&to)) else {
+            continue;
+        };
+        let points = wire_points(from_rect.right_center(), to_rect.left_center());
+        let mut d = format!("M {} {}", points[0].x, points[0].y);
+        for idx in 1..=WIRE_SAMPLES {
+            let point = bezier_at(&points, idx as f32 / WIRE_SAMPLES as f32);
+            d.push_str(&format!(" L {} {}", point.x, point.y));
+        }
+        let (color, width) = if on_path(from, to) {
+            (path_color.as_str(), 3.0 * options.path_width)
+        } else {
+            ("#555555", 1.5)
+        };
+        svg.push_str(&format!(
+            "  <path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"/>\n",
+            d, color, width
+        ));
+
+        if let Some(cost) = edge.cost {
+            let middle = bezier_at(&points, 0.5);
+            labels.push_str(&format!(
+                "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" dominant-baseline=\"middle\" fill=\"{}\" stroke=\"white\" stroke-width=\"3\" paint-order=\"stroke\">{}</text>\n",
+                middle.x,
+                middle.y,
+                color,
+                format_cost(cost)
+            ));
+        }
+    }
+
+    for (rect, node) in &nodes {
+        let on_path = path.contains(&node.id);
+        let stroke = if on_path {
+            path_color.as_str()
+        } else {
+            "#333333"
+        };
+        let opacity = if options.dim_off_path && !path.is_empty() && !on_path {
+            format!(" opacity=\"{}\"", DIMMED_OPACITY)
+        } else {
+            String::new()
+        };
+        svg.push_str(&format!("  <g{}>\n", opacity));
+        svg.push_str(&format!(
+            "    <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"6\" fill=\"#f4f4f4\" stroke=\"{}\" stroke-width=\"1.5\"/>\n",
+            rect.min.x,
+            rect.min.y,
+            rect.width(),
+            rect.height(),
+            stroke
+        ));
+        svg.push_str(&format!(
+            "    <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" dominant-baseline=\"middle\">{}</text>\n",
+            rect.center().x,
+            rect.min.y + HEADER_HEIGHT / 2.0,
+            escape(node.title())
+        ));
+        svg.push_str("  </g>\n");
+    }
+    // Labels go last so nodes don't cover them
+    svg.push_str(&labels);
+    if options.legend {
+        for (idx, (color, text)) in legend_rows.iter().enumerate() {
+            let y = legend_top + LEGEND_ROW * (idx as f32 + 0.5);
+            let x = bounds.min.x + MARGIN;
+            svg.push_str(&format!(
+                "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"3\"/>\n",
+                x,
+                y,
+                x + 24.0,
+                y,
+                color
+            ));
+            svg.push_str(&format!(
+                "  <text x=\"{}\" y=\"{}\" dominant-baseline=\"middle\">{}</text>\n",
+                x + 32.0,
+                y,
+                text
+            ));
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use quick_xml::Reader;
+    use quick_xml::events::Event;
+
+    use super::*;
+    use crate::model::Role;
+
+    fn graph() -> Graph {
+        let mut graph = Graph::default();
+        graph.add_node(Role::Start, String::new());
+        graph.add_node(Role::Distance, "<A & B>".to_string());
+        graph.add_node(Role::Finish, String::new());
+        for (idx, pos) in [(0.0, 0.0), (200.0, -50.0), (400.0, 100.0)]
+            .into_iter()
+            .enumerate()
+        {
+            graph.nodes[idx].pos = Some(Pos2::new(pos.0, pos.1));
+        }
+        graph.add_edge(0, 1, Some(12.0));
+        graph.add_edge(1, 2, Some(7.5));
+        graph
+    }
+
+    fn options(legend: bool) -> Options {
+        Options {
+            legend,
+            path_color: Color32::from_rgb(255, 0, 0),
+            path_width: 1.0,
+            dim_off_path: true,
+        }
+    }
+
+    // Parse the whole document, returning the viewBox and the text content
+    fn parse(svg: &str) -> ([f32; 4], String) {
+        let mut reader = Reader::from_str(svg);
+        let (mut view_box, mut text) = (None, String::new());
+        loop {
+            match reader.read_event().expect("well-formed SVG") {
+                Event::Start(element) if element.local_name().as_ref() == b"svg" => {
+                    let value = element
+                        .try_get_attribute("viewBox")
+                        .unwrap()
+                        .expect("the root has a viewBox")
+                        .unescape_value()
+                        .unwrap()
+                        .into_owned();
+                    let numbers: Vec<f32> = value
+                        .split(' ')
+                        .map(|number| number.parse().unwrap())
+                        .collect();
+                    view_box = Some([numbers[0], numbers[1], numbers[2], numbers[3]]);
+                }
+                Event::Text(content) => text.push_str(&content.unescape().unwrap()),
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        (view_box.expect("an svg element"), text)
+    }
+
+    #[test]
+    fn is_well_formed_with_escaped_labels() {
+        let path = [NodeId(0), NodeId(1), NodeId(2)];
+        let (_, text) = parse(&to_svg(&graph(), &HashMap::new(), &path, &options(true)));
+        assert!(text.contains("<A & B>"));
+        assert!(text.contains("Shortest path"));
+    }
+
+    #[test]
+    fn view_box_surrounds_the_nodes() {
+        let (view_box, _) = parse(&to_svg(&graph(), &HashMap::new(), &[], &options(false)));
+        // From the top-left of the highest node to the bottom-right of the last one
+        let (width, height) = (FALLBACK_NODE_SIZE.x, FALLBACK_NODE_SIZE.y);
+        assert_eq!(
+            view_box,
+            [
+                -MARGIN,
+                -50.0 - MARGIN,
+                400.0 + width + 2.0 * MARGIN,
+                150.0 + height + 2.0 * MARGIN
+            ]
+        );
+
+        // Drawn rects win over the saved positions
+        let rects = HashMap::from([(
+            NodeId(2),
+            Rect::from_min_size(Pos2::new(400.0, 100.0), Vec2::new(300.0, 80.0)),
+        )]);
+        let (view_box, _) = parse(&to_svg(&graph(), &rects, &[], &options(false)));
+        assert_eq!(view_box[2], 700.0 + 2.0 * MARGIN);
+        assert_eq!(view_box[3], 230.0 + 2.0 * MARGIN);
+    }
+
+    #[test]
+    fn legend_extends_the_view_box() {
+        let (without, _) = parse(&to_svg(&graph(), &HashMap::new(), &[], &options(false)));
+        let (with, _) = parse(&to_svg(&graph(), &HashMap::new(), &[], &options(true)));
+        assert_eq!(with[..3], without[..3]);
+        // Only the connection row without a path
+        assert_eq!(with[3], without[3] + LEGEND_ROW + MARGIN / 2.0);
+    }
+}
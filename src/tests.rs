@@ -0,0 +1,567 @@
+// Editor behavior that needs the viewer and a snarl, without drawing anything
+
+use std::collections::HashMap;
+
+use egui::{Pos2, Rect, Vec2};
+use egui_snarl::{InPinId, NodeId, OutPinId, Snarl};
+
+use crate::settings::Rounding;
+use crate::{Cost, CostScale, DijkstraNode, DijkstraViewer, NodeKind};
+
+fn distance() -> DijkstraNode {
+    DijkstraNode::new(NodeKind::Distance(HashMap::new()))
+}
+
+// Connect through the viewer, the same way a dragged wire is
+fn connect(
+    viewer: &mut DijkstraViewer,
+    snarl: &mut Snarl<DijkstraNode>,
+    from: NodeId,
+    to: NodeId,
+) -> bool {
+    let from = snarl.out_pin(OutPinId {
+        node: from,
+        output: 0,
+    });
+    let to = snarl.in_pin(InPinId { node: to, input: 0 });
+    viewer.connect_pins(&from, &to, snarl)
+}
+
+#[test]
+fn self_loops_are_refused() {
+    let (mut viewer, mut snarl) = (DijkstraViewer::new(), Snarl::new());
+    let node = snarl.insert_node(Pos2::ZERO, distance());
+    assert!(!connect(&mut viewer, &mut snarl, node, node));
+    assert_eq!(snarl.wires().count(), 0);
+}
+
+#[test]
+fn duplicate_connections_are_refused() {
+    let (mut viewer, mut snarl) = (DijkstraViewer::new(), Snarl::new());
+    let a = snarl.insert_node(Pos2::ZERO, distance());
+    let b = snarl.insert_node(Pos2::new(200.0, 0.0), distance());
+    assert!(connect(&mut viewer, &mut snarl, a, b));
+    assert!(!connect(&mut viewer, &mut snarl, a, b));
+    assert_eq!(snarl.wires().count(), 1);
+    // The way back is a different connection
+    assert!(connect(&mut viewer, &mut snarl, b, a));
+    assert_eq!(snarl.wires().count(), 2);
+}
+
+#[test]
+fn roles_limit_connections() {
+    let (mut viewer, mut snarl) = (DijkstraViewer::new(), Snarl::new());
+    let start = snarl.insert_node(Pos2::ZERO, DijkstraNode::new(NodeKind::Start));
+    let finish = snarl.insert_node(
+        Pos2::new(400.0, 0.0),
+        DijkstraNode::new(NodeKind::Finish(HashMap::new())),
+    );
+    let middle = snarl.insert_node(Pos2::new(200.0, 0.0), distance());
+    assert!(!connect(&mut viewer, &mut snarl, start, finish));
+    assert!(connect(&mut viewer, &mut snarl, start, middle));
+    assert!(connect(&mut viewer, &mut snarl, middle, finish));
+    assert_eq!(snarl.wires().count(), 2);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn saved_dir_is_created() {
+    let base = tempfile::tempdir().unwrap();
+    let dir = crate::saved_dir_in(Some(base.path())).unwrap();
+    assert_eq!(dir, base.path().join("saved"));
+    assert!(dir.is_dir());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn existing_saved_dir_is_kept() {
+    let base = tempfile::tempdir().unwrap();
+    let existing = base.path().join("saved");
+    std::fs::create_dir(&existing).unwrap();
+    std::fs::write(existing.join("graph.json"), "{}").unwrap();
+    assert_eq!(
+        crate::saved_dir_in(Some(base.path())),
+        Some(existing.clone())
+    );
+    assert!(existing.join("graph.json").exists());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn saved_dir_falls_back_when_it_cant_be_created() {
+    // A file where the folder would go
+    let base = tempfile::tempdir().unwrap();
+    std::fs::write(base.path().join("saved"), "").unwrap();
+    let fallback = dirs::document_dir().or_else(dirs::home_dir);
+    assert_eq!(crate::saved_dir_in(Some(base.path())), fallback);
+    assert_eq!(crate::saved_dir_in(None), fallback);
+}
+
+#[test]
+fn every_connection_of_a_busy_node_is_searched() {
+    let (mut viewer, mut snarl) = (DijkstraViewer::new(), Snarl::new());
+    let hub = snarl.insert_node(Pos2::ZERO, distance());
+    let mut wires = Vec::new();
+    for i in 0..12 {
+        let source = snarl.insert_node(Pos2::new(-200.0, i as f32 * 60.0), distance());
+        let target = snarl.insert_node(Pos2::new(200.0, i as f32 * 60.0), distance());
+        assert!(connect(&mut viewer, &mut snarl, source, hub));
+        assert!(connect(&mut viewer, &mut snarl, hub, target));
+        wires.extend([(source, hub), (hub, target)]);
+    }
+    let adjacency = crate::wire_costs(&snarl);
+    let mut found: Vec<(NodeId, NodeId)> =
+        adjacency.iter().map(|(from, to, _)| (*from, *to)).collect();
+    found.sort();
+    wires.sort();
+    assert_eq!(found, wires);
+
+    let graph = crate::search_graph(&snarl, &adjacency);
+    for (from, to) in &wires {
+        let path = crate::algorithm::shortest_path(&graph, *from, *to).unwrap();
+        assert_eq!(path.path, [*from, *to]);
+    }
+}
+
+// What `final_node_rect` does once a node is drawn at `pos`
+fn place(viewer: &mut DijkstraViewer, node: NodeId, pos: Pos2) {
+    let rect = Rect::from_min_size(pos, Vec2::new(100.0, 50.0));
+    if viewer.stored_nodes.insert(node, rect) != Some(rect) {
+        viewer.moved_nodes.insert(node);
+    }
+}
+
+fn finish_costs(snarl: &Snarl<DijkstraNode>, finish: NodeId) -> HashMap<NodeId, Cost> {
+    match &snarl[finish].kind {
+        NodeKind::Finish(costs) => costs.clone(),
+        _ => unreachable!(),
+    }
+}
+
+// Start, two Distance nodes in parallel and the Finish, all drawn and with their costs
+fn diamond() -> (DijkstraViewer, Snarl<DijkstraNode>, [NodeId; 4]) {
+    let (mut viewer, mut snarl) = (DijkstraViewer::new(), Snarl::new());
+    let start = snarl.insert_node(Pos2::ZERO, DijkstraNode::new(NodeKind::Start));
+    let upper = snarl.insert_node(Pos2::new(200.0, 0.0), distance());
+    let lower = snarl.insert_node(Pos2::new(200.0, 200.0), distance());
+    let finish = snarl.insert_node(
+        Pos2::new(400.0, 0.0),
+        DijkstraNode::new(NodeKind::Finish(HashMap::new())),
+    );
+    for (from, to) in [
+        (start, upper),
+        (upper, finish),
+        (start, lower),
+        (lower, finish),
+    ] {
+        assert!(connect(&mut viewer, &mut snarl, from, to));
+    }
+    for node in [start, upper, lower, finish] {
+        place(&mut viewer, node, snarl.get_node_info(node).unwrap().pos);
+    }
+    assert!(viewer.update_costs(&mut snarl));
+    (viewer, snarl, [start, upper, lower, finish])
+}
+
+#[test]
+fn costs_refresh_after_removing_a_path_node() {
+    let (mut viewer, mut snarl, [start, upper, lower, finish]) = diamond();
+    // 100 pixels straight across, and 100 across and 200 down
+    assert_eq!(
+        finish_costs(&snarl, finish),
+        HashMap::from([(upper, 10.0), (lower, 22.0)])
+    );
+    viewer.path_nodes = vec![start, upper, finish];
+
+    viewer.remove_nodes(&[upper], &mut snarl);
+    assert!(!viewer.stored_nodes.contains_key(&upper));
+    assert!(viewer.path_nodes.is_empty());
+    viewer.update_costs(&mut snarl);
+    assert_eq!(finish_costs(&snarl, finish), HashMap::from([(lower, 22.0)]));
+
+    // A node connected where the removed one was gets its costs like any other
+    let replacement = snarl.insert_node(Pos2::new(200.0, 0.0), distance());
+    assert!(connect(&mut viewer, &mut snarl, start, replacement));
+    assert!(connect(&mut viewer, &mut snarl, replacement, finish));
+    place(&mut viewer, replacement, Pos2::new(200.0, 0.0));
+    assert!(viewer.update_costs(&mut snarl));
+    assert_eq!(
+        finish_costs(&snarl, finish),
+        HashMap::from([(replacement, 10.0), (lower, 22.0)])
+    );
+}
+
+fn rects(nodes: &[(NodeId, Pos2)]) -> HashMap<NodeId, Rect> {
+    nodes
+        .iter()
+        .map(|(node, pos)| (*node, Rect::from_min_size(*pos, Vec2::new(100.0, 50.0))))
+        .collect()
+}
+
+#[test]
+fn incoming_costs_are_at_least_the_minimum() {
+    let mut snarl = Snarl::new();
+    let (near, far, node) = (
+        snarl.insert_node(Pos2::ZERO, distance()),
+        snarl.insert_node(Pos2::ZERO, distance()),
+        snarl.insert_node(Pos2::ZERO, distance()),
+    );
+    // Touching the node, and 300 pixels away
+    let rects = rects(&[
+        (near, Pos2::new(0.0, 0.0)),
+        (far, Pos2::new(-200.0, 0.0)),
+        (node, Pos2::new(100.0, 0.0)),
+    ]);
+    let scale = CostScale::default();
+    let costs = crate::compute_incoming_costs(node, &[near, far], &rects, &snarl, scale);
+    assert_eq!(costs, HashMap::from([(near, 1.0), (far, 20.0)]));
+
+    let scale = CostScale {
+        min_cost: 25,
+        ..scale
+    };
+    let costs = crate::compute_incoming_costs(node, &[near, far], &rects, &snarl, scale);
+    assert_eq!(costs, HashMap::from([(near, 25.0), (far, 25.0)]));
+}
+
+#[test]
+fn sources_without_rects_get_no_cost() {
+    let mut snarl = Snarl::new();
+    let drawn = snarl.insert_node(Pos2::ZERO, distance());
+    let hidden = snarl.insert_node(Pos2::ZERO, distance());
+    let manual = snarl.insert_node(Pos2::ZERO, distance());
+    let node = snarl.insert_node(Pos2::ZERO, distance());
+    snarl[node].meta.overrides.insert(manual, 7.0);
+    let rects = rects(&[(drawn, Pos2::ZERO), (node, Pos2::new(200.0, 0.0))]);
+    let sources = [drawn, hidden, manual];
+    let costs = crate::compute_incoming_costs(node, &sources, &rects, &snarl, CostScale::default());
+    // Manual costs don't need the rect
+    assert_eq!(costs, HashMap::from([(drawn, 10.0), (manual, 7.0)]));
+
+    // Nothing can be measured before the node itself is drawn
+    let mut rects = rects;
+    rects.remove(&node);
+    let costs = crate::compute_incoming_costs(node, &sources, &rects, &snarl, CostScale::default());
+    assert_eq!(costs, HashMap::from([(manual, 7.0)]));
+}
+
+// The whole app, driven frame by frame without a window
+mod app {
+    use std::sync::atomic::Ordering;
+
+    use egui::accesskit::Role;
+    use egui_kittest::Harness;
+    use egui_kittest::kittest::Queryable;
+
+    use super::*;
+    use crate::DijkstraApp;
+    use crate::i18n::{Text, tr};
+
+    fn harness() -> Harness<'static, DijkstraApp> {
+        let mut harness = Harness::builder()
+            .with_size(Vec2::new(1200.0, 800.0))
+            .build_eframe(|cc| DijkstraApp::new(cc, None));
+        // Windows that open by themselves would cover the graph
+        harness.state_mut().show_help = false;
+        harness.state_mut().show_inspector = false;
+        harness.run_steps(2);
+        harness
+    }
+
+    fn button_exists(harness: &Harness<'_, DijkstraApp>, label: &str) -> bool {
+        harness
+            .query_by(|node| node.role() == Role::Button && node.label().as_deref() == Some(label))
+            .is_some()
+    }
+
+    // Right click on the graph, away from the panels
+    fn open_graph_menu(harness: &mut Harness<'_, DijkstraApp>, pos: Pos2) {
+        let input = harness.input_mut();
+        input.events.push(egui::Event::PointerMoved(pos));
+        for pressed in [true, false] {
+            input.events.push(egui::Event::PointerButton {
+                pos,
+                button: egui::PointerButton::Secondary,
+                pressed,
+                modifiers: egui::Modifiers::NONE,
+            });
+        }
+        harness.run_steps(2);
+    }
+
+    #[test]
+    fn costs_are_measured_and_the_path_is_found() {
+        let mut harness = harness();
+        let app = harness.state_mut();
+        let start = app
+            .snarl
+            .insert_node(Pos2::new(0.0, 0.0), DijkstraNode::new(NodeKind::Start));
+        let middle = app.snarl.insert_node(Pos2::new(250.0, 0.0), distance());
+        let finish = app.snarl.insert_node(
+            Pos2::new(500.0, 0.0),
+            DijkstraNode::new(NodeKind::Finish(HashMap::new())),
+        );
+        app.connect_nodes(start, middle);
+        app.connect_nodes(middle, finish);
+        // Drawing the nodes reports their rects, the costs follow from those
+        harness.run_steps(3);
+        let app = harness.state();
+        let measured = |node: NodeId, remote: NodeId| match &app.snarl[node].kind {
+            NodeKind::Distance(costs) | NodeKind::Finish(costs) => costs.get(&remote).copied(),
+            NodeKind::Start => None,
+        };
+        let first = measured(middle, start).expect("the first connection has a cost");
+        let second = measured(finish, middle).expect("the second connection has a cost");
+        assert!(first > 1.0 && second > 1.0);
+
+        harness.get_by_label(tr(Text::RunDijkstra)).click();
+        // The search runs on the worker, its result arrives in a later frame
+        for _ in 0..200 {
+            harness.step();
+            if harness.state().total_cost.is_some() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        let app = harness.state();
+        assert_eq!(app.viewer.path_nodes, [start, middle, finish]);
+        assert_eq!(app.total_cost, Some(first + second));
+    }
+
+    #[test]
+    fn only_one_start_can_be_added() {
+        let mut harness = harness();
+        open_graph_menu(&mut harness, Pos2::new(900.0, 500.0));
+        assert!(button_exists(&harness, tr(Text::Start)));
+        harness
+            .get_by(|node| {
+                node.role() == Role::Button && node.label().as_deref() == Some(tr(Text::Start))
+            })
+            .click();
+        harness.run_steps(2);
+        let starts = harness
+            .state()
+            .snarl
+            .nodes()
+            .filter(|node| matches!(node.kind, NodeKind::Start))
+            .count();
+        assert_eq!(starts, 1);
+
+        // Not where the new node now is
+        open_graph_menu(&mut harness, Pos2::new(700.0, 300.0));
+        assert!(button_exists(&harness, tr(Text::Value)));
+        assert!(!button_exists(&harness, tr(Text::Start)));
+    }
+
+    fn positions(app: &DijkstraApp) -> Vec<(NodeId, Pos2)> {
+        let mut positions: Vec<_> = app
+            .snarl
+            .nodes_pos_ids()
+            .map(|(id, pos, _)| (id, pos))
+            .collect();
+        positions.sort_by_key(|(id, _)| *id);
+        positions
+    }
+
+    #[test]
+    fn moving_the_view_keeps_the_positions() {
+        let mut harness = harness();
+        let app = harness.state_mut();
+        let near = app.snarl.insert_node(Pos2::new(0.0, 0.0), distance());
+        let far = app.snarl.insert_node(Pos2::new(3000.0, 2000.0), distance());
+        app.snarl[far].meta.locked = true;
+        harness.run_steps(3);
+        let before = positions(harness.state());
+
+        harness.state_mut().center_on(far);
+        harness.run_steps(3);
+        let app = harness.state();
+        assert_eq!(positions(app), before);
+        // The node is drawn in the middle of the view
+        let view = app.viewer.view.unwrap();
+        let center = view.screen_pos_to_graph(view.rect.center());
+        assert!(center.distance(app.viewer.stored_nodes[&far].center()) < 1.0);
+
+        harness.press_key(egui::Key::Home);
+        harness.run_steps(3);
+        assert_eq!(positions(harness.state()), before);
+        harness.state_mut().center_on(near);
+        harness.run_steps(3);
+        assert_eq!(positions(harness.state()), before);
+    }
+
+    #[test]
+    fn cancelled_runs_are_not_restarted() {
+        let mut harness = harness();
+        let app = harness.state_mut();
+        let start = app
+            .snarl
+            .insert_node(Pos2::new(0.0, 0.0), DijkstraNode::new(NodeKind::Start));
+        let middle = app.snarl.insert_node(Pos2::new(250.0, 0.0), distance());
+        let finish = app.snarl.insert_node(
+            Pos2::new(500.0, 0.0),
+            DijkstraNode::new(NodeKind::Finish(HashMap::new())),
+        );
+        app.connect_nodes(start, middle);
+        app.connect_nodes(middle, finish);
+        harness.run_steps(3);
+
+        // Cancelled, then the graph changes before the reply arrives
+        let app = harness.state_mut();
+        app.start_search(true);
+        let started = app.next_search_id;
+        let pending = app.pending_search.as_ref().unwrap();
+        pending.progress.cancelled.store(true, Ordering::Relaxed);
+        app.viewer.mark_changed();
+        for _ in 0..200 {
+            harness.step();
+            if harness.state().pending_search.is_none() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        harness.run_steps(3);
+        let app = harness.state();
+        assert!(app.pending_search.is_none());
+        assert_eq!(app.next_search_id, started);
+        assert_eq!(app.total_cost, None);
+    }
+
+    #[test]
+    fn reloading_keeps_the_view() {
+        let mut harness = harness();
+        let app = harness.state_mut();
+        let near = app.snarl.insert_node(Pos2::new(0.0, 0.0), distance());
+        let far = app.snarl.insert_node(Pos2::new(3000.0, 2000.0), distance());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.json");
+        app.write_save(&path).unwrap();
+        harness.run_steps(3);
+
+        harness.state_mut().center_on(far);
+        harness.run_steps(3);
+        let shift = harness.state().viewer.view_shift;
+        assert!(harness.state_mut().load_path(path.clone()));
+        harness.run_steps(3);
+        assert_eq!(harness.state().viewer.view_shift, shift);
+
+        // Opening the file shows all of it
+        harness.state_mut().center_on(near);
+        harness.run_steps(3);
+        let shift = harness.state().viewer.view_shift;
+        harness.state_mut().open_path(path);
+        harness.run_steps(3);
+        assert_ne!(harness.state().viewer.view_shift, shift);
+    }
+
+    #[test]
+    fn failed_loads_keep_the_graph() {
+        let mut harness = harness();
+        let app = harness.state_mut();
+        let node = app.snarl.insert_node(Pos2::ZERO, distance());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.json");
+        app.write_save(&path).unwrap();
+        app.current_file = Some(path.clone());
+
+        std::fs::write(&path, "{ not a graph").unwrap();
+        assert!(!app.load_path(path.clone()));
+        assert!(!app.load_path(dir.path().join("missing.json")));
+        assert!(app.snarl.get_node(node).is_some());
+        assert_eq!(app.current_file, Some(path));
+    }
+
+    #[test]
+    fn aligning_updates_the_costs() {
+        let mut harness = harness();
+        let app = harness.state_mut();
+        let upper = app.snarl.insert_node(Pos2::new(0.0, 0.0), distance());
+        let lower = app.snarl.insert_node(Pos2::new(300.0, 400.0), distance());
+        app.connect_nodes(upper, lower);
+        harness.run_steps(3);
+        let cost = |app: &DijkstraApp| match &app.snarl[lower].kind {
+            NodeKind::Distance(costs) => costs[&upper],
+            _ => unreachable!(),
+        };
+        let before = cost(harness.state());
+
+        let app = harness.state_mut();
+        app.viewer.selected.extend([upper, lower]);
+        app.align_selected(crate::Alignment::Top);
+        harness.run_steps(2);
+        let app = harness.state();
+        assert_eq!(app.snarl.get_node_info(lower).unwrap().pos.y, 0.0);
+        assert!(cost(app) < before);
+    }
+}
+
+#[test]
+fn reused_ids_start_without_costs() {
+    let (mut viewer, mut snarl, [start, upper, lower, finish]) = diamond();
+    snarl[finish].meta.overrides.insert(upper, 3.0);
+    viewer.remove_nodes(&[upper], &mut snarl);
+
+    // The snarl hands out the free id again
+    let reused = snarl.insert_node(Pos2::new(200.0, 0.0), distance());
+    assert_eq!(reused, upper);
+    assert!(!finish_costs(&snarl, finish).contains_key(&reused));
+    assert!(!snarl[finish].meta.overrides.contains_key(&reused));
+
+    // Connected again, it is measured rather than inheriting the manual cost
+    assert!(connect(&mut viewer, &mut snarl, start, reused));
+    assert!(connect(&mut viewer, &mut snarl, reused, finish));
+    place(&mut viewer, reused, Pos2::new(200.0, 0.0));
+    viewer.update_costs(&mut snarl);
+    assert_eq!(
+        finish_costs(&snarl, finish),
+        HashMap::from([(reused, 10.0), (lower, 22.0)])
+    );
+}
+
+#[test]
+fn moves_after_a_removal_update_the_costs() {
+    let (mut viewer, mut snarl, [start, upper, lower, finish]) = diamond();
+    viewer.remove_nodes(&[upper], &mut snarl);
+    viewer.update_costs(&mut snarl);
+
+    // Up into the free row, both of its connections are 100 pixels long now
+    place(&mut viewer, lower, Pos2::new(200.0, 0.0));
+    assert!(viewer.update_costs(&mut snarl));
+    assert_eq!(finish_costs(&snarl, finish), HashMap::from([(lower, 10.0)]));
+    assert_eq!(
+        snarl[lower].kind,
+        NodeKind::Distance(HashMap::from([(start, 10.0)]))
+    );
+}
+
+#[test]
+fn incoming_costs_follow_the_rounding() {
+    let mut snarl = Snarl::new();
+    let sources = [
+        snarl.insert_node(Pos2::ZERO, distance()),
+        snarl.insert_node(Pos2::ZERO, distance()),
+        snarl.insert_node(Pos2::ZERO, distance()),
+    ];
+    let node = snarl.insert_node(Pos2::ZERO, distance());
+    // Wires 121, 125 and 130 pixels long
+    let rects = rects(&[
+        (sources[0], Pos2::new(-221.0, 0.0)),
+        (sources[1], Pos2::new(-225.0, 0.0)),
+        (sources[2], Pos2::new(-230.0, 0.0)),
+        (node, Pos2::ZERO),
+    ]);
+    let costs = |rounding: Rounding, fractional: bool| {
+        let scale = CostScale {
+            rounding,
+            fractional,
+            ..CostScale::default()
+        };
+        let costs = crate::compute_incoming_costs(node, &sources, &rects, &snarl, scale);
+        sources.map(|source| costs[&source])
+    };
+    assert_eq!(costs(Rounding::Floor, false), [12.0, 12.0, 13.0]);
+    assert_eq!(costs(Rounding::Round, false), [12.0, 13.0, 13.0]);
+    assert_eq!(costs(Rounding::Ceil, false), [13.0, 13.0, 13.0]);
+    // Fractional costs aren't rounded at all
+    assert_eq!(costs(Rounding::Ceil, true), [12.1, 12.5, 13.0]);
+}
@@ -0,0 +1,128 @@
+// Step-by-step log of a run, entries only hold ids and numbers and are turned into
+// text when exported, so recording costs next to nothing
+
+use egui_snarl::NodeId;
+use serde_json::json;
+
+use crate::algorithm::{Cost, format_cost};
+use crate::model::Graph;
+use crate::save::CostNumber;
+
+pub enum TraceEntry {
+    Settled {
+        node: NodeId,
+        dist: Cost,
+    },
+    // Every edge looked at from a settled node, `previous` is infinite while unreached
+    Relaxed {
+        from: NodeId,
+        to: NodeId,
+        dist: Cost,
+        cost: Cost,
+        previous: Cost,
+    },
+    SkippedStale {
+        node: NodeId,
+        cost: Cost,
+    },
+}
+
+fn label(graph: &Graph, node: NodeId) -> String {
+    graph
+        .node(node)
+        .map_or(format!("Removed {}", node.0), |value| value.display_label())
+}
+
+fn distance(dist: Cost) -> String {
+    if dist.is_finite() {
+        format_cost(dist)
+    } else {
+        "∞".to_string()
+    }
+}
+
+// One line per entry, like "relaxed edge 4→7: 12+5=17 improves 21"
+pub fn to_text(graph: &Graph, trace: &[TraceEntry]) -> String {
+    let mut text = String::new();
+    for entry in trace {
+        let line = match *entry {
+            TraceEntry::Settled { node, dist } => {
+                format!(
+                    "settled node {} ({}) at distance {}",
+                    node.0,
+                    label(graph, node),
+                    format_cost(dist)
+                )
+            }
+            TraceEntry::Relaxed {
+                from,
+                to,
+                dist,
+                cost,
+                previous,
+            } => {
+                let verdict = if dist + cost < previous {
+                    "improves"
+                } else {
+                    "does not improve"
+                };
+                format!(
+                    "relaxed edge {}→{}: {}+{}={} {} {}",
+                    from.0,
+                    to.0,
+                    format_cost(dist),
+                    format_cost(cost),
+                    format_cost(dist + cost),
+                    verdict,
+                    distance(previous)
+                )
+            }
+            TraceEntry::SkippedStale { node, cost } => {
+                format!(
+                    "skipped stale heap entry for node {} at distance {}",
+                    node.0,
+                    format_cost(cost)
+                )
+            }
+        };
+        text.push_str(&line);
+        text.push('\n');
+    }
+    text
+}
+
+pub fn to_json(graph: &Graph, trace: &[TraceEntry]) -> String {
+    let steps: Vec<_> = trace
+        .iter()
+        .map(|entry| match *entry {
+            TraceEntry::Settled { node, dist } => json!({
+                "event": "settled",
+                "node": node.0,
+                "label": label(graph, node),
+                "distance": CostNumber(dist),
+            }),
+            TraceEntry::Relaxed {
+                from,
+                to,
+                dist,
+                cost,
+                previous,
+            } => json!({
+                "event": "relaxed",
+                "from": from.0,
+                "to": to.0,
+                "distance": CostNumber(dist),
+                "edge_cost": CostNumber(cost),
+                "candidate": CostNumber(dist + cost),
+                "previous": previous.is_finite().then_some(CostNumber(previous)),
+                "improves": dist + cost < previous,
+            }),
+            TraceEntry::SkippedStale { node, cost } => json!({
+                "event": "skipped_stale",
+                "node": node.0,
+                "distance": CostNumber(cost),
+            }),
+        })
+        .collect();
+    serde_json::to_string_pretty(&steps).unwrap_or_default()
+}
@@ -0,0 +1,69 @@
+// Points along the wires, for what is drawn on top of them like the path pulse and the
+// arrowheads. The snarl doesn't expose its wire curves, so they are rebuilt here the way it
+// lays them out: exactly for wires going left to right, the usual case, and close enough for
+// wires that turn back.
+
+use egui::{Pos2, pos2};
+use egui_snarl::ui::WireStyle;
+
+// The snarl's default frame is three pin sizes
+pub fn frame_size(style: &egui::Style, scale: f32) -> f32 {
+    style.spacing.interact_size.y * 1.8 * scale
+}
+
+// `frame_size` is the snarl's frame at the current zoom, the horizontal run out of each pin
+pub fn point_on_wire(style: WireStyle, frame_size: f32, from: Pos2, to: Pos2, t: f32) -> Pos2 {
+    // The snarl never lets the frame grow past a quarter of the wire
+    let frame_size = frame_size.min((to - from).length() / 4.0);
+    let from_2 = pos2(from.x + frame_size, from.y);
+    let to_2 = pos2(to.x - frame_size, to.y);
+    let (middle_1, middle_2) = if from_2.x <= to_2.x && (to_2 - from_2).length() >= frame_size * 2.0
+    {
+        (
+            from_2 + (to_2 - from_2).normalized() * frame_size,
+            to_2 + (from_2 - to_2).normalized() * frame_size,
+        )
+    } else {
+        (from_2, to_2)
+    };
+
+    match style {
+        WireStyle::Line => from.lerp(to, t),
+        WireStyle::Bezier3 => bezier(&[from, middle_1, middle_2, to], t),
+        WireStyle::Bezier5 => bezier(&[from, from_2, middle_1, middle_2, to_2, to], t),
+        // Out of the pin, across at the middle and into the other pin
+        WireStyle::AxisAligned { .. } => {
+            let x = (from_2.x + to_2.x) / 2.0;
+            polyline(&[from, pos2(x, from.y), pos2(x, to.y), to], t)
+        }
+    }
+}
+
+// De Casteljau, fine for the few points a wire has
+fn bezier(points: &[Pos2], t: f32) -> Pos2 {
+    let mut points = points.to_vec();
+    while points.len() > 1 {
+        points = points
+            .windows(2)
+            .map(|pair| pair[0].lerp(pair[1], t))
+            .collect();
+    }
+    points[0]
+}
+
+// At a constant speed along the segments
+fn polyline(points: &[Pos2], t: f32) -> Pos2 {
+    let total: f32 = points
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).length())
+        .sum();
+    let mut left = total * t;
+    for pair in points.windows(2) {
+        let length = (pair[1] - pair[0]).length();
+        if left <= length && length > 0.0 {
+            return pair[0].lerp(pair[1], left / length);
+        }
+        left -= length;
+    }
+    points[points.len() - 1]
+}
@@ -0,0 +1,105 @@
+// Thread running searches, so large graphs don't stall drawing. Browsers have no threads,
+// there the search runs right away and the reply is picked up the same way.
+
+use std::sync::Arc;
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::time::Duration;
+
+use egui_snarl::NodeId;
+use web_time::Instant;
+
+use crate::algorithm::{self, Graph, PathError, PathResult, Progress};
+use crate::trace::TraceEntry;
+
+pub struct SearchRequest {
+    pub id: u64,
+    pub graph: Graph,
+    pub start: NodeId,
+    pub finish: NodeId,
+    pub record_trace: bool,
+    // Read by the UI while the search runs, also how it is cancelled
+    pub progress: Arc<Progress>,
+}
+
+pub struct SearchResponse {
+    pub id: u64,
+    pub result: Result<PathResult, PathError>,
+    pub trace: Vec<TraceEntry>,
+    // Time spent in the algorithm itself
+    pub elapsed: Duration,
+}
+
+pub struct SearchWorker {
+    #[cfg(not(target_arch = "wasm32"))]
+    requests: Sender<SearchRequest>,
+    #[cfg(target_arch = "wasm32")]
+    outgoing: Sender<SearchResponse>,
+    responses: Receiver<SearchResponse>,
+}
+
+fn run(request: SearchRequest) -> SearchResponse {
+    let started = Instant::now();
+    let mut trace = Vec::new();
+    let result = algorithm::shortest_path_watched(
+        &request.graph,
+        request.start,
+        request.finish,
+        request.record_trace.then_some(&mut trace),
+        &request.progress,
+    );
+    SearchResponse {
+        id: request.id,
+        result,
+        trace,
+        elapsed: started.elapsed(),
+    }
+}
+
+impl SearchWorker {
+    // The worker asks for a repaint after every reply, so results show up without input
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spawn(ctx: egui::Context) -> Self {
+        let (requests, incoming) = channel::<SearchRequest>();
+        let (outgoing, responses) = channel();
+        std::thread::spawn(move || {
+            while let Ok(mut request) = incoming.recv() {
+                // Only the newest of the queued requests is still of interest
+                while let Ok(newer) = incoming.try_recv() {
+                    request = newer;
+                }
+                if outgoing.send(run(request)).is_err() {
+                    break;
+                }
+                ctx.request_repaint();
+            }
+        });
+        Self {
+            requests,
+            responses,
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn spawn(_ctx: egui::Context) -> Self {
+        let (outgoing, responses) = channel();
+        Self {
+            outgoing,
+            responses,
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn send(&self, request: SearchRequest) {
+        // The worker only stops once the app is gone
+        let _ = self.requests.send(request);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn send(&self, request: SearchRequest) {
+        let _ = self.outgoing.send(run(request));
+    }
+
+    pub fn try_recv(&self) -> Option<SearchResponse> {
+        self.responses.try_recv().ok()
+    }
+}